@@ -0,0 +1,45 @@
+// SPDX-License-Identifier: Unlicense
+pragma solidity ^0.8.13;
+
+/// Stands in for the Aligned service manager in local tests. It only accepts the batch inclusion
+/// it was deployed with, so a claim succeeds only if the caller encoded every argument correctly.
+contract MockAlignedServiceManager {
+    bytes32 public immutable proofCommitment;
+    bytes32 public immutable pubInputCommitment;
+    bytes20 public immutable proofGeneratorAddr;
+    bytes32 public immutable batchMerkleRoot;
+    bytes32 public immutable merkleProofHash;
+    uint256 public immutable verificationDataBatchIndex;
+
+    constructor(
+        bytes32 _proofCommitment,
+        bytes32 _pubInputCommitment,
+        bytes20 _proofGeneratorAddr,
+        bytes32 _batchMerkleRoot,
+        bytes32 _merkleProofHash,
+        uint256 _verificationDataBatchIndex
+    ) {
+        proofCommitment = _proofCommitment;
+        pubInputCommitment = _pubInputCommitment;
+        proofGeneratorAddr = _proofGeneratorAddr;
+        batchMerkleRoot = _batchMerkleRoot;
+        merkleProofHash = _merkleProofHash;
+        verificationDataBatchIndex = _verificationDataBatchIndex;
+    }
+
+    function verifyBatchInclusion(
+        bytes32 _proofCommitment,
+        bytes32 _pubInputCommitment,
+        bytes32,
+        bytes20 _proofGeneratorAddr,
+        bytes32 _batchMerkleRoot,
+        bytes memory _merkleProof,
+        uint256 _verificationDataBatchIndex,
+        address
+    ) external view returns (bool) {
+        return _proofCommitment == proofCommitment && _pubInputCommitment == pubInputCommitment
+            && _proofGeneratorAddr == proofGeneratorAddr && _batchMerkleRoot == batchMerkleRoot
+            && keccak256(_merkleProof) == merkleProofHash
+            && _verificationDataBatchIndex == verificationDataBatchIndex;
+    }
+}
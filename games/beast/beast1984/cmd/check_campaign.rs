@@ -0,0 +1,144 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process,
+    str::FromStr,
+};
+
+use alloy::primitives::Address;
+use beast1984::{
+    chain_client::{ChainClient, split_rpc_urls},
+    consistency::{GamesSource, check_contract_games, check_leaderboard_file, check_levels},
+    ethereum::network_name,
+    leaderboard::{Leaderboard, read_beast_games},
+    submit_config::load_env_file,
+};
+use campaign::Leaderboard as LeaderboardFile;
+use game_logic::common::game::GameJson;
+use serde::de::DeserializeOwned;
+
+const USAGE: &str = "Usage: check_campaign [--network <network>] [--levels <path>] [--leaderboard-file <path>] [--chain] [--rpc-url <url[,url...]>] [--leaderboard <address>] [--env-file <path>]
+
+Checks that the game config of every game in the levels file is the encoding of its levels and that
+the leaderboard file has the same windows and configs. The files default to levels/<network>.json and
+levels/leaderboard_<network>.json, the network to the one the game was built for.
+
+With --chain the games are compared with beastGames of the leaderboard contract as well. The rpc url
+and the contract default to ETH_RPC_URL and LEADERBOARD_CONTRACT_ADDRESS, more rpc urls can be given
+separated by commas.";
+
+fn exit_with_usage(error: &str) -> ! {
+    eprintln!("{error}\n\n{USAGE}");
+    process::exit(2);
+}
+
+fn read_json<T: DeserializeOwned>(path: &Path) -> T {
+    let content = fs::read_to_string(path).unwrap_or_else(|e| {
+        eprintln!("could not read {}: {e}", path.display());
+        process::exit(2);
+    });
+    serde_json::from_str(&content).unwrap_or_else(|e| {
+        eprintln!("invalid {}: {e}", path.display());
+        process::exit(2);
+    })
+}
+
+#[tokio::main]
+async fn main() {
+    let mut network = network_name().to_string();
+    let mut levels_path = None;
+    let mut leaderboard_path = None;
+    let mut chain = false;
+    let mut rpc_url = None;
+    let mut leaderboard_address = None;
+    let mut env_file = None;
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = |flag: &str| {
+            args.next()
+                .unwrap_or_else(|| exit_with_usage(&format!("{flag} needs a value")))
+        };
+        match arg.as_str() {
+            "--help" | "-h" => {
+                println!("{USAGE}");
+                return;
+            }
+            "--network" => network = value("--network"),
+            "--levels" => levels_path = Some(PathBuf::from(value("--levels"))),
+            "--leaderboard-file" => {
+                leaderboard_path = Some(PathBuf::from(value("--leaderboard-file")))
+            }
+            "--chain" => chain = true,
+            "--rpc-url" => rpc_url = Some(value("--rpc-url")),
+            "--leaderboard" => leaderboard_address = Some(value("--leaderboard")),
+            "--env-file" => env_file = Some(PathBuf::from(value("--env-file"))),
+            flag => exit_with_usage(&format!("unknown argument {flag}")),
+        }
+    }
+
+    let levels_path =
+        levels_path.unwrap_or_else(|| PathBuf::from(format!("levels/{network}.json")));
+    let leaderboard_path = leaderboard_path
+        .unwrap_or_else(|| PathBuf::from(format!("levels/leaderboard_{network}.json")));
+    let games: Vec<GameJson> = read_json(&levels_path);
+    let leaderboard: LeaderboardFile = read_json(&leaderboard_path);
+
+    let mut mismatches = check_levels(&games);
+    mismatches.extend(check_leaderboard_file(&games, &leaderboard));
+
+    if chain {
+        load_env_file(env_file.as_deref()).unwrap_or_else(|e| exit_with_usage(&e.to_string()));
+        let rpc_url = rpc_url
+            .or_else(|| env::var("ETH_RPC_URL").ok())
+            .unwrap_or_else(|| exit_with_usage("--rpc-url or ETH_RPC_URL must be set"));
+        let leaderboard_address = leaderboard_address
+            .or_else(|| env::var("LEADERBOARD_CONTRACT_ADDRESS").ok())
+            .unwrap_or_else(|| {
+                exit_with_usage("--leaderboard or LEADERBOARD_CONTRACT_ADDRESS must be set")
+            });
+        let leaderboard_address = Address::from_str(&leaderboard_address).unwrap_or_else(|_| {
+            exit_with_usage(&format!("\"{leaderboard_address}\" is not a valid address"))
+        });
+
+        let chain_client = ChainClient::connect(&split_rpc_urls(&rpc_url))
+            .unwrap_or_else(|e| exit_with_usage(&e.to_string()));
+        let contract_games = chain_client
+            .read("the beast games", |provider| async move {
+                read_beast_games(&Leaderboard::new(leaderboard_address, provider)).await
+            })
+            .await
+            .unwrap_or_else(|e| {
+                eprintln!("{e}");
+                process::exit(1);
+            });
+        mismatches.extend(check_contract_games(
+            &games,
+            &contract_games,
+            GamesSource::Chain,
+        ));
+    }
+
+    let compared = if chain {
+        "the leaderboard file and the chain"
+    } else {
+        "the leaderboard file"
+    };
+    if mismatches.is_empty() {
+        println!(
+            "The {} games of {} match their levels and {compared}",
+            games.len(),
+            levels_path.display()
+        );
+        return;
+    }
+
+    eprintln!(
+        "{} does not match its levels or {compared}:",
+        levels_path.display()
+    );
+    for mismatch in &mismatches {
+        eprintln!("  {mismatch}");
+    }
+    process::exit(1);
+}
@@ -0,0 +1,59 @@
+use std::{env, process};
+
+use alloy::hex;
+use beast1984::proof_file::{ProofFile, proving_system_name};
+use chrono::DateTime;
+
+fn main() {
+    let Some(path) = env::args().nth(1) else {
+        eprintln!("Usage: inspect <sp1_solution_*.bin>");
+        process::exit(2);
+    };
+
+    let file = ProofFile::read(&path).unwrap_or_else(|e| {
+        eprintln!("{path}: {e}");
+        process::exit(1);
+    });
+    // the reader already checked that the public values decode and match the header
+    let values = file
+        .decoded_public_values()
+        .expect("The public values were checked when reading");
+    let header = &file.header;
+    let written_at = DateTime::from_timestamp(header.timestamp as i64, 0)
+        .map(|time| time.to_rfc3339())
+        .unwrap_or_else(|| header.timestamp.to_string());
+
+    println!("file                   {path}");
+    println!("format version         {}", header.version);
+    println!(
+        "proving system         {} ({})",
+        proving_system_name(header.proving_system),
+        header.proving_system
+    );
+    println!("game                   {}", header.game);
+    println!("network                {}", header.network);
+    println!("address                0x{}", hex::encode(header.address));
+    println!("level reached          {}", header.level);
+    println!("written at             {written_at}");
+    println!(
+        "program vk commitment  0x{}",
+        hex::encode(header.program_vk_commitment)
+    );
+    println!("proof                  {} bytes", file.proof.len());
+    println!();
+    println!("public values");
+    println!("  level                {}", values.level);
+    println!(
+        "  game config          0x{}",
+        hex::encode(values.game_config)
+    );
+    for (idx, [blocks, static_blocks, common_beasts, super_beasts]) in
+        values.level_configs().into_iter().enumerate()
+    {
+        println!(
+            "    level {}            blocks {blocks}, static blocks {static_blocks}, common beasts {common_beasts}, super beasts {super_beasts}",
+            idx + 1
+        );
+    }
+    println!("  address              0x{}", hex::encode(values.address));
+}
@@ -0,0 +1,129 @@
+use std::{env, path::PathBuf, process, str::FromStr};
+
+use alloy::primitives::{Address, U256};
+use beast1984::{
+    chain_client::{ChainClient, split_rpc_urls},
+    error::format_timestamp,
+    leaderboard::{Leaderboard, read_leaderboard_summary},
+    settings::Settings,
+    submit_config::load_env_file,
+};
+
+const USAGE: &str =
+    "Usage: leaderboard [address] [--rpc-url <url[,url...]>] [--leaderboard <address>] [--env-file <path>]
+
+The address defaults to the one in the settings file, the rpc url and the leaderboard contract to
+ETH_RPC_URL and LEADERBOARD_CONTRACT_ADDRESS. More rpc urls can be given separated by commas, they
+are tried in order when one fails.";
+
+fn exit_with_usage(error: &str) -> ! {
+    eprintln!("{error}\n\n{USAGE}");
+    process::exit(2);
+}
+
+fn parse_address(value: &str) -> Address {
+    Address::from_str(value)
+        .unwrap_or_else(|_| exit_with_usage(&format!("\"{value}\" is not a valid address")))
+}
+
+fn timestamp(value: U256) -> String {
+    u64::try_from(value)
+        .map(format_timestamp)
+        .unwrap_or_else(|_| value.to_string())
+}
+
+#[tokio::main]
+async fn main() {
+    let mut user = None;
+    let mut rpc_url = None;
+    let mut leaderboard_address = None;
+    let mut env_file = None;
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = |flag: &str| {
+            args.next()
+                .unwrap_or_else(|| exit_with_usage(&format!("{flag} needs a value")))
+        };
+        match arg.as_str() {
+            "--help" | "-h" => {
+                println!("{USAGE}");
+                return;
+            }
+            "--rpc-url" => rpc_url = Some(value("--rpc-url")),
+            "--leaderboard" => leaderboard_address = Some(value("--leaderboard")),
+            "--env-file" => env_file = Some(PathBuf::from(value("--env-file"))),
+            flag if flag.starts_with("--") => exit_with_usage(&format!("unknown flag {flag}")),
+            address => user = Some(parse_address(address)),
+        }
+    }
+
+    load_env_file(env_file.as_deref()).unwrap_or_else(|e| exit_with_usage(&e.to_string()));
+    let rpc_url = rpc_url
+        .or_else(|| env::var("ETH_RPC_URL").ok())
+        .unwrap_or_else(|| exit_with_usage("--rpc-url or ETH_RPC_URL must be set"));
+    let leaderboard_address = leaderboard_address
+        .or_else(|| env::var("LEADERBOARD_CONTRACT_ADDRESS").ok())
+        .unwrap_or_else(|| {
+            exit_with_usage("--leaderboard or LEADERBOARD_CONTRACT_ADDRESS must be set")
+        });
+    let user = user.or_else(|| {
+        Settings::load()
+            .ok()
+            .and_then(|settings| settings.address)
+            .map(|address| parse_address(&address))
+    });
+
+    let chain_client = ChainClient::connect(&split_rpc_urls(&rpc_url))
+        .unwrap_or_else(|e| exit_with_usage(&e.to_string()));
+    let leaderboard_address = parse_address(&leaderboard_address);
+
+    let summary = chain_client
+        .read("the leaderboard", |provider| async move {
+            let leaderboard = Leaderboard::new(leaderboard_address, provider);
+            read_leaderboard_summary(&leaderboard, user).await
+        })
+        .await
+        .unwrap_or_else(|e| {
+            eprintln!("{e}");
+            process::exit(1);
+        });
+
+    match &summary.current_game {
+        Some((idx, game)) => {
+            println!("current game           {idx}");
+            println!("  starts at            {}", timestamp(game.startsAtTime));
+            println!("  ends at              {}", timestamp(game.endsAtTime));
+            println!("  game config          {:#066x}", game.gameConfig);
+        }
+        None => println!("current game           none"),
+    }
+
+    if let Some(standing) = &summary.user {
+        println!();
+        println!("address                {}", standing.address);
+        println!("score                  {}", standing.score);
+        match standing.level_completed {
+            Some(level) => println!("level in current game  {level}"),
+            None => println!("level in current game  -"),
+        }
+        match summary.rank() {
+            Some(rank) => println!("rank                   {rank}"),
+            None => println!("rank                   not in the top 10"),
+        }
+    }
+
+    println!();
+    println!("top 10");
+    if summary.top10.is_empty() {
+        println!("  nobody has claimed yet");
+    }
+    for (idx, (address, score)) in summary.top10.iter().enumerate() {
+        let marker = if Some(*address) == user {
+            "  <- you"
+        } else {
+            ""
+        };
+        println!("  {:>2}. {address}  {score}{marker}", idx + 1);
+    }
+}
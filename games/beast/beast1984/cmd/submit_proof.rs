@@ -1,85 +1,30 @@
-use std::{env, str::FromStr};
+use std::{env, fmt::Display, process, str::FromStr};
 
-use aligned_sdk::common::types::{AlignedVerificationData, Network};
+use aligned_sdk::common::types::Network;
 use alloy::{
     hex,
-    network::{EthereumWallet, ReceiptResponse},
-    primitives::{Address, Bytes, U256},
-    providers::ProviderBuilder,
-    signers::local::LocalSigner,
-    sol,
+    primitives::{Address, U256},
 };
-use beast1984::aligned_client::AlignedClient;
-use tracing::info;
+use beast1984::{
+    aligned_client::{
+        AlignedClient, discard_submission_state, proving_system_from_id, submission_state_path,
+    },
+    chain_client::ChainClient,
+    leaderboard::{
+        BatchInclusion, Leaderboard, claim_beast_points, dry_run_claim, find_game_index,
+        read_leaderboard_games,
+    },
+    proof_file::ProofFile,
+    settings::Settings,
+    sp1_prover::{BEAST_1984_PROGRAM_ELF, program_vk_commitment},
+    submit_config::{SubmitArgs, SubmitConfig, USAGE, load_env_file},
+};
+use tracing::{info, warn};
 use tracing_subscriber::FmtSubscriber;
 
-sol!(
-    #[sol(rpc)]
-    Leaderboard,
-    "cmd/abi/Leaderboard.json"
-);
-
-const PROOF_FILE_PATH: &str = "./beast1984/proof.bin";
-const PUBLIC_INPUTS_FILE_PATH: &str = "./beast1984/public_inputs.bin";
-const PROGRAM_ID_FILE_PATH: &str = "./beast1984/proof_id.bin";
-const ENV_FILE: &str = "./beast1984/cmd/.env";
-
-async fn send_solution_to_leaderboard(
-    aligned_verification_data: AlignedVerificationData,
-    pub_input: Vec<u8>,
-    eth_rpc_url: String,
-    wallet_private_key: String,
-    leaderboard_contract_address: String,
-) -> [u8; 32] {
-    let rpc_url = eth_rpc_url.parse().expect("RPC URL should be valid");
-    let signer = LocalSigner::from_str(&wallet_private_key).expect("Valid private key");
-    let wallet = EthereumWallet::new(signer);
-    let rpc_provider = ProviderBuilder::new().wallet(wallet).connect_http(rpc_url);
-    let leaderboard = Leaderboard::new(
-        Address::from_str(&leaderboard_contract_address)
-            .expect("Leaderboard address should be valid"),
-        rpc_provider,
-    );
-
-    let merkle_path = Bytes::from(
-        aligned_verification_data
-            .batch_inclusion_proof
-            .merkle_path
-            .as_slice()
-            .iter()
-            .flatten()
-            .copied()
-            .collect::<Vec<u8>>(),
-    );
-
-    let res = leaderboard
-        .claimBeastPoints(
-            aligned_verification_data
-                .verification_data_commitment
-                .proof_commitment
-                .into(),
-            pub_input.into(),
-            aligned_verification_data
-                .verification_data_commitment
-                .proving_system_aux_data_commitment
-                .into(),
-            aligned_verification_data
-                .verification_data_commitment
-                .proof_generator_addr
-                .into(),
-            aligned_verification_data.batch_merkle_root.into(),
-            merkle_path.into(),
-            U256::from(aligned_verification_data.index_in_batch),
-        )
-        .send()
-        .await
-        .expect("To be able to send the transaction");
-
-    res.get_receipt()
-        .await
-        .expect("to get transaction receipt")
-        .transaction_hash()
-        .0
+fn exit_with_error(error: impl Display) -> ! {
+    eprintln!("✘ {error}");
+    process::exit(1);
 }
 
 #[tokio::main]
@@ -87,55 +32,153 @@ async fn main() {
     let subscriber = FmtSubscriber::builder().finish();
     tracing::subscriber::set_global_default(subscriber).expect("setting default subscriber failed");
 
+    let args = SubmitArgs::parse(env::args().skip(1)).unwrap_or_else(|e| {
+        eprintln!("{e}\n\n{USAGE}");
+        process::exit(2);
+    });
+    if args.help {
+        println!("{USAGE}");
+        return;
+    }
+
     info!("Reading config...");
-    dotenv::from_path(ENV_FILE).ok().expect("To have .env file");
-    let chain_id: u64 = env::var("CHAIN_ID")
-        .ok()
-        .map(|s| s.parse().expect("CHAIN_ID must be a valid u64"))
-        .expect("CHAIN_ID must b e set");
-    let eth_rpc_url = env::var("ETH_RPC_URL").expect("ETH_RPC_URL must be set");
-    let network = env::var("NETWORK").expect("NETWORK must be set");
-    let wallet_private_key =
-        env::var("WALLET_PRIVATE_KEY").expect("WALLET_PRIVATE_KEY must be set");
-    let leaderboard_contract_address =
-        env::var("LEADERBOARD_CONTRACT_ADDRESS").expect("LEADERBOARD_CONTRACT_ADDRESS must be set");
-    let network = match network.as_str() {
+    load_env_file(args.env_file.as_deref()).unwrap_or_else(|e| exit_with_error(e));
+    let settings = Settings::load().unwrap_or_else(|e| exit_with_error(e));
+    let config = SubmitConfig::resolve(args, |name| env::var(name).ok(), settings.network)
+        .unwrap_or_else(|e| {
+            eprintln!("{e}\n\n{USAGE}");
+            process::exit(2);
+        });
+    let signer = config
+        .signer
+        .load(|name| env::var(name).ok())
+        .unwrap_or_else(|e| exit_with_error(e));
+    info!("Signing as {}", signer.address());
+    let sender = signer.address();
+    let chain_client = ChainClient::connect_with_signer(&config.rpc_urls, signer.clone())
+        .unwrap_or_else(|e| exit_with_error(e));
+
+    let proof_path = config.proof_path;
+    let leaderboard_games_path = format!("./levels/leaderboard_{}.json", config.network);
+    let network = match config.network.as_str() {
         "mainnet" => Network::Mainnet,
         "holesky" => Network::Holesky,
         "holesky-stage" => Network::HoleskyStage,
         "devnet" => Network::Devnet,
-        _ => panic!("Invalid NETWORK, possible values: mainnet|holeksy|holesky-stage|devnet"),
+        _ => exit_with_error(
+            "Invalid network, possible values: mainnet|holeksy|holesky-stage|devnet",
+        ),
     };
 
     let aligned_client = AlignedClient::new(
-        chain_id.clone(),
-        eth_rpc_url.clone(),
+        config.chain_id,
+        chain_client.primary_url().to_string(),
         network,
-        wallet_private_key.clone(),
-    );
+        &signer,
+    )
+    .unwrap_or_else(|e| exit_with_error(e))
+    .with_fee_strategy(config.fee_strategy);
 
     info!("Config correct, reading proof...");
 
-    let proof = std::fs::read(PROOF_FILE_PATH).expect("proof to exist");
-    let image_id = std::fs::read(PROGRAM_ID_FILE_PATH).expect("image id file to exist");
-    let pub_input = std::fs::read(PUBLIC_INPUTS_FILE_PATH).expect("public inputs file to exist");
+    let proof_file = ProofFile::read(&proof_path)
+        .unwrap_or_else(|e| exit_with_error(format!("{proof_path}: {e}")));
+    let proving_system = proving_system_from_id(proof_file.header.proving_system)
+        .unwrap_or_else(|e| exit_with_error(e));
+    // the program isn't part of the proof file, so the proof has to come from the program we embed
+    if proof_file.header.program_vk_commitment != program_vk_commitment() {
+        exit_with_error(
+            "The proof was generated with another version of the game, please update and prove again",
+        );
+    }
+    let pub_input = proof_file.public_values.clone();
+    let public_values = proof_file
+        .decoded_public_values()
+        .unwrap_or_else(|e| exit_with_error(e));
+    let game_config = U256::from_be_bytes(public_values.game_config);
 
-    info!("Proof loaded, sending to verify on aligned...");
-    let aligned_verification_data = aligned_client
-        .send_proof_to_be_verified_on_aligned(proof, image_id, pub_input.clone())
+    let leaderboard_address = Address::from_str(&config.leaderboard).unwrap_or_else(|_| {
+        exit_with_error(format!("\"{}\" is not a valid address", config.leaderboard))
+    });
+
+    // look the game up before paying for the verification so a proof that can't be claimed isn't sent
+    let game_index = match config.game_index {
+        Some(game_index) => game_index,
+        None => {
+            let games = read_leaderboard_games(&leaderboard_games_path).unwrap_or_else(|e| {
+                warn!("{e}, only the current game can be matched");
+                vec![]
+            });
+            let games = &games;
+            chain_client
+                .read("the game index", move |provider| async move {
+                    let leaderboard = Leaderboard::new(leaderboard_address, provider);
+                    find_game_index(&leaderboard, game_config, games).await
+                })
+                .await
+                .unwrap_or_else(|e| exit_with_error(e))
+        }
+    };
+    info!("Claiming for game {game_index}");
+
+    // the same checks the contract makes, so a claim that would revert isn't paid for
+    let values = &public_values;
+    let eligibility = chain_client
+        .read("the claim eligibility", move |provider| async move {
+            let leaderboard = Leaderboard::new(leaderboard_address, provider);
+            dry_run_claim(&leaderboard, sender, game_index, values).await
+        })
         .await;
+    if config.dry_run {
+        match eligibility {
+            Ok(()) => {
+                println!(
+                    "✔ Level {} can be claimed for game {game_index} once the proof is verified on aligned",
+                    public_values.level
+                );
+                return;
+            }
+            Err(e) => {
+                eprintln!("✘ {e}");
+                process::exit(1);
+            }
+        }
+    }
+    eligibility.unwrap_or_else(|e| exit_with_error(e));
+
+    let state_path = submission_state_path(&proof_path);
+    if config.resubmit {
+        discard_submission_state(&state_path).unwrap_or_else(|e| exit_with_error(e));
+    }
+
+    info!(
+        "Proof for level {} loaded, sending to verify on aligned...",
+        proof_file.header.level
+    );
+    let aligned_verification_data = aligned_client
+        .send_proof_to_be_verified_on_aligned(
+            state_path,
+            proving_system,
+            proof_file.proof,
+            BEAST_1984_PROGRAM_ELF.to_vec(),
+            pub_input.clone(),
+        )
+        .await
+        .unwrap_or_else(|e| exit_with_error(e));
 
     info!(
         "Proof verified on aligned with batch merkle root {:?}, sending submission to contract...",
         hex::encode(aligned_verification_data.batch_merkle_root)
     );
-    let tx_hash = send_solution_to_leaderboard(
-        aligned_verification_data,
+    // the claim is only sent through the first endpoint so it can't go out twice
+    let leaderboard = Leaderboard::new(leaderboard_address, chain_client.primary());
+    let tx_hash = claim_beast_points(
+        &leaderboard,
+        game_index,
+        &BatchInclusion::from(&aligned_verification_data),
         pub_input,
-        eth_rpc_url,
-        wallet_private_key,
-        leaderboard_contract_address,
     )
-    .await;
+    .await
+    .unwrap_or_else(|e| exit_with_error(e));
     info!("Solution sent, tx hash: {:?}", hex::encode(tx_hash));
 }
@@ -0,0 +1,33 @@
+use std::{env, process};
+
+use alloy::hex;
+use beast1984::{proof_file::ProofFile, sp1_prover::verify_proof_file};
+
+fn main() {
+    let Some(path) = env::args().nth(1) else {
+        eprintln!("Usage: verify <sp1_solution_*.bin>");
+        process::exit(2);
+    };
+
+    let file = ProofFile::read(&path).unwrap_or_else(|e| {
+        eprintln!("✘ {path}: {e}");
+        process::exit(1);
+    });
+
+    println!("Verifying {path}, this can take a moment...");
+    match verify_proof_file(&file) {
+        Ok(values) => {
+            println!("✔ The proof is valid");
+            println!("  level                {}", values.level);
+            println!(
+                "  game config          0x{}",
+                hex::encode(values.game_config)
+            );
+            println!("  address              0x{}", hex::encode(values.address));
+        }
+        Err(e) => {
+            eprintln!("✘ {path}: {e}");
+            process::exit(1);
+        }
+    }
+}
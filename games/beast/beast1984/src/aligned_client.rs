@@ -1,74 +1,617 @@
-use std::str::FromStr;
+//! this module contains the client that sends proofs to aligned and waits for them to be verified
+
+use std::{
+    fmt, fs,
+    future::Future,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::{Duration, Instant},
+};
 
 use aligned_sdk::{
-    common::types::{
-        AlignedVerificationData, Network, Signer, SigningKey, VerificationData, Wallet,
+    common::{
+        errors::{FeeEstimateError, GetNonceError, SubmitError, VerificationError},
+        types::{
+            AlignedVerificationData, FeeEstimationType, Network, ProvingSystemId, Signer,
+            SigningKey, VerificationData, Wallet,
+        },
     },
-    verification_layer::estimate_fee,
+    verification_layer::{estimate_fee, get_nonce_from_batcher, is_proof_verified, submit},
 };
+use alloy::signers::local::PrivateKeySigner;
+use tracing::{info, warn};
+
+use crate::proof_file::proving_system_name;
+
+/// everything that can go wrong while talking to aligned
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AlignedError {
+    /// the wallet private key is not valid
+    InvalidPrivateKey(String),
+    /// the proof file was generated with a proving system the game doesn't submit
+    UnsupportedProvingSystem(u8),
+    /// the nonce could not be retrieved from the batcher
+    Nonce(String),
+    /// the fee could not be estimated
+    FeeEstimation(String),
+    /// the estimated fee is above what the player is willing to pay
+    FeeAboveCap {
+        /// the estimated max fee in wei
+        estimated: u128,
+        /// the configured cap in wei
+        cap: u128,
+    },
+    /// the batcher did not accept the proof
+    Submit(String),
+    /// the connection dropped after the proof was sent and the batcher took it, its batch is unknown
+    SubmissionUnconfirmed {
+        /// the nonce the proof was sent with
+        nonce: String,
+    },
+    /// the verification status could not be read
+    Verification(String),
+    /// the proof was not verified in time, waiting can be resumed later
+    VerificationTimeout(Duration),
+    /// the state of a submitted proof could not be read or written
+    State(String),
+}
+
+impl fmt::Display for AlignedError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AlignedError::InvalidPrivateKey(e) => write!(f, "invalid private key: {e}"),
+            AlignedError::UnsupportedProvingSystem(id) => write!(
+                f,
+                "proving system {} ({id}) is not supported",
+                proving_system_name(*id)
+            ),
+            AlignedError::Nonce(e) => write!(f, "could not retrieve nonce from batcher: {e}"),
+            AlignedError::FeeEstimation(e) => write!(f, "could not estimate max fee: {e}"),
+            AlignedError::FeeAboveCap { estimated, cap } => write!(
+                f,
+                "the estimated max fee of {estimated} wei is above the cap of {cap} wei, try again later or raise the cap"
+            ),
+            AlignedError::Submit(e) => write!(f, "could not submit proof: {e}"),
+            AlignedError::SubmissionUnconfirmed { nonce } => write!(
+                f,
+                "the batcher took the proof sent with nonce {nonce} but the connection dropped before it answered, run the submission with --resubmit to pay for it again"
+            ),
+            AlignedError::Verification(e) => {
+                write!(f, "could not check the proof verification: {e}")
+            }
+            AlignedError::VerificationTimeout(timeout) => write!(
+                f,
+                "the proof was not verified within {}s, run the submission again to keep waiting or with --resubmit to send it again",
+                timeout.as_secs()
+            ),
+            AlignedError::State(e) => write!(f, "could not access the submission state: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for AlignedError {}
+
+/// the aligned proving system of a proof file header, only the systems the game proves with are accepted
+pub fn proving_system_from_id(id: u8) -> Result<ProvingSystemId, AlignedError> {
+    match id {
+        id if id == ProvingSystemId::SP1 as u8 => Ok(ProvingSystemId::SP1),
+        _ => Err(AlignedError::UnsupportedProvingSystem(id)),
+    }
+}
+
+/// how the max fee is estimated
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeeEstimation {
+    /// the fee of a proof in an average batch
+    Default,
+    /// a fee high enough to send a batch with only our proof right away
+    Instant,
+    /// the fee of a proof in a batch of this many proofs
+    BatchOf(usize),
+}
+
+impl FromStr for FeeEstimation {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "default" => Ok(FeeEstimation::Default),
+            "instant" => Ok(FeeEstimation::Instant),
+            value => value
+                .parse::<usize>()
+                .ok()
+                .filter(|proofs| *proofs > 0)
+                .map(FeeEstimation::BatchOf)
+                .ok_or_else(|| {
+                    format!(
+                        "invalid fee estimation \"{value}\", possible values: default|instant|<proofs in batch>"
+                    )
+                }),
+        }
+    }
+}
+
+impl From<FeeEstimation> for FeeEstimationType {
+    fn from(estimation: FeeEstimation) -> Self {
+        match estimation {
+            FeeEstimation::Default => FeeEstimationType::Default,
+            FeeEstimation::Instant => FeeEstimationType::Instant,
+            FeeEstimation::BatchOf(proofs) => FeeEstimationType::Custom(proofs),
+        }
+    }
+}
+
+/// how the max fee of a submission is picked
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeeStrategy {
+    pub estimation: FeeEstimation,
+    /// the most the player is willing to pay in wei, no cap if empty
+    pub max_fee_wei: Option<u128>,
+}
+
+impl Default for FeeStrategy {
+    fn default() -> Self {
+        Self {
+            estimation: FeeEstimation::Instant,
+            max_fee_wei: None,
+        }
+    }
+}
+
+impl FeeStrategy {
+    /// check an estimated fee against the cap
+    pub fn check(&self, estimated: u128) -> Result<(), AlignedError> {
+        match self.max_fee_wei {
+            Some(cap) if estimated > cap => Err(AlignedError::FeeAboveCap { estimated, cap }),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// how often and how fast requests that failed for a transient reason are retried
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// how many times a request is tried in total
+    pub attempts: u32,
+    /// the wait before the first retry, doubled for every following one
+    pub initial_backoff: Duration,
+    /// the longest wait between two tries
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            attempts: 5,
+            initial_backoff: Duration::from_secs(2),
+            max_backoff: Duration::from_secs(60),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// how long to wait after the given failed attempt, starting at 1
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+
+    /// run a request until it succeeds, fails with an error that isn't transient or runs out of attempts
+    async fn run<T, E, F, Fut>(
+        &self,
+        what: &str,
+        is_transient: impl Fn(&E) -> bool,
+        mut request: F,
+    ) -> Result<T, E>
+    where
+        E: fmt::Debug,
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        let mut attempt = 1;
+        loop {
+            match request().await {
+                Ok(value) => return Ok(value),
+                Err(error) if attempt < self.attempts && is_transient(&error) => {
+                    let backoff = self.backoff(attempt);
+                    warn!(
+                        "{what} failed ({error:?}), retrying in {}s",
+                        backoff.as_secs()
+                    );
+                    tokio::time::sleep(backoff).await;
+                    attempt += 1;
+                }
+                Err(error) => return Err(error),
+            }
+        }
+    }
+}
+
+/// the connection to the batcher failed, the proof may or may not have reached it
+fn is_transient_submit_error(error: &SubmitError) -> bool {
+    matches!(error, SubmitError::WebSocketConnectionError(_))
+}
+
+/// the rpc didn't answer the gas price, an rpc url that isn't valid fails the same way every time
+fn is_transient_fee_error(error: &FeeEstimateError) -> bool {
+    matches!(error, FeeEstimateError::EthereumGasPriceError(_))
+}
+
+/// the batcher or its rpc couldn't be reached, a request the batcher rejects fails the same way every time
+fn is_transient_nonce_error(error: &GetNonceError) -> bool {
+    matches!(
+        error,
+        GetNonceError::ConnectionFailed(_) | GetNonceError::EthRpcError(_)
+    )
+}
+
+/// the call to the rpc failed, an rpc url that isn't valid or an answer that can't be decoded won't change
+fn is_transient_verification_error(error: &VerificationError) -> bool {
+    matches!(error, VerificationError::EthereumCallError(_))
+}
+
+/// where the verification data of a submitted proof is kept so waiting for it can be resumed
+pub fn submission_state_path(proof_path: impl AsRef<Path>) -> PathBuf {
+    let mut path = proof_path.as_ref().as_os_str().to_owned();
+    path.push(".aligned.json");
+    path.into()
+}
 
 pub struct AlignedClient {
     eth_rpc_url: String,
     network: Network,
     wallet: Wallet<SigningKey>,
+    fee_strategy: FeeStrategy,
+    retry_policy: RetryPolicy,
+    verification_timeout: Duration,
 }
 
 impl AlignedClient {
+    /// create a client that pays with the key of `signer`
     pub fn new(
         chain_id: u64,
         eth_rpc_url: String,
         network: Network,
-        wallet_private_key: String,
-    ) -> Self {
-        let wallet = Wallet::from_str(&wallet_private_key)
-            .expect("A valid private key")
-            .with_chain_id(chain_id);
+        signer: &PrivateKeySigner,
+    ) -> Result<Self, AlignedError> {
+        let signing_key = SigningKey::from_slice(signer.to_bytes().as_slice())
+            .map_err(|e| AlignedError::InvalidPrivateKey(e.to_string()))?;
+        let wallet = Wallet::from(signing_key).with_chain_id(chain_id);
 
-        Self {
+        Ok(Self {
             eth_rpc_url,
             network,
             wallet,
-        }
+            fee_strategy: FeeStrategy::default(),
+            retry_policy: RetryPolicy::default(),
+            verification_timeout: Duration::from_secs(60 * 60),
+        })
     }
 
-    pub async fn send_proof_to_be_verified_on_aligned(
+    /// set how the max fee is picked
+    pub fn with_fee_strategy(mut self, fee_strategy: FeeStrategy) -> Self {
+        self.fee_strategy = fee_strategy;
+        self
+    }
+
+    /// set how requests are retried
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// set how long to wait for a submitted proof to be verified
+    pub fn with_verification_timeout(mut self, verification_timeout: Duration) -> Self {
+        self.verification_timeout = verification_timeout;
+        self
+    }
+
+    /// send a proof to the batcher and return once it is in a batch, the batch still has to be verified
+    pub async fn submit_proof(
         &self,
+        proving_system: ProvingSystemId,
         proof: Vec<u8>,
-        image_id: Vec<u8>,
+        vm_program_code: Vec<u8>,
         pub_input: Vec<u8>,
-    ) -> AlignedVerificationData {
+    ) -> Result<AlignedVerificationData, AlignedError> {
         let verification_data = VerificationData {
             proof_generator_addr: self.wallet.address(),
-            proving_system: aligned_sdk::common::types::ProvingSystemId::Risc0,
+            proving_system,
             proof,
-            vm_program_code: Some(image_id),
+            vm_program_code: Some(vm_program_code),
             pub_input: Some(pub_input),
             verification_key: None,
         };
 
-        let nonce = aligned_sdk::verification_layer::get_nonce_from_batcher(
-            self.network.clone(),
-            self.wallet.address(),
-        )
-        .await
-        .expect("Retrieve nonce from aligned batcher");
-
-        let max_fee = estimate_fee(
-            &self.eth_rpc_url,
-            aligned_sdk::common::types::FeeEstimationType::Instant,
-        )
-        .await
-        .expect("Max fee to be retrieved");
-
-        aligned_sdk::verification_layer::submit_and_wait_verification(
-            &self.eth_rpc_url,
-            self.network.clone(),
-            &verification_data,
-            max_fee,
-            self.wallet.clone(),
-            nonce,
-        )
-        .await
-        .expect("Proof to be sent")
+        let max_fee = self
+            .retry_policy
+            .run("fee estimation", is_transient_fee_error, || {
+                estimate_fee(&self.eth_rpc_url, self.fee_strategy.estimation.into())
+            })
+            .await
+            .map_err(|e| AlignedError::FeeEstimation(format!("{e:?}")))?;
+        self.fee_strategy
+            .check(u128::try_from(max_fee).unwrap_or(u128::MAX))?;
+
+        // the nonce is read again before every retry, if it moved on the batcher took the proof that
+        // lost its connection and sending it again would pay twice
+        let mut attempt = 1;
+        let mut sent_nonce = None;
+        loop {
+            let nonce = self
+                .retry_policy
+                .run("nonce retrieval", is_transient_nonce_error, || {
+                    get_nonce_from_batcher(self.network.clone(), self.wallet.address())
+                })
+                .await
+                .map_err(|e| AlignedError::Nonce(format!("{e:?}")))?;
+            if let Some(sent_nonce) = sent_nonce.filter(|sent_nonce| nonce > *sent_nonce) {
+                return Err(AlignedError::SubmissionUnconfirmed {
+                    nonce: sent_nonce.to_string(),
+                });
+            }
+
+            match submit(
+                self.network.clone(),
+                &verification_data,
+                max_fee,
+                self.wallet.clone(),
+                nonce,
+            )
+            .await
+            {
+                Ok(aligned_verification_data) => return Ok(aligned_verification_data),
+                Err(error)
+                    if attempt < self.retry_policy.attempts
+                        && is_transient_submit_error(&error) =>
+                {
+                    let backoff = self.retry_policy.backoff(attempt);
+                    warn!(
+                        "submission failed ({error:?}), retrying in {}s",
+                        backoff.as_secs()
+                    );
+                    tokio::time::sleep(backoff).await;
+                    sent_nonce = Some(nonce);
+                    attempt += 1;
+                }
+                Err(error) => return Err(AlignedError::Submit(format!("{error:?}"))),
+            }
+        }
+    }
+
+    /// wait until the batch of a submitted proof is verified on chain
+    pub async fn wait_for_verification(
+        &self,
+        verification_data: &AlignedVerificationData,
+    ) -> Result<(), AlignedError> {
+        let started = Instant::now();
+        let mut check = 1;
+
+        loop {
+            let verified = self
+                .retry_policy
+                .run(
+                    "verification check",
+                    is_transient_verification_error,
+                    || {
+                        is_proof_verified(
+                            verification_data,
+                            self.network.clone(),
+                            &self.eth_rpc_url,
+                        )
+                    },
+                )
+                .await
+                .map_err(|e| AlignedError::Verification(format!("{e:?}")))?;
+            if verified {
+                return Ok(());
+            }
+
+            if started.elapsed() >= self.verification_timeout {
+                return Err(AlignedError::VerificationTimeout(self.verification_timeout));
+            }
+            let backoff = self.retry_policy.backoff(check);
+            info!(
+                "Batch not verified yet, checking again in {}s",
+                backoff.as_secs()
+            );
+            tokio::time::sleep(backoff).await;
+            check += 1;
+        }
+    }
+
+    /// submit a proof and wait for its verification, resuming from `state_path` if it was already submitted
+    ///
+    /// The verification data is written to `state_path` as soon as the proof is in a batch so a crash or a
+    /// timeout never makes the player pay for the same proof twice.
+    pub async fn send_proof_to_be_verified_on_aligned(
+        &self,
+        state_path: impl AsRef<Path>,
+        proving_system: ProvingSystemId,
+        proof: Vec<u8>,
+        vm_program_code: Vec<u8>,
+        pub_input: Vec<u8>,
+    ) -> Result<AlignedVerificationData, AlignedError> {
+        let state_path = state_path.as_ref();
+
+        let verification_data = match read_submission_state(state_path)? {
+            Some(verification_data) => {
+                info!(
+                    "Proof was already submitted, resuming from {}",
+                    state_path.display()
+                );
+                verification_data
+            }
+            None => {
+                let verification_data = self
+                    .submit_proof(proving_system, proof, vm_program_code, pub_input)
+                    .await?;
+                write_submission_state(state_path, &verification_data)?;
+                verification_data
+            }
+        };
+
+        self.wait_for_verification(&verification_data).await?;
+        Ok(verification_data)
+    }
+}
+
+/// forget a submitted proof so the next submission sends it again instead of waiting for its batch
+pub fn discard_submission_state(path: impl AsRef<Path>) -> Result<(), AlignedError> {
+    let path = path.as_ref();
+    match fs::remove_file(path) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(AlignedError::State(format!("{}: {e}", path.display()))),
+    }
+}
+
+fn read_submission_state(path: &Path) -> Result<Option<AlignedVerificationData>, AlignedError> {
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let content = fs::read_to_string(path)
+        .map_err(|e| AlignedError::State(format!("{}: {e}", path.display())))?;
+    serde_json::from_str(&content)
+        .map(Some)
+        .map_err(|e| AlignedError::State(format!("{}: {e}", path.display())))
+}
+
+fn write_submission_state(
+    path: &Path,
+    verification_data: &AlignedVerificationData,
+) -> Result<(), AlignedError> {
+    let content = serde_json::to_string_pretty(verification_data)
+        .map_err(|e| AlignedError::State(e.to_string()))?;
+    fs::write(path, content).map_err(|e| AlignedError::State(format!("{}: {e}", path.display())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_test() {
+        let policy = RetryPolicy {
+            attempts: 5,
+            initial_backoff: Duration::from_secs(2),
+            max_backoff: Duration::from_secs(10),
+        };
+
+        assert_eq!(
+            policy.backoff(1),
+            Duration::from_secs(2),
+            "The first retry waits the initial backoff"
+        );
+        assert_eq!(
+            policy.backoff(3),
+            Duration::from_secs(8),
+            "The backoff doubles with every retry"
+        );
+        assert_eq!(
+            policy.backoff(40),
+            Duration::from_secs(10),
+            "The backoff never exceeds the max"
+        );
+    }
+
+    #[test]
+    fn is_transient_error_test() {
+        let error = || String::from("error");
+
+        assert!(
+            is_transient_fee_error(&FeeEstimateError::EthereumGasPriceError(error())),
+            "A failed gas price request is retried"
+        );
+        assert!(
+            !is_transient_fee_error(&FeeEstimateError::EthereumProviderError(error())),
+            "An invalid rpc url is reported right away"
+        );
+        assert!(
+            is_transient_nonce_error(&GetNonceError::ConnectionFailed(error()))
+                && is_transient_nonce_error(&GetNonceError::EthRpcError(error())),
+            "A batcher or rpc that can't be reached is retried"
+        );
+        assert!(
+            !is_transient_nonce_error(&GetNonceError::InvalidRequest(error()))
+                && !is_transient_nonce_error(&GetNonceError::SerializationError(error())),
+            "A request the batcher rejects is reported right away"
+        );
+        assert!(
+            is_transient_verification_error(&VerificationError::EthereumCallError(error())),
+            "A failed verification call is retried"
+        );
+        assert!(
+            !is_transient_verification_error(&VerificationError::EthereumProviderError(error()))
+                && !is_transient_verification_error(&VerificationError::HexDecodingError(error())),
+            "An invalid rpc url or an answer that can't be decoded is reported right away"
+        );
+    }
+
+    #[test]
+    fn fee_strategy_test() {
+        assert_eq!(
+            "instant".parse::<FeeEstimation>(),
+            Ok(FeeEstimation::Instant),
+            "Named estimations are parsed"
+        );
+        assert_eq!(
+            "16".parse::<FeeEstimation>(),
+            Ok(FeeEstimation::BatchOf(16)),
+            "A number is the size of the batch"
+        );
+        assert!(
+            "0".parse::<FeeEstimation>().is_err(),
+            "A batch needs at least one proof"
+        );
+
+        let capped = FeeStrategy {
+            estimation: FeeEstimation::Default,
+            max_fee_wei: Some(100),
+        };
+        assert_eq!(capped.check(100), Ok(()), "A fee at the cap is paid");
+        assert_eq!(
+            capped.check(101),
+            Err(AlignedError::FeeAboveCap {
+                estimated: 101,
+                cap: 100
+            }),
+            "A fee above the cap is refused"
+        );
+        assert_eq!(
+            FeeStrategy::default().check(u128::MAX),
+            Ok(()),
+            "Without a cap every fee is paid"
+        );
+    }
+
+    #[test]
+    fn discard_submission_state_test() {
+        let path = std::env::temp_dir().join("beast_discard_submission_state.aligned.json");
+        fs::write(&path, "{}").expect("Failed to write temp state file");
+
+        assert_eq!(
+            discard_submission_state(&path),
+            Ok(()),
+            "The state file is removed"
+        );
+        assert!(!path.exists(), "The state is gone");
+        assert_eq!(
+            discard_submission_state(&path),
+            Ok(()),
+            "A missing state is nothing to discard"
+        );
+    }
+
+    #[test]
+    fn submission_state_path_test() {
+        assert_eq!(
+            submission_state_path("proofs/sp1_solution_2025-01-01_00-00-00.bin"),
+            PathBuf::from("proofs/sp1_solution_2025-01-01_00-00-00.bin.aligned.json"),
+            "The state is kept next to the proof file"
+        );
     }
 }
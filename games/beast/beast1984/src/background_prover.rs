@@ -0,0 +1,412 @@
+//! this module contains the background prover that proves completed levels while the player keeps playing
+//!
+//! Every time a level is completed the game hands us the prefix of the completion log. Only one proof
+//! runs at a time since SP1 already uses every core, newer requests wait in a single slot and replace
+//! each other. Finished proofs are compared by how many levels they prove and only the best one is
+//! kept on disk, unless the worse one was already handed out to the player.
+//!
+//! Proofs belong to a [ProofRun], the game and the attempt they were made for. Starting another run
+//! drops the proofs of the previous one so they never stand in for the current run.
+
+use std::{
+    fmt, fs,
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+use game_logic::{common::levels::LevelJson, proving::LevelLog};
+
+use crate::sp1_prover::{ProvingError, prove as sp1_prove, save_proof as sp1_save_proof};
+
+/// the run proofs are made for, the config of the game being played and the attempt at it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ProofRun {
+    /// the encoded levels of the game
+    pub game_config: [u8; 32],
+    /// counts the games started by the player
+    pub attempt: u64,
+}
+
+/// everything needed to prove a run
+#[derive(Debug, Clone)]
+struct ProofRequest {
+    run: ProofRun,
+    levels_log: Vec<LevelLog>,
+    levels: Vec<LevelJson>,
+    address: String,
+}
+
+/// a proof that is being generated on another thread
+struct RunningJob {
+    run: ProofRun,
+    levels: usize,
+    started: Instant,
+    handle: JoinHandle<Result<String, ProvingError>>,
+}
+
+/// a proof that was saved to disk
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FinishedProof {
+    /// the run the proof was made for
+    pub run: ProofRun,
+    /// how many levels this proof covers
+    pub levels: usize,
+    /// where the proof file was written
+    pub path: String,
+}
+
+/// what the background prover is doing, shown in the footer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProverStatus {
+    /// nothing was proven yet
+    Idle,
+    /// a proof is being generated
+    Proving {
+        /// how many levels the running proof covers
+        levels: usize,
+        /// how long the proof has been running
+        elapsed: Duration,
+    },
+    /// the best proof is ready to be submitted
+    Ready {
+        /// how many levels the proof covers
+        levels: usize,
+    },
+    /// the last proof failed and nothing better is ready
+    Failed(String),
+}
+
+impl fmt::Display for ProverStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let plural = |levels: &usize| if *levels == 1 { "level" } else { "levels" };
+
+        match self {
+            ProverStatus::Idle => Ok(()),
+            ProverStatus::Proving { levels, elapsed } => write!(
+                f,
+                "⚙ Proving {levels} {} in the background {:02}:{:02}",
+                plural(levels),
+                elapsed.as_secs() / 60,
+                elapsed.as_secs() % 60
+            ),
+            ProverStatus::Ready { levels } => {
+                write!(f, "✔ Proof for {levels} {} ready", plural(levels))
+            }
+            ProverStatus::Failed(error) => write!(f, "✘ Background proof failed: {error}"),
+        }
+    }
+}
+
+/// proves completed levels on a background thread
+#[derive(Default)]
+pub struct BackgroundProver {
+    run: ProofRun,
+    running: Option<RunningJob>,
+    pending: Option<ProofRequest>,
+    best: Option<FinishedProof>,
+    last_error: Option<ProvingError>,
+    /// the proofs the player was told about, their files are never removed
+    handed_out: Vec<String>,
+}
+
+impl BackgroundProver {
+    /// create a new background prover without any jobs
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// prove the levels of another run from now on, the proofs of the previous run are dropped
+    ///
+    /// A proof of the previous run that is still running can't be stopped, it finishes first and its
+    /// file is left on disk.
+    pub fn start_run(&mut self, run: ProofRun) {
+        if run == self.run {
+            return;
+        }
+
+        self.run = run;
+        self.pending = None;
+        self.best = None;
+        self.last_error = None;
+    }
+
+    /// is the running job proving the current run
+    fn running_current(&self) -> Option<&RunningJob> {
+        self.running.as_ref().filter(|job| job.run == self.run)
+    }
+
+    /// the most levels covered by a finished, running or queued proof of the current run
+    fn covered_levels(&self) -> usize {
+        let best = self.best.as_ref().map(|proof| proof.levels).unwrap_or(0);
+        let running = self.running_current().map(|job| job.levels).unwrap_or(0);
+        let pending = self
+            .pending
+            .as_ref()
+            .map(|request| request.levels_log.len())
+            .unwrap_or(0);
+        best.max(running).max(pending)
+    }
+
+    /// queue a proof for the completed levels, requests that can't beat what we already have are ignored
+    pub fn submit(&mut self, levels_log: Vec<LevelLog>, levels: Vec<LevelJson>, address: String) {
+        if levels_log.is_empty() || levels_log.len() <= self.covered_levels() {
+            return;
+        }
+
+        self.pending = Some(ProofRequest {
+            run: self.run,
+            levels_log,
+            levels,
+            address,
+        });
+        self.poll();
+    }
+
+    /// collect a finished job and start the queued one, call this regularly
+    pub fn poll(&mut self) {
+        if self
+            .running
+            .as_ref()
+            .is_some_and(|job| job.handle.is_finished())
+        {
+            self.finish_running();
+        }
+
+        if self.running.is_some() {
+            return;
+        }
+        if let Some(request) = self.pending.take() {
+            self.start(request);
+        }
+    }
+
+    /// block until a proof covering at least `levels` levels is ready or there is nothing left to wait for,
+    /// the proof is handed out to the player so its file is kept from now on
+    pub fn wait_for(&mut self, levels: usize) -> Result<FinishedProof, ProvingError> {
+        loop {
+            if let Some(best) = self.best.clone().filter(|best| best.levels >= levels) {
+                if !self.handed_out.contains(&best.path) {
+                    self.handed_out.push(best.path.clone());
+                }
+                return Ok(best);
+            }
+
+            if self.running.is_some() {
+                self.finish_running();
+            } else if let Some(request) = self.pending.take() {
+                self.start(request);
+            } else {
+                return Err(self.last_error.clone().unwrap_or_else(|| {
+                    ProvingError::Prove(String::from("there is no completed level to prove"))
+                }));
+            }
+        }
+    }
+
+    /// is a proof covering at least `levels` levels ready without waiting
+    pub fn is_ready(&self, levels: usize) -> bool {
+        self.best.as_ref().is_some_and(|best| best.levels >= levels)
+    }
+
+    /// what the prover is doing right now
+    pub fn status(&self) -> ProverStatus {
+        if let Some(job) = self.running_current() {
+            return ProverStatus::Proving {
+                levels: job.levels,
+                elapsed: job.started.elapsed(),
+            };
+        }
+
+        match (&self.best, &self.last_error) {
+            (Some(best), _) => ProverStatus::Ready {
+                levels: best.levels,
+            },
+            (None, Some(error)) => ProverStatus::Failed(error.to_string()),
+            (None, None) => ProverStatus::Idle,
+        }
+    }
+
+    fn start(&mut self, request: ProofRequest) {
+        let levels = request.levels_log.len();
+        let handle = thread::spawn(move || {
+            let proof = sp1_prove(request.levels_log, request.levels, request.address)?;
+            sp1_save_proof(proof)
+        });
+
+        self.running = Some(RunningJob {
+            run: request.run,
+            levels,
+            started: Instant::now(),
+            handle,
+        });
+    }
+
+    /// wait for the running job and keep its proof if it beats the best one of the current run
+    fn finish_running(&mut self) {
+        let Some(job) = self.running.take() else {
+            return;
+        };
+        let current = job.run == self.run;
+
+        let result = job.handle.join().unwrap_or_else(|_| {
+            Err(ProvingError::Prove(String::from(
+                "the prover thread crashed",
+            )))
+        });
+
+        match result {
+            Ok(path) if current => {
+                let proof = FinishedProof {
+                    run: job.run,
+                    levels: job.levels,
+                    path,
+                };
+                self.keep_best(proof);
+                self.last_error = None;
+            }
+            Err(error) if current => self.last_error = Some(error),
+            // the run is over, its proof stays on disk but isn't offered for the current run
+            _ => {}
+        }
+    }
+
+    /// keep the proof covering more levels and delete the other file unless it was handed out
+    fn keep_best(&mut self, proof: FinishedProof) {
+        let (keep, discard) = match self.best.take() {
+            Some(best) if best.levels > proof.levels => (best, Some(proof)),
+            Some(best) => (proof, Some(best)),
+            None => (proof, None),
+        };
+
+        if let Some(discard) = discard
+            .filter(|discard| discard.path != keep.path && !self.handed_out.contains(&discard.path))
+        {
+            let _ = fs::remove_file(&discard.path);
+        }
+        self.best = Some(keep);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn proof_file(name: &str, levels: usize) -> FinishedProof {
+        let path = std::env::temp_dir().join(format!("beast_background_prover_{name}.bin"));
+        fs::write(&path, [0u8]).expect("Failed to write temp proof file");
+
+        FinishedProof {
+            run: ProofRun::default(),
+            levels,
+            path: path.to_string_lossy().into_owned(),
+        }
+    }
+
+    #[test]
+    fn keep_best_proof_test() {
+        let mut prover = BackgroundProver::new();
+        let one_level = proof_file("one", 1);
+        let two_levels = proof_file("two", 2);
+
+        prover.keep_best(one_level.clone());
+        prover.keep_best(two_levels.clone());
+        assert_eq!(
+            prover.best,
+            Some(two_levels.clone()),
+            "The proof with more levels is kept"
+        );
+        assert!(
+            !std::path::Path::new(&one_level.path).exists(),
+            "The worse proof file is removed"
+        );
+
+        let worse = proof_file("worse", 1);
+        prover.keep_best(worse.clone());
+        assert_eq!(
+            prover.best,
+            Some(two_levels.clone()),
+            "A worse proof doesn't replace the best"
+        );
+        assert!(
+            !std::path::Path::new(&worse.path).exists(),
+            "The worse proof file is removed"
+        );
+        assert_eq!(
+            prover.status(),
+            ProverStatus::Ready { levels: 2 },
+            "The best proof is ready"
+        );
+
+        let _ = fs::remove_file(&two_levels.path);
+    }
+
+    #[test]
+    fn handed_out_proof_is_kept_test() {
+        let mut prover = BackgroundProver::new();
+        let one_level = proof_file("handed_out", 1);
+
+        prover.keep_best(one_level.clone());
+        assert_eq!(
+            prover.wait_for(1).ok(),
+            Some(one_level.clone()),
+            "The ready proof is handed out"
+        );
+
+        let two_levels = proof_file("after_handed_out", 2);
+        prover.keep_best(two_levels.clone());
+        assert_eq!(
+            prover.best,
+            Some(two_levels.clone()),
+            "The proof with more levels is kept"
+        );
+        assert!(
+            std::path::Path::new(&one_level.path).exists(),
+            "The proof the player was told about is not removed"
+        );
+
+        let _ = fs::remove_file(&one_level.path);
+        let _ = fs::remove_file(&two_levels.path);
+    }
+
+    #[test]
+    fn start_run_drops_previous_proofs_test() {
+        let mut prover = BackgroundProver::new();
+        let previous = proof_file("previous_run", 2);
+        prover.keep_best(previous.clone());
+
+        let next_run = ProofRun {
+            game_config: [1; 32],
+            attempt: 1,
+        };
+        prover.start_run(next_run);
+        assert!(
+            !prover.is_ready(1),
+            "A proof of the previous run isn't ready for the new one"
+        );
+        assert_eq!(
+            prover.status(),
+            ProverStatus::Idle,
+            "The new run has no proof"
+        );
+        assert!(
+            std::path::Path::new(&previous.path).exists(),
+            "The proof of the previous run stays on disk"
+        );
+        assert!(prover.wait_for(1).is_err(), "There is nothing to wait for");
+
+        let _ = fs::remove_file(&previous.path);
+    }
+
+    #[test]
+    fn nothing_to_prove_test() {
+        let mut prover = BackgroundProver::new();
+
+        prover.submit(vec![], vec![], String::new());
+        assert_eq!(
+            prover.status(),
+            ProverStatus::Idle,
+            "An empty log isn't proven"
+        );
+        assert!(prover.wait_for(1).is_err(), "There is nothing to wait for");
+    }
+}
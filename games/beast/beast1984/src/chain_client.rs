@@ -0,0 +1,333 @@
+//! this module contains the client every read from and write to the chain goes through
+//!
+//! A client holds one or more rpc endpoints of the same chain. Reads are tried on each endpoint in
+//! order until one answers in time. Transactions only go through the first endpoint, so a claim that
+//! timed out is never sent a second time through another one. Tests answer from mocked endpoints
+//! with [ChainClient::mocked] instead of the network.
+
+use std::{future::Future, time::Duration};
+
+use alloy::{
+    eips::BlockNumberOrTag,
+    network::EthereumWallet,
+    providers::{DynProvider, Provider, ProviderBuilder},
+    signers::local::PrivateKeySigner,
+    transports::{http::reqwest::Url, mock::Asserter},
+};
+use tracing::warn;
+
+use crate::error::BeastError;
+
+/// how long a single read may take before the next endpoint is tried
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// the public rpc endpoints of the network the game was built for, in the order they are tried
+pub fn network_rpc_urls() -> &'static [&'static str] {
+    #[cfg(feature = "devnet")]
+    return &["http://localhost:8545"];
+
+    #[cfg(feature = "holesky-stage")]
+    return &[
+        "https://ethereum-holesky-rpc.publicnode.com",
+        "https://holesky.drpc.org",
+    ];
+
+    #[cfg(feature = "holesky")]
+    return &[
+        "https://ethereum-holesky-rpc.publicnode.com",
+        "https://holesky.drpc.org",
+    ];
+
+    #[cfg(feature = "mainnet")]
+    return &[
+        "https://ethereum-rpc.publicnode.com",
+        "https://eth.drpc.org",
+    ];
+
+    #[cfg(feature = "sepolia")]
+    return &[
+        "https://ethereum-sepolia-rpc.publicnode.com",
+        "https://sepolia.drpc.org",
+    ];
+
+    &[]
+}
+
+/// split a comma separated list of rpc urls, like the one `ETH_RPC_URL` can hold
+pub fn split_rpc_urls(urls: &str) -> Vec<String> {
+    urls.split(',')
+        .map(str::trim)
+        .filter(|url| !url.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+#[derive(Debug, Clone)]
+struct Endpoint {
+    /// where the endpoint is, only used in messages
+    url: String,
+    provider: DynProvider,
+}
+
+/// the rpc endpoints of one chain
+#[derive(Debug, Clone)]
+pub struct ChainClient {
+    endpoints: Vec<Endpoint>,
+    timeout: Duration,
+}
+
+impl ChainClient {
+    /// connect to the endpoints to read from the chain
+    pub fn connect(rpc_urls: &[impl AsRef<str>]) -> Result<Self, BeastError> {
+        Self::connect_with(rpc_urls, |url| {
+            ProviderBuilder::new().connect_http(url).erased()
+        })
+    }
+
+    /// connect to the endpoints with a signer so transactions can be sent as well
+    pub fn connect_with_signer(
+        rpc_urls: &[impl AsRef<str>],
+        signer: PrivateKeySigner,
+    ) -> Result<Self, BeastError> {
+        let wallet = EthereumWallet::new(signer);
+        Self::connect_with(rpc_urls, |url| {
+            ProviderBuilder::new()
+                .wallet(wallet.clone())
+                .connect_http(url)
+                .erased()
+        })
+    }
+
+    fn connect_with(
+        rpc_urls: &[impl AsRef<str>],
+        connect: impl Fn(Url) -> DynProvider,
+    ) -> Result<Self, BeastError> {
+        if rpc_urls.is_empty() {
+            return Err(BeastError::Rpc(String::from("no rpc url is set")));
+        }
+
+        let endpoints = rpc_urls
+            .iter()
+            .map(|url| {
+                let url = url.as_ref();
+                let parsed = url
+                    .parse()
+                    .map_err(|_| BeastError::Rpc(format!("\"{url}\" is not a valid url")))?;
+                Ok(Endpoint {
+                    url: url.to_string(),
+                    provider: connect(parsed),
+                })
+            })
+            .collect::<Result<_, BeastError>>()?;
+
+        Ok(Self {
+            endpoints,
+            timeout: DEFAULT_TIMEOUT,
+        })
+    }
+
+    /// a client with one endpoint per asserter that answers with the responses pushed into it
+    pub fn mocked(asserters: impl IntoIterator<Item = Asserter>) -> Self {
+        let endpoints = asserters
+            .into_iter()
+            .enumerate()
+            .map(|(idx, asserter)| Endpoint {
+                url: format!("mock {idx}"),
+                provider: ProviderBuilder::new()
+                    .connect_mocked_client(asserter)
+                    .erased(),
+            })
+            .collect();
+
+        Self {
+            endpoints,
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+
+    /// set how long a single read may take before the next endpoint is tried
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// the url of the endpoint transactions are sent through
+    pub fn primary_url(&self) -> &str {
+        &self.endpoints[0].url
+    }
+
+    /// the provider transactions are sent through, it doesn't fall back to the other endpoints
+    pub fn primary(&self) -> DynProvider {
+        self.endpoints[0].provider.clone()
+    }
+
+    /// run a read on each endpoint in order until one answers in time and return the last error if
+    /// none did, a claim the leaderboard would reject is returned right away as every endpoint agrees
+    pub async fn read<T, F, Fut>(&self, what: &str, request: F) -> Result<T, BeastError>
+    where
+        F: Fn(DynProvider) -> Fut,
+        Fut: Future<Output = Result<T, BeastError>>,
+    {
+        let mut last_error = BeastError::Rpc(format!("there is no endpoint to read {what} from"));
+        for endpoint in &self.endpoints {
+            match tokio::time::timeout(self.timeout, request(endpoint.provider.clone())).await {
+                Ok(Ok(value)) => return Ok(value),
+                Ok(Err(error @ BeastError::ClaimWouldRevert(_))) => return Err(error),
+                Ok(Err(error)) => {
+                    warn!("reading {what} from {} failed: {error}", endpoint.url);
+                    last_error = error;
+                }
+                Err(_) => {
+                    warn!("reading {what} from {} timed out", endpoint.url);
+                    last_error = BeastError::Rpc(format!(
+                        "reading {what} from {} timed out after {}s",
+                        endpoint.url,
+                        self.timeout.as_secs_f32()
+                    ));
+                }
+            }
+        }
+        Err(last_error)
+    }
+
+    /// the timestamp of the latest block
+    pub async fn block_timestamp(&self) -> Result<u64, BeastError> {
+        self.read("the block timestamp", |provider| async move {
+            provider
+                .get_block_by_number(BlockNumberOrTag::Latest)
+                .await
+                .map_err(|e| BeastError::BlockTimestamp(e.to_string()))?
+                .map(|block| block.header.timestamp)
+                .ok_or_else(|| BeastError::BlockTimestamp(String::from("there is no latest block")))
+        })
+        .await
+    }
+
+    /// [ChainClient::block_timestamp] for code that doesn't run in an async runtime, like the game
+    pub fn block_timestamp_blocking(&self) -> Result<u64, BeastError> {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|e| BeastError::Rpc(format!("could not start the async runtime: {e}")))?
+            .block_on(self.block_timestamp())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::rpc::types::Block;
+    use std::cell::Cell;
+
+    fn block(timestamp: u64) -> Block {
+        let mut block: Block = Block::default();
+        block.header.inner.timestamp = timestamp;
+        block
+    }
+
+    #[test]
+    fn split_rpc_urls_test() {
+        assert_eq!(
+            split_rpc_urls("https://a.rpc"),
+            vec!["https://a.rpc"],
+            "A single url is kept"
+        );
+        assert_eq!(
+            split_rpc_urls(" https://a.rpc, https://b.rpc ,,"),
+            vec!["https://a.rpc", "https://b.rpc"],
+            "A list is split, trimmed and empty entries are dropped"
+        );
+        assert!(split_rpc_urls("").is_empty(), "An empty string has no urls");
+    }
+
+    #[test]
+    fn connect_test() {
+        assert!(
+            ChainClient::connect(&["https://a.rpc", "http://localhost:8545"]).is_ok(),
+            "Valid urls connect"
+        );
+        assert!(
+            matches!(
+                ChainClient::connect(&[] as &[&str]),
+                Err(BeastError::Rpc(_))
+            ),
+            "At least one url is needed"
+        );
+        assert!(
+            matches!(
+                ChainClient::connect(&["https://a.rpc", "not a url"]),
+                Err(BeastError::Rpc(_))
+            ),
+            "An invalid url is rejected"
+        );
+    }
+
+    #[tokio::test]
+    async fn block_timestamp_test() {
+        let first = Asserter::new();
+        let second = Asserter::new();
+        let client = ChainClient::mocked([first.clone(), second.clone()]);
+
+        first.push_success(&block(1_700_000_000));
+        assert_eq!(
+            client.block_timestamp().await.unwrap(),
+            1_700_000_000,
+            "The first endpoint answers"
+        );
+
+        first.push_failure_msg("rate limited");
+        second.push_success(&block(1_700_000_012));
+        assert_eq!(
+            client.block_timestamp().await.unwrap(),
+            1_700_000_012,
+            "A failing endpoint falls back to the next one"
+        );
+
+        first.push_failure_msg("rate limited");
+        second.push_failure_msg("down");
+        assert!(
+            matches!(
+                client.block_timestamp().await,
+                Err(BeastError::BlockTimestamp(error)) if error.contains("down")
+            ),
+            "The error of the last endpoint is returned when all fail"
+        );
+    }
+
+    #[tokio::test]
+    async fn read_test() {
+        let client = ChainClient::mocked([Asserter::new(), Asserter::new()])
+            .with_timeout(Duration::from_millis(50));
+
+        let calls = Cell::new(0);
+        let value = client
+            .read("a value", |_| {
+                calls.set(calls.get() + 1);
+                let slow = calls.get() == 1;
+                async move {
+                    if slow {
+                        tokio::time::sleep(Duration::from_secs(5)).await;
+                    }
+                    Ok(42)
+                }
+            })
+            .await;
+        assert_eq!(
+            value.unwrap(),
+            42,
+            "An endpoint that doesn't answer in time falls back to the next one"
+        );
+        assert_eq!(calls.get(), 2, "Both endpoints were asked");
+
+        let value: Result<u8, _> = client
+            .read("a value", |_| async {
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                Ok(42)
+            })
+            .await;
+        assert!(
+            matches!(value, Err(BeastError::Rpc(error)) if error.contains("timed out")),
+            "A timeout is reported when no endpoint answers in time"
+        );
+    }
+}
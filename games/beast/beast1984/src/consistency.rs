@@ -0,0 +1,355 @@
+//! this module contains the checks that the files of a campaign agree with each other and the contract
+//!
+//! Players prove the levels of `levels/<network>.json`, the proof commits the encoding of those levels
+//! and the leaderboard contract rejects it with `InvalidGame` when its `beastGames` entry has another
+//! config. So the game config and window of every game in the levels file is what the leaderboard file
+//! and the games on chain are compared against.
+
+use std::fmt;
+
+use alloy::hex;
+use campaign::{Leaderboard, LeaderboardGame};
+use game_logic::common::game::{GameJson, encode_game_config};
+
+/// a game as the leaderboard contract stores it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ContractGame {
+    pub starts_at: u64,
+    pub ends_at: u64,
+    pub config: [u8; 32],
+}
+
+impl ContractGame {
+    /// the game of a leaderboard file entry, `None` if one of its values isn't valid
+    pub fn from_leaderboard_game(game: &LeaderboardGame) -> Option<Self> {
+        let window = game.window()?;
+        let config = hex::decode(game.game_config.strip_prefix("0x")?).ok()?;
+        Some(Self {
+            starts_at: window.starts_at,
+            ends_at: window.ends_at,
+            config: config.try_into().ok()?,
+        })
+    }
+}
+
+/// where the games compared to the levels file come from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GamesSource {
+    LeaderboardFile,
+    Chain,
+}
+
+impl fmt::Display for GamesSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GamesSource::LeaderboardFile => write!(f, "the leaderboard file"),
+            GamesSource::Chain => write!(f, "the chain"),
+        }
+    }
+}
+
+/// a difference between the levels file and the other games of a campaign, games count from 0
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Mismatch {
+    /// the game config of the levels file isn't the encoding of its levels
+    UnencodedConfig {
+        game: usize,
+        config: String,
+        encoded: [u8; 32],
+    },
+    /// the source has another amount of games than the levels file
+    GameCount {
+        source: GamesSource,
+        levels: usize,
+        games: usize,
+    },
+    /// an entry of the leaderboard file can't be read
+    InvalidEntry { game: usize },
+    /// the source plays the game at other times
+    Window {
+        source: GamesSource,
+        game: usize,
+        levels: (u64, u64),
+        games: (u64, u64),
+    },
+    /// the source has another config for the game, proofs of its levels are rejected
+    Config {
+        source: GamesSource,
+        game: usize,
+        levels: [u8; 32],
+        games: [u8; 32],
+    },
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Mismatch::UnencodedConfig {
+                game,
+                config,
+                encoded,
+            } => write!(
+                f,
+                "game {game}: the game config {config} is not the encoding 0x{} of its levels",
+                hex::encode(encoded)
+            ),
+            Mismatch::GameCount {
+                source,
+                levels,
+                games,
+            } => write!(
+                f,
+                "the levels file has {levels} games but {source} has {games}"
+            ),
+            Mismatch::InvalidEntry { game } => {
+                write!(f, "game {game}: the leaderboard file entry can't be read")
+            }
+            Mismatch::Window {
+                source,
+                game,
+                levels,
+                games,
+            } => write!(
+                f,
+                "game {game}: the levels file plays it from {} to {} but {source} from {} to {}",
+                levels.0, levels.1, games.0, games.1
+            ),
+            Mismatch::Config {
+                source,
+                game,
+                levels,
+                games,
+            } => write!(
+                f,
+                "game {game}: the levels encode to 0x{} but {source} has 0x{}, proofs are rejected with InvalidGame",
+                hex::encode(levels),
+                hex::encode(games)
+            ),
+        }
+    }
+}
+
+/// check that the game config of every game is the encoding of its levels
+pub fn check_levels(games: &[GameJson]) -> Vec<Mismatch> {
+    games
+        .iter()
+        .enumerate()
+        .filter_map(|(game, json)| {
+            let encoded = encode_game_config(&json.levels);
+            let config = json.game_config.trim_start_matches("0x");
+            (hex::decode(config).ok().as_deref() != Some(&encoded[..])).then(|| {
+                Mismatch::UnencodedConfig {
+                    game,
+                    config: json.game_config.clone(),
+                    encoded,
+                }
+            })
+        })
+        .collect()
+}
+
+/// compare the window and the encoded levels of every game with the games of the source
+pub fn check_contract_games(
+    games: &[GameJson],
+    contract_games: &[ContractGame],
+    source: GamesSource,
+) -> Vec<Mismatch> {
+    let mut mismatches = Vec::new();
+
+    if games.len() != contract_games.len() {
+        mismatches.push(Mismatch::GameCount {
+            source,
+            levels: games.len(),
+            games: contract_games.len(),
+        });
+    }
+
+    for (game, (json, contract_game)) in games.iter().zip(contract_games).enumerate() {
+        let levels = (json.from_time, json.to_time);
+        let window = (contract_game.starts_at, contract_game.ends_at);
+        if levels != window {
+            mismatches.push(Mismatch::Window {
+                source,
+                game,
+                levels,
+                games: window,
+            });
+        }
+
+        let encoded = encode_game_config(&json.levels);
+        if encoded != contract_game.config {
+            mismatches.push(Mismatch::Config {
+                source,
+                game,
+                levels: encoded,
+                games: contract_game.config,
+            });
+        }
+    }
+
+    mismatches
+}
+
+/// compare the levels file with the leaderboard file the contract is configured from
+pub fn check_leaderboard_file(games: &[GameJson], leaderboard: &Leaderboard) -> Vec<Mismatch> {
+    let mut mismatches = Vec::new();
+    let mut contract_games = Vec::with_capacity(leaderboard.games.len());
+
+    for (game, entry) in leaderboard.games.iter().enumerate() {
+        match ContractGame::from_leaderboard_game(entry) {
+            Some(contract_game) => contract_games.push(contract_game),
+            None => mismatches.push(Mismatch::InvalidEntry { game }),
+        }
+    }
+    // the entries after one that can't be read would all be compared to the wrong game
+    if !mismatches.is_empty() {
+        return mismatches;
+    }
+
+    check_contract_games(games, &contract_games, GamesSource::LeaderboardFile)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use campaign::{GameWindow, LeaderboardGame};
+    use game_logic::common::levels::LevelJson;
+
+    fn game(from_time: u64, to_time: u64, blocks: u8) -> GameJson {
+        let level = LevelJson {
+            blocks,
+            static_blocks: 5,
+            common_beasts: 3,
+            super_beasts: 1,
+            eggs: 0,
+            egg_hatching_time: 20000,
+            beast_starting_distance: 5,
+            time: 150,
+            completion_score: 5,
+        };
+        let levels = vec![level.clone(), level.clone(), level];
+
+        GameJson {
+            game_config: hex::encode(encode_game_config(&levels)),
+            levels,
+            from_time,
+            to_time,
+            seed: None,
+            generator: None,
+        }
+    }
+
+    fn leaderboard(games: &[GameJson]) -> Leaderboard {
+        Leaderboard {
+            seed: None,
+            generator: None,
+            games: games
+                .iter()
+                .map(|game| {
+                    LeaderboardGame::new(
+                        &GameWindow {
+                            starts_at: game.from_time,
+                            ends_at: game.to_time,
+                        },
+                        &encode_game_config(&game.levels),
+                    )
+                })
+                .collect(),
+            appended: vec![],
+        }
+    }
+
+    #[test]
+    fn check_levels_test() {
+        let mut games = vec![game(100, 250, 10), game(200, 350, 20)];
+        assert!(check_levels(&games).is_empty(), "Encoded configs match");
+
+        games[1].game_config = format!("0x{}", games[1].game_config);
+        assert!(
+            check_levels(&games).is_empty(),
+            "A 0x prefixed config matches"
+        );
+
+        games[1].levels[2].super_beasts = 2;
+        assert_eq!(
+            check_levels(&games),
+            vec![Mismatch::UnencodedConfig {
+                game: 1,
+                config: games[1].game_config.clone(),
+                encoded: encode_game_config(&games[1].levels),
+            }],
+            "A level changed after the config was encoded"
+        );
+    }
+
+    #[test]
+    fn check_leaderboard_file_test() {
+        let games = vec![game(100, 250, 10), game(200, 350, 20)];
+        assert!(
+            check_leaderboard_file(&games, &leaderboard(&games)).is_empty(),
+            "A generated leaderboard file matches"
+        );
+
+        let mut other = leaderboard(&games);
+        other.games.swap(0, 1);
+        other.games.push(other.games[0].clone());
+        assert_eq!(
+            check_leaderboard_file(&games, &other)
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            vec![
+                String::from("the levels file has 2 games but the leaderboard file has 3"),
+                String::from(
+                    "game 0: the levels file plays it from 100 to 250 but the leaderboard file from 200 to 350"
+                ),
+                format!(
+                    "game 0: the levels encode to 0x{} but the leaderboard file has 0x{}, proofs are rejected with InvalidGame",
+                    hex::encode(encode_game_config(&games[0].levels)),
+                    hex::encode(encode_game_config(&games[1].levels))
+                ),
+                String::from(
+                    "game 1: the levels file plays it from 200 to 350 but the leaderboard file from 100 to 250"
+                ),
+                format!(
+                    "game 1: the levels encode to 0x{} but the leaderboard file has 0x{}, proofs are rejected with InvalidGame",
+                    hex::encode(encode_game_config(&games[1].levels)),
+                    hex::encode(encode_game_config(&games[0].levels))
+                ),
+            ],
+            "Every difference is reported"
+        );
+
+        let mut broken = leaderboard(&games);
+        broken.games[1].ends_at_time = String::from("0x10");
+        assert_eq!(
+            check_leaderboard_file(&games, &broken),
+            vec![Mismatch::InvalidEntry { game: 1 }],
+            "An entry that can't be read is reported"
+        );
+    }
+
+    #[test]
+    fn check_contract_games_test() {
+        let games = vec![game(100, 250, 10), game(200, 350, 20)];
+        let on_chain = leaderboard(&games)
+            .games
+            .iter()
+            .filter_map(ContractGame::from_leaderboard_game)
+            .collect::<Vec<_>>();
+
+        assert!(
+            check_contract_games(&games, &on_chain, GamesSource::Chain).is_empty(),
+            "The games on chain match"
+        );
+        assert_eq!(
+            check_contract_games(&games, &on_chain[..1], GamesSource::Chain),
+            vec![Mismatch::GameCount {
+                source: GamesSource::Chain,
+                levels: 2,
+                games: 1
+            }],
+            "A game that wasn't added on chain is reported"
+        );
+    }
+}
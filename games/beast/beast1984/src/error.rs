@@ -0,0 +1,123 @@
+//! this module contains the error type that is surfaced to the player
+
+use std::{fmt, io};
+
+use chrono::{DateTime, Utc};
+use game_logic::common::game::GameLevelsError;
+
+use crate::{aligned_client::AlignedError, leaderboard::ClaimRevert, sp1_prover::ProvingError};
+
+/// everything that can go wrong outside of the game rules themselves
+#[derive(Debug)]
+pub enum BeastError {
+    /// reading the player input from the terminal failed
+    Stdin(io::Error),
+    /// the terminal could not be switched into raw mode
+    RawMode(io::Error),
+    /// the block timestamp could not be retrieved from the rpc
+    BlockTimestamp(String),
+    /// none of the rpc endpoints answered
+    Rpc(String),
+    /// the game levels file could not be read or downloaded
+    LevelsFetch(String),
+    /// the game levels file is not valid json
+    LevelsParse(serde_json::Error),
+    /// there is no game to play for the current block timestamp
+    NoActiveGame {
+        /// when the next game starts (unix seconds), if one is scheduled
+        next_game_starts_at: Option<u64>,
+    },
+    /// generating or saving the proof failed
+    Proving(ProvingError),
+    /// talking to aligned failed
+    Aligned(AlignedError),
+    /// the settings file could not be read or is not valid
+    Settings(String),
+    /// reading from or claiming on the leaderboard contract failed
+    Leaderboard(String),
+    /// the leaderboard would reject the claim
+    ClaimWouldRevert(ClaimRevert),
+    /// the player couldn't prove owning their address
+    AddressOwnership(String),
+    /// the options of the submit tool are missing or not valid
+    SubmitConfig(String),
+}
+
+impl fmt::Display for BeastError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BeastError::Stdin(error) => write!(f, "Could not read from the terminal: {error}"),
+            BeastError::RawMode(error) => {
+                write!(f, "Raw mode could not be entered in this shell: {error}")
+            }
+            BeastError::BlockTimestamp(error) => {
+                write!(
+                    f,
+                    "Could not get the current block timestamp from the rpc: {error}"
+                )
+            }
+            BeastError::Rpc(error) => write!(f, "RPC request failed: {error}"),
+            BeastError::LevelsFetch(error) => write!(f, "Could not load the game levels: {error}"),
+            BeastError::LevelsParse(error) => {
+                write!(f, "The game levels file is not valid: {error}")
+            }
+            BeastError::NoActiveGame {
+                next_game_starts_at: Some(timestamp),
+            } => write!(
+                f,
+                "There is no game to play right now. The next game starts at {}.",
+                format_timestamp(*timestamp)
+            ),
+            BeastError::NoActiveGame {
+                next_game_starts_at: None,
+            } => write!(
+                f,
+                "There is no game to play right now and no upcoming game is scheduled yet."
+            ),
+            BeastError::Proving(error) => write!(f, "Could not prove program: {error}"),
+            BeastError::Aligned(error) => write!(f, "Aligned request failed: {error}"),
+            BeastError::Settings(error) => write!(f, "Could not load the settings file: {error}"),
+            BeastError::Leaderboard(error) => write!(f, "Leaderboard request failed: {error}"),
+            BeastError::ClaimWouldRevert(revert) => {
+                write!(f, "The leaderboard would reject the claim with {revert}")
+            }
+            BeastError::AddressOwnership(error) => {
+                write!(f, "Could not prove the ownership of the address: {error}")
+            }
+            BeastError::SubmitConfig(error) => write!(f, "Invalid submit options: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for BeastError {}
+
+impl From<ProvingError> for BeastError {
+    fn from(error: ProvingError) -> Self {
+        BeastError::Proving(error)
+    }
+}
+
+impl From<AlignedError> for BeastError {
+    fn from(error: AlignedError) -> Self {
+        BeastError::Aligned(error)
+    }
+}
+
+impl From<GameLevelsError> for BeastError {
+    fn from(error: GameLevelsError) -> Self {
+        match error {
+            GameLevelsError::NoActiveGame {
+                next_game_starts_at,
+            } => BeastError::NoActiveGame {
+                next_game_starts_at,
+            },
+        }
+    }
+}
+
+/// format a unix timestamp in seconds as a human readable UTC date
+pub fn format_timestamp(timestamp: u64) -> String {
+    DateTime::<Utc>::from_timestamp(timestamp as i64, 0)
+        .map(|date| date.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+        .unwrap_or_else(|| timestamp.to_string())
+}
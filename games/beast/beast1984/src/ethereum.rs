@@ -1,76 +1,211 @@
 use alloy::primitives::Address;
-use std::io;
+use std::{env, io};
+
+use crate::{error::BeastError, settings::Settings, submit_config::SignerSource};
+
+/// the env var with a private key to prove the player address with, a keystore in the settings wins
+pub const PRIVATE_KEY_ENV_VAR: &str = "BEAST_PRIVATE_KEY";
+
+/// how an address was typed, only a checksummed address protects against typos
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressChecksum {
+    /// the mixed case matches the EIP-55 checksum
+    Valid,
+    /// all lower or all upper case, so typos can't be caught
+    Missing,
+    /// mixed case that doesn't match the checksum, most likely a typo
+    Invalid,
+}
+
+/// check the EIP-55 checksum of an address that is known to parse
+pub fn address_checksum(address: &str) -> AddressChecksum {
+    let digits = address.trim_start_matches("0x");
+
+    if Address::parse_checksummed(address, None).is_ok() {
+        AddressChecksum::Valid
+    } else if digits == digits.to_lowercase() || digits == digits.to_uppercase() {
+        AddressChecksum::Missing
+    } else {
+        AddressChecksum::Invalid
+    }
+}
+
+/// the address of an unlocked key, it has to be the one of the settings file if that has one
+pub fn check_signer_address(
+    signer: Address,
+    expected: Option<&str>,
+) -> Result<Address, BeastError> {
+    let Some(expected) = expected else {
+        return Ok(signer);
+    };
+
+    let expected = expected.parse::<Address>().map_err(|_| {
+        BeastError::AddressOwnership(format!(
+            "the settings file has the invalid address '{expected}'"
+        ))
+    })?;
+    if expected != signer {
+        return Err(BeastError::AddressOwnership(format!(
+            "the key is for {signer} but the settings file has the address {expected}"
+        )));
+    }
+
+    Ok(signer)
+}
+
+/// find out who plays, by unlocking the keystore of the settings or the private key of the env, and
+/// by asking for the address otherwise
+pub fn read_player_address(settings: &Settings) -> Result<String, BeastError> {
+    let source = match (&settings.keystore, env::var(PRIVATE_KEY_ENV_VAR).ok()) {
+        (Some(keystore), _) => SignerSource::Keystore(keystore.into()),
+        (None, Some(key)) => SignerSource::PrivateKey(key),
+        (None, None) => return read_address(settings.address.as_deref()),
+    };
+
+    let signer = source.load(|name| env::var(name).ok())?;
+    let address = check_signer_address(signer.address(), settings.address.as_deref())?;
+    println!("\x1B[32m✔\x1B[0m You own {address}");
+
+    Ok(address.to_checksum(None))
+}
+
+fn read_line() -> Result<String, BeastError> {
+    let mut line = String::new();
+    let read = io::stdin()
+        .read_line(&mut line)
+        .map_err(BeastError::Stdin)?;
+    if read == 0 {
+        return Err(BeastError::Stdin(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            "stdin was closed before an address was entered",
+        )));
+    }
+    Ok(line.trim().to_string())
+}
+
+/// ask for the player address, an empty answer picks the address from the settings file
+pub fn read_address(default: Option<&str>) -> Result<String, BeastError> {
+    // a settings address with a broken checksum is as likely to have a typo as a typed one
+    let default = default.filter(|address| {
+        is_valid_address(address) && address_checksum(address) != AddressChecksum::Invalid
+    });
 
-pub fn read_address() -> String {
     loop {
-        println!("Please enter your Ethereum address to start the game:");
-        let mut address = String::new();
+        match default {
+            Some(default) => println!(
+                "Please enter your Ethereum address to start the game or press [ENTER] to use {default}:"
+            ),
+            None => println!("Please enter your Ethereum address to start the game:"),
+        }
 
-        io::stdin()
-            .read_line(&mut address)
-            .expect("Failed to read from stdin");
-        address = address.trim().to_string();
+        let address = read_line()?;
+        if let Some(default) = default.filter(|_| address.is_empty()) {
+            return Ok(default.to_string());
+        }
 
-        if !is_valid_address(&address) {
+        let Ok(parsed) = address.parse::<Address>() else {
             println!("Invalid Ethereum address: '{}'. Please try again.", address);
             continue;
+        };
+        match address_checksum(&address) {
+            AddressChecksum::Valid => return Ok(address),
+            AddressChecksum::Invalid => {
+                println!(
+                    "\x1B[31mERROR:\x1B[0m '{address}' doesn't match its checksum, it most likely has a typo. Please try again."
+                );
+            }
+            AddressChecksum::Missing => {
+                println!(
+                    "\x1B[31mWARNING:\x1B[0m '{address}' has no checksum, so a typo can't be caught and your proofs would be bound to the wrong account."
+                );
+                println!(
+                    "Press [ENTER] to play as {} or enter the address again:",
+                    parsed.to_checksum(None)
+                );
+                if read_line()?.is_empty() {
+                    return Ok(parsed.to_checksum(None));
+                }
+            }
         }
-        return address;
     }
 }
 
 fn is_valid_address(address: &str) -> bool {
-    match address.parse::<Address>() {
-        Ok(_) => true,
-        Err(_) => false,
-    }
+    address.parse::<Address>().is_ok()
 }
 
-fn match_network_rpc_url() -> Option<&'static str> {
+/// the network the game was built for, written into the proof files
+pub fn network_name() -> &'static str {
     #[cfg(feature = "devnet")]
-    return Some("http://localhost:8545");
+    return "devnet";
 
     #[cfg(feature = "holesky-stage")]
-    return Some("https://ethereum-holesky-rpc.publicnode.com");
+    return "holesky-stage";
 
     #[cfg(feature = "holesky")]
-    return Some("https://ethereum-holesky-rpc.publicnode.com");
+    return "holesky";
 
     #[cfg(feature = "mainnet")]
-    return Some("https://ethereum-rpc.publicnode.com");
+    return "mainnet";
 
     #[cfg(feature = "sepolia")]
-    return Some("https://ethereum-sepolia-rpc.publicnode.com");
+    return "sepolia";
 
-    None
+    "unknown"
 }
 
-pub fn get_current_block_timestamp() -> Result<u64, String> {
-    let rpc_url = match_network_rpc_url().ok_or("No network feature enabled")?;
-
-    let body = serde_json::json!({
-        "jsonrpc": "2.0",
-        "method": "eth_getBlockByNumber",
-        "params": ["latest", false],
-        "id": 1
-    })
-    .to_string();
-
-    let response = ureq::post(rpc_url)
-        .set("Content-Type", "application/json")
-        .send_string(&body)
-        .map_err(|e| format!("Request failed: {}", e))?;
-
-    let json: serde_json::Value =
-        serde_json::from_str(&response.into_string().map_err(|e| e.to_string())?)
-            .map_err(|e| format!("Invalid JSON response: {}", e))?;
-
-    let hex_timestamp = json
-        .get("result")
-        .and_then(|r| r.get("timestamp"))
-        .and_then(|t| t.as_str())
-        .ok_or("Missing 'timestamp' field in block JSON")?;
-
-    u64::from_str_radix(hex_timestamp.trim_start_matches("0x"), 16)
-        .map_err(|e| format!("Invalid hex value for block timestamp: {}", e))
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn address_checksum_test() {
+        assert_eq!(
+            address_checksum("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"),
+            AddressChecksum::Valid,
+            "A checksummed address is valid"
+        );
+        assert_eq!(
+            address_checksum("0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed"),
+            AddressChecksum::Missing,
+            "A lower case address has no checksum"
+        );
+        assert_eq!(
+            address_checksum("0x5AAEB6053F3E94C9B9A09F33669435E7EF1BEAED"),
+            AddressChecksum::Missing,
+            "An upper case address has no checksum"
+        );
+        assert_eq!(
+            address_checksum("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD"),
+            AddressChecksum::Invalid,
+            "A mixed case address with a wrong checksum is invalid"
+        );
+    }
+
+    #[test]
+    fn check_signer_address_test() {
+        let signer: Address = "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"
+            .parse()
+            .unwrap();
+
+        assert_eq!(
+            check_signer_address(signer, None).ok(),
+            Some(signer),
+            "Without a settings address the key decides"
+        );
+        assert_eq!(
+            check_signer_address(signer, Some("0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed")).ok(),
+            Some(signer),
+            "The settings address matches the key"
+        );
+        assert!(
+            check_signer_address(signer, Some("0x0000000000000000000000000000000000000001"))
+                .is_err(),
+            "A key for another address is refused"
+        );
+        assert!(
+            check_signer_address(signer, Some("0x12")).is_err(),
+            "An invalid settings address is refused"
+        );
+    }
 }
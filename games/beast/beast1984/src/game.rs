@@ -2,29 +2,36 @@
 
 use crate::levels::get_game_levels;
 use crate::{
+    background_prover::{BackgroundProver, ProofRun},
+    chain_client::{network_rpc_urls, ChainClient},
+    error::{format_timestamp, BeastError},
     ethereum,
     help::Help,
-    sp1_prover::{prove as sp1_prove, save_proof as sp1_save_proof},
+    renderer::{format_countdown, render_centered_line, render_window_status, TerminalRenderer},
+    settings::{Action, Settings},
+    sp1_prover::ProvingError,
     stty::{install_raw_mode_signal_handler, RawMode},
 };
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
 use dialoguer::MultiSelect;
 use game_logic::{
     beasts::{Beast, BeastAction, CommonBeast, Egg, HatchedBeast, HatchingState, SuperBeast},
     board::Board,
-    common::{game::GameLevels, levels::Level},
+    common::{
+        game::{encode_game_config, GameJson, GameLevels, GameLevelsError, WindowStatus},
+        levels::Level,
+    },
     player::{Player, PlayerAction},
     proving::{GameLogEntry, LevelLog},
-    Dir, Tile, ANSI_BOLD, ANSI_LEFT_BORDER, ANSI_RESET, ANSI_RESET_BG, ANSI_RESET_FONT,
-    ANSI_RIGHT_BORDER, BOARD_HEIGHT, BOARD_WIDTH, LOGO,
+    render::{Flash, Frame, Hud, Modal, Renderer},
+    Dir, Tile, ANSI_BOLD, ANSI_LEFT_BORDER, ANSI_RESET, ANSI_RESET_FONT, ANSI_RIGHT_BORDER,
+    BOARD_HEIGHT, BOARD_WIDTH, LOGO,
 };
 use std::{
     io::{self, Read, Write},
-    thread::{self, JoinHandle},
+    thread::JoinHandle,
     time::{Duration, Instant},
 };
-use crossterm::{
-    event::{self, Event, KeyCode, KeyEventKind},
-};
 
 /// the height of the board
 pub const ANSI_BOARD_HEIGHT: usize = BOARD_HEIGHT;
@@ -39,6 +46,10 @@ const TICK_DURATION: Duration = Duration::from_millis(200);
 
 const SP1: &str = "SP1";
 
+/// how long it takes to prove a run, get it verified on aligned and claim it, games closing sooner
+/// are flagged to the player
+const PROOF_SUBMISSION_SECS: u64 = 30 * 60;
+
 /// we need the [Beat] to count down when we call the beast advance methods and for animations
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Beat {
@@ -73,6 +84,8 @@ pub enum GameState {
     Dying(Beat),
     /// the killing state is to make sure we can keep track of dying animations, it is also a playing state
     Killing(Beat),
+    /// the game is paused, the clock, eggs and beasts are frozen until the player resumes
+    Paused,
     /// the level is done and we display a modal to give the player a short pause
     LevelComplete,
     /// displaying the help screen
@@ -87,11 +100,16 @@ pub enum GameState {
     Won,
     /// the game was lost
     Quit,
+    /// the game window rolled over or closed while playing and the player has to pick what to do
+    WindowChanged,
+    /// the game closed and there is no other game to play, no new run can be started
+    CampaignOver,
 }
 
 /// this is our main game struct that orchestrates the game and its bits
 pub struct Game {
-    pub block_timestamp: u64, // Currently unused
+    /// the block timestamp when the game started, the chain time is counted on from it
+    pub block_timestamp: u64,
     /// our board
     pub board: Board,
     /// the current level we're in
@@ -114,35 +132,61 @@ pub struct Game {
     /// Store the log for proving the completion of games in the zkvm
     pub levels_completion_log: Vec<LevelLog>,
     pub has_won: bool,
+    /// all games of the campaign, to switch to the next one when the window rolls over
+    games: Vec<GameJson>,
+    /// when [Game::block_timestamp] was read
+    clock_start: Instant,
+    /// the status of the window the player was last told about
+    window_notice: Option<WindowStatus>,
     beat: Beat,
     _raw_mode: RawMode,
     address: String,
     proving_systems: Vec<String>,
     proof_completion_message: String,
+    renderer: TerminalRenderer,
+    settings: Settings,
+    prover: BackgroundProver,
+    /// how many games were started, the proofs of every attempt are kept apart
+    attempts: u64,
 }
 
 impl Game {
     /// create a new instance of the beast game
-    pub fn new() -> Self {
-        let address = ethereum::read_address();
+    pub fn new() -> Result<Self, BeastError> {
+        let settings = Settings::load()?;
+        let address = ethereum::read_player_address(&settings)?;
 
         let proving_systems = vec![SP1.to_string()];
 
-        let block_timestamp = ethereum::get_current_block_timestamp()
-            .expect("Could not get block timestamp from rpc");
+        let block_timestamp =
+            ChainClient::connect(network_rpc_urls())?.block_timestamp_blocking()?;
         println!("Loading game for block timestamp {}...", block_timestamp);
 
-        let game_levels = get_game_levels();
+        let game_levels = get_game_levels()?;
 
-        let game_match = GameLevels::new(block_timestamp, game_levels);
+        let game_match = GameLevels::new(block_timestamp, game_levels.clone())?;
+        if let Some(window) = game_match.window() {
+            println!(
+                "Game {} is open until {}, its proofs can be claimed until {}.",
+                window.index,
+                format_timestamp(window.play_until),
+                format_timestamp(window.submit_until)
+            );
+            if let WindowStatus::Closing { secs_to_submit } =
+                window.status(block_timestamp, PROOF_SUBMISSION_SECS)
+            {
+                println!(
+                    "\x1B[33mWARNING:\x1B[0m claims for this game close in {}, a proof made now might not be verified and claimed in time.",
+                    format_countdown(secs_to_submit)
+                );
+            }
+        }
+        let clock_start = Instant::now();
 
         let board_terrain_info = Board::generate_terrain(game_match.get_config(Level::One));
 
         install_raw_mode_signal_handler();
-        let _raw_mode = RawMode::enter().unwrap_or_else(|error| {
-            eprintln!("Raw mode could not be entered in this shell: {error}\x1b[?25h",);
-            std::process::exit(1);
-        });
+        let _raw_mode = RawMode::enter().map_err(BeastError::RawMode)?;
 
         let board = Board::new(board_terrain_info.buffer);
 
@@ -152,7 +196,7 @@ impl Game {
             game_log: vec![],
         };
 
-        Self {
+        let mut game = Self {
             levels_completion_log: vec![fist_level_log],
             block_timestamp,
             board: Board::new(board_terrain_info.buffer),
@@ -169,13 +213,28 @@ impl Game {
             has_won: false,
             _raw_mode,
             address,
+            games: game_levels,
+            clock_start,
+            window_notice: None,
             proving_systems,
             proof_completion_message: String::new(),
-        }
+            renderer: TerminalRenderer::with_settings(settings.theme.clone(), &settings.keys),
+            settings,
+            prover: BackgroundProver::new(),
+            attempts: 0,
+        };
+        game.prover.start_run(game.proof_run());
+        Ok(game)
     }
 
     pub fn start_new_game(&mut self) {
         Self::clear_screen();
+        if !self.switch_to_current_game() {
+            self.state = GameState::CampaignOver;
+            return;
+        }
+        self.attempts += 1;
+        self.prover.start_run(self.proof_run());
         self.level = Level::One;
         let board_terrain_info = Board::generate_terrain(self.game_match.get_config(self.level));
         let board = Board::new(board_terrain_info.buffer);
@@ -194,7 +253,7 @@ impl Game {
         self.eggs = board_terrain_info.eggs;
         self.hatched_beasts = board_terrain_info.hatched_beasts;
         self.player = board_terrain_info.player;
-        self.player.score = 0;
+        self.player.score = Level::One.start_score(&self.game_match.get_config(Level::One));
         self.has_won = false;
         self.beat = Beat::One;
         self.state = GameState::Playing;
@@ -212,6 +271,9 @@ impl Game {
                 GameState::Playing | GameState::Dying(_) | GameState::Killing(_) => {
                     self.handle_playing_state(last_tick);
                 }
+                GameState::Paused => {
+                    self.handle_paused_state();
+                }
                 GameState::LevelComplete => {
                     self.handle_level_complete();
                 }
@@ -234,29 +296,36 @@ impl Game {
                     println!("Bye...");
                     break;
                 }
+                GameState::WindowChanged => {
+                    self.handle_window_changed_state();
+                }
+                GameState::CampaignOver => {
+                    self.handle_campaign_over_state();
+                }
             }
         }
     }
 
     fn handle_intro_state(&mut self) {
-        println!("{}", Self::render_intro());
+        println!("{}", self.render_intro());
 
         loop {
             if event::poll(Duration::from_millis(100)).unwrap_or(false) {
                 if let Ok(Event::Key(key_event)) = event::read() {
                     if key_event.kind == KeyEventKind::Press {
-                        match key_event.code {
-                            KeyCode::Char(' ') => {
+                        let actions = [Action::Play, Action::Help, Action::Quit];
+                        match self.settings.keys.action(key_event.code, &actions) {
+                            Some(Action::Play) => {
                                 self.level_start = Into::into(Instant::now());
                                 self.state = GameState::Playing;
                                 break;
                             }
-                            KeyCode::Char('h') | KeyCode::Char('H') => {
+                            Some(Action::Help) => {
                                 self.level_start = Into::into(Instant::now());
                                 self.state = GameState::Help;
                                 break;
                             }
-                            KeyCode::Char('q') | KeyCode::Char('Q') => {
+                            Some(Action::Quit) => {
                                 self.state = GameState::Quit;
                                 break;
                             }
@@ -269,51 +338,56 @@ impl Game {
     }
 
     fn handle_playing_state(&mut self, mut last_tick: Instant) {
-        print!("{}", self.render_board());
+        // whatever screen we come from has drawn over the board
+        self.renderer.invalidate();
+        self.render_board(Flash::None);
 
         loop {
             // Handle crossterm input events
             if event::poll(Duration::from_millis(1)).unwrap_or(false) {
-                if let Ok(Event::Key(key_event)) = event::read() {
+                let event = event::read();
+                if let Ok(Event::Resize(_, _)) = event {
+                    self.renderer.invalidate();
+                    self.render_with_state();
+                }
+                if let Ok(Event::Key(key_event)) = event {
                     // Only handle key press events, ignore key release and repeat
                     if key_event.kind == KeyEventKind::Press {
-                        match key_event.code {
-                        // Arrow keys
-                        KeyCode::Up => {
-                            self.handle_movement(Dir::Up);
-                        }
-                        KeyCode::Down => {
-                            self.handle_movement(Dir::Down);
-                        }
-                        KeyCode::Left => {
-                            self.handle_movement(Dir::Left);
-                        }
-                        KeyCode::Right => {
-                            self.handle_movement(Dir::Right);
-                        }
-                        // WASD keys
-                        KeyCode::Char('w') | KeyCode::Char('W') => {
-                            self.handle_movement(Dir::Up);
-                        }
-                        KeyCode::Char('s') | KeyCode::Char('S') => {
-                            self.handle_movement(Dir::Down);
-                        }
-                        KeyCode::Char('a') | KeyCode::Char('A') => {
-                            self.handle_movement(Dir::Left);
-                        }
-                        KeyCode::Char('d') | KeyCode::Char('D') => {
-                            self.handle_movement(Dir::Right);
-                        }
-                        // Other keys
-                        KeyCode::Char('q') | KeyCode::Char('Q') => {
-                            self.state = GameState::Quit;
-                            break;
-                        }
-                        KeyCode::Char('h') | KeyCode::Char('H') => {
-                            self.state = GameState::Help;
-                            break;
-                        }
-                        _ => {}
+                        let actions = [
+                            Action::Up,
+                            Action::Down,
+                            Action::Left,
+                            Action::Right,
+                            Action::Pause,
+                            Action::Quit,
+                            Action::Help,
+                        ];
+                        match self.settings.keys.action(key_event.code, &actions) {
+                            Some(Action::Up) => {
+                                self.handle_movement(Dir::Up);
+                            }
+                            Some(Action::Down) => {
+                                self.handle_movement(Dir::Down);
+                            }
+                            Some(Action::Left) => {
+                                self.handle_movement(Dir::Left);
+                            }
+                            Some(Action::Right) => {
+                                self.handle_movement(Dir::Right);
+                            }
+                            Some(Action::Pause) => {
+                                self.state = GameState::Paused;
+                                break;
+                            }
+                            Some(Action::Quit) => {
+                                self.state = GameState::Quit;
+                                break;
+                            }
+                            Some(Action::Help) => {
+                                self.state = GameState::Help;
+                                break;
+                            }
+                            _ => {}
                         }
                     }
                 }
@@ -350,7 +424,7 @@ impl Game {
                 == 0
             {
                 let secs_remaining = self.get_secs_remaining();
-                self.player.score += secs_remaining as u16 / 10;
+                self.player.score += Level::completed_score(secs_remaining);
 
                 self.state = GameState::LevelComplete;
                 break;
@@ -408,6 +482,11 @@ impl Game {
                     break;
                 }
 
+                self.poll_prover();
+                if self.window_needs_notice() {
+                    self.state = GameState::WindowChanged;
+                    break;
+                }
                 // render with Dying and Killing animation
                 self.render_with_state();
                 self.beat = self.beat.next();
@@ -416,41 +495,294 @@ impl Game {
         }
     }
 
-    fn handle_death_state(&mut self) {
-        println!("{}", self.render_death_screen());
+    fn handle_paused_state(&mut self) {
+        let pause = Instant::now();
+        let keys = &self.settings.keys;
+        let modal = Modal {
+            title: String::from("PAUSED"),
+            lines: vec![
+                format!("Press {} to continue", keys.label(Action::Pause)),
+                format!(
+                    "{} Quit  {} Help",
+                    keys.label(Action::Quit),
+                    keys.label(Action::Help)
+                ),
+            ],
+        };
+        // the HUD is taken once so the frozen clock doesn't tick on redraws
+        let frame = Frame::new(&self.board, self.hud()).with_modal(modal);
+        self.draw_frame(&frame);
 
         loop {
             if event::poll(Duration::from_millis(100)).unwrap_or(false) {
-                if let Ok(Event::Key(key_event)) = event::read() {
+                let event = event::read();
+                if let Ok(Event::Resize(_, _)) = event {
+                    self.renderer.invalidate();
+                    self.draw_frame(&frame);
+                }
+                if let Ok(Event::Key(key_event)) = event {
                     if key_event.kind == KeyEventKind::Press {
-                        match key_event.code {
-                        KeyCode::Char(' ') => {
-                            if Self::render_confirmation_prompt("Are you sure you want to restart the game?") {
-                                self.start_new_game();
+                        let actions = [Action::Pause, Action::Play, Action::Help, Action::Quit];
+                        match self.settings.keys.action(key_event.code, &actions) {
+                            Some(Action::Pause) | Some(Action::Play) => {
+                                self.resume(pause);
+                                self.state = GameState::Playing;
                                 break;
-                            } else {
-                                println!("{}", self.render_death_screen());
+                            }
+                            Some(Action::Help) => {
+                                self.resume(pause);
+                                self.state = GameState::Help;
                                 break;
                             }
+                            Some(Action::Quit) => {
+                                self.state = GameState::Quit;
+                                break;
+                            }
+                            _ => {}
                         }
-                        KeyCode::Enter => {
-                            self.state = GameState::ProveExecution;
-                            break;
-                        }
-                        KeyCode::Char('h') | KeyCode::Char('H') => {
-                            self.state = GameState::Help;
-                            break;
-                        }
-                        KeyCode::Char('q') | KeyCode::Char('Q') => {
-                            if Self::render_confirmation_prompt("Are you sure you want to quit?") {
+                    }
+                }
+            }
+        }
+    }
+
+    /// shift the level clock and the eggs by the time spent paused, the replay only has moves so the
+    /// pause stays out of it and proofs of paused runs are the same as of any other run
+    fn resume(&mut self, pause: Instant) {
+        let paused = pause.elapsed();
+        self.level_start += paused;
+        for egg in self.eggs.iter_mut() {
+            egg.delay(paused);
+        }
+    }
+
+    /// the chain time, counted on from the block timestamp read at the start
+    fn chain_time(&self) -> u64 {
+        self.block_timestamp + self.clock_start.elapsed().as_secs()
+    }
+
+    fn window_status(&self) -> Option<WindowStatus> {
+        self.game_match
+            .window()
+            .map(|window| window.status(self.chain_time(), PROOF_SUBMISSION_SECS))
+    }
+
+    /// has the game rolled over or closed since the player was last told
+    fn window_needs_notice(&self) -> bool {
+        let changed = |status: &WindowStatus| {
+            matches!(
+                status,
+                WindowStatus::RolledOver { .. } | WindowStatus::Expired
+            )
+        };
+        match (self.window_status(), self.window_notice) {
+            (Some(WindowStatus::Expired), Some(WindowStatus::Expired)) => false,
+            (Some(WindowStatus::Expired), _) => true,
+            (Some(status), notice) => {
+                changed(&status) && !notice.is_some_and(|notice| changed(&notice))
+            }
+            (None, _) => false,
+        }
+    }
+
+    /// pick the game that is open now if the one being played was taken over by a newer one, returns
+    /// false when the game closed and there is no game to switch to
+    fn switch_to_current_game(&mut self) -> bool {
+        let status = self.window_status();
+        if !matches!(
+            status,
+            Some(WindowStatus::RolledOver { .. } | WindowStatus::Expired)
+        ) {
+            return true;
+        }
+
+        match GameLevels::new(self.chain_time(), self.games.clone()) {
+            Ok(game_match) => {
+                self.game_match = game_match;
+                self.window_notice = None;
+                self.prover.start_run(self.proof_run());
+                true
+            }
+            // the proofs of a game that rolled over can still be claimed so it can be played on
+            Err(_) => !matches!(status, Some(WindowStatus::Expired)),
+        }
+    }
+
+    /// the run the background proofs are made for
+    fn proof_run(&self) -> ProofRun {
+        ProofRun {
+            game_config: encode_game_config(&self.game_match.get_levels_in_json()),
+            attempt: self.attempts,
+        }
+    }
+
+    fn handle_window_changed_state(&mut self) {
+        let pause = Instant::now();
+        let status = self.window_status();
+        self.window_notice = status;
+        let keys = &self.settings.keys;
+        let expired = matches!(status, Some(WindowStatus::Expired));
+
+        let modal = if expired {
+            Modal {
+                title: String::from("THIS GAME IS OVER"),
+                lines: vec![
+                    String::from("Its proofs can't be claimed anymore"),
+                    format!(
+                        "{} Play the current game  {} Quit",
+                        keys.label(Action::Play),
+                        keys.label(Action::Quit)
+                    ),
+                ],
+            }
+        } else {
+            Modal {
+                title: String::from("A NEW GAME STARTED"),
+                lines: vec![
+                    status.map(render_window_status).unwrap_or_default(),
+                    format!(
+                        "{} Switch to the new game  {} Prove this run",
+                        keys.label(Action::Play),
+                        keys.label(Action::Prove)
+                    ),
+                    format!(
+                        "{} Keep playing  {} Quit",
+                        keys.label(Action::Pause),
+                        keys.label(Action::Quit)
+                    ),
+                ],
+            }
+        };
+        let frame = Frame::new(&self.board, self.hud()).with_modal(modal);
+        self.draw_frame(&frame);
+
+        loop {
+            if event::poll(Duration::from_millis(100)).unwrap_or(false) {
+                let event = event::read();
+                if let Ok(Event::Resize(_, _)) = event {
+                    self.renderer.invalidate();
+                    self.draw_frame(&frame);
+                }
+                if let Ok(Event::Key(key_event)) = event {
+                    if key_event.kind == KeyEventKind::Press {
+                        let actions = [Action::Play, Action::Prove, Action::Pause, Action::Quit];
+                        match self.settings.keys.action(key_event.code, &actions) {
+                            Some(Action::Play) => {
+                                self.start_new_game();
+                                break;
+                            }
+                            Some(Action::Prove) if !expired => {
+                                self.state = GameState::ProveExecution;
+                                break;
+                            }
+                            Some(Action::Pause) if !expired => {
+                                self.resume(pause);
+                                self.state = GameState::Playing;
+                                break;
+                            }
+                            Some(Action::Quit) => {
                                 self.state = GameState::Quit;
                                 break;
-                            } else {
-                                println!("{}", self.render_death_screen());
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn handle_campaign_over_state(&mut self) {
+        let next_game = match GameLevels::new(self.chain_time(), self.games.clone()) {
+            Err(GameLevelsError::NoActiveGame {
+                next_game_starts_at: Some(timestamp),
+            }) => format!("The next game starts at {}", format_timestamp(timestamp)),
+            _ => String::from("No upcoming game is scheduled yet"),
+        };
+        let keys = &self.settings.keys;
+        let modal = Modal {
+            title: String::from("THE CAMPAIGN IS OVER"),
+            lines: vec![
+                String::from("There is no game to play right now"),
+                next_game,
+                format!(
+                    "{} Try again  {} Quit",
+                    keys.label(Action::Play),
+                    keys.label(Action::Quit)
+                ),
+            ],
+        };
+        self.renderer.invalidate();
+        let frame = Frame::new(&self.board, self.hud()).with_modal(modal);
+        self.draw_frame(&frame);
+
+        loop {
+            if event::poll(Duration::from_millis(100)).unwrap_or(false) {
+                let event = event::read();
+                if let Ok(Event::Resize(_, _)) = event {
+                    self.renderer.invalidate();
+                    self.draw_frame(&frame);
+                }
+                if let Ok(Event::Key(key_event)) = event {
+                    if key_event.kind == KeyEventKind::Press {
+                        let actions = [Action::Play, Action::Quit];
+                        match self.settings.keys.action(key_event.code, &actions) {
+                            Some(Action::Play) => {
+                                self.start_new_game();
+                                break;
+                            }
+                            Some(Action::Quit) => {
+                                self.state = GameState::Quit;
                                 break;
                             }
+                            _ => {}
                         }
-                        _ => {}
+                    }
+                }
+            }
+        }
+    }
+
+    fn handle_death_state(&mut self) {
+        println!("{}", self.render_death_screen());
+
+        loop {
+            if event::poll(Duration::from_millis(100)).unwrap_or(false) {
+                if let Ok(Event::Key(key_event)) = event::read() {
+                    if key_event.kind == KeyEventKind::Press {
+                        let actions = [Action::Play, Action::Prove, Action::Help, Action::Quit];
+                        match self.settings.keys.action(key_event.code, &actions) {
+                            Some(Action::Play) => {
+                                if Self::render_confirmation_prompt(
+                                    "Are you sure you want to restart the game?",
+                                ) {
+                                    self.start_new_game();
+                                    break;
+                                } else {
+                                    println!("{}", self.render_death_screen());
+                                    break;
+                                }
+                            }
+                            Some(Action::Prove) => {
+                                self.state = GameState::ProveExecution;
+                                break;
+                            }
+                            Some(Action::Help) => {
+                                self.state = GameState::Help;
+                                break;
+                            }
+                            Some(Action::Quit) => {
+                                if Self::render_confirmation_prompt(
+                                    "Are you sure you want to quit?",
+                                ) {
+                                    self.state = GameState::Quit;
+                                    break;
+                                } else {
+                                    println!("{}", self.render_death_screen());
+                                    break;
+                                }
+                            }
+                            _ => {}
                         }
                     }
                 }
@@ -465,24 +797,25 @@ impl Game {
             if event::poll(Duration::from_millis(100)).unwrap_or(false) {
                 if let Ok(Event::Key(key_event)) = event::read() {
                     if key_event.kind == KeyEventKind::Press {
-                        match key_event.code {
-                        KeyCode::Char(' ') => {
-                            self.start_new_game();
-                            break;
-                        }
-                        KeyCode::Enter => {
-                            self.state = GameState::ProveExecution;
-                            break;
-                        }
-                        KeyCode::Char('h') | KeyCode::Char('H') => {
-                            self.state = GameState::Help;
-                            break;
-                        }
-                        KeyCode::Char('q') | KeyCode::Char('Q') => {
-                            self.state = GameState::Quit;
-                            break;
-                        }
-                        _ => {}
+                        let actions = [Action::Play, Action::Prove, Action::Help, Action::Quit];
+                        match self.settings.keys.action(key_event.code, &actions) {
+                            Some(Action::Play) => {
+                                self.start_new_game();
+                                break;
+                            }
+                            Some(Action::Prove) => {
+                                self.state = GameState::ProveExecution;
+                                break;
+                            }
+                            Some(Action::Help) => {
+                                self.state = GameState::Help;
+                                break;
+                            }
+                            Some(Action::Quit) => {
+                                self.state = GameState::Quit;
+                                break;
+                            }
+                            _ => {}
                         }
                     }
                 }
@@ -491,6 +824,16 @@ impl Game {
     }
 
     fn handle_level_complete(&mut self) {
+        // the log now ends with the level that was just completed so the whole log can be proven
+        if self.proving_systems.contains(&SP1.to_string()) {
+            self.prover.submit(
+                self.levels_completion_log.clone(),
+                self.game_match.get_levels_in_json(),
+                self.address.clone(),
+            );
+            self.poll_prover();
+        }
+
         let handle = Self::render_loader_in_new_thread("LEVEL COMPLETED.", 5000, true);
         let _ = handle.join();
 
@@ -512,7 +855,7 @@ impl Game {
             self.eggs = board_terrain_info.eggs;
             self.hatched_beasts = board_terrain_info.hatched_beasts;
             self.player.position = board_terrain_info.player.position;
-            self.player.score += self.game_match.get_config(self.level).completion_score;
+            self.player.score += level.start_score(&self.game_match.get_config(level));
             self.state = GameState::Playing;
         } else {
             self.has_won = true;
@@ -522,32 +865,38 @@ impl Game {
 
     fn handle_help_state(&mut self) {
         let pause = Instant::now();
-        let mut help = Help::new();
+        let mut help = Help::new(&self.settings.keys, &self.settings.theme);
         println!("{}", help.render());
 
         loop {
             if event::poll(Duration::from_millis(100)).unwrap_or(false) {
                 if let Ok(Event::Key(key_event)) = event::read() {
                     if key_event.kind == KeyEventKind::Press {
-                        match key_event.code {
-                        KeyCode::Char(' ') => {
-                            self.level_start += pause.elapsed();
-                            self.state = GameState::Playing;
-                            break;
-                        }
-                        KeyCode::Char('q') | KeyCode::Char('Q') => {
-                            self.state = GameState::Quit;
-                            break;
-                        }
-                        KeyCode::Char('d') | KeyCode::Char('D') | KeyCode::Right => {
-                            help.next_page();
-                            println!("{}", help.render());
-                        }
-                        KeyCode::Char('a') | KeyCode::Char('A') | KeyCode::Left => {
-                            help.previous_page();
-                            println!("{}", help.render());
-                        }
-                        _ => {}
+                        let actions = [
+                            Action::Play,
+                            Action::Quit,
+                            Action::NextPage,
+                            Action::PreviousPage,
+                        ];
+                        match self.settings.keys.action(key_event.code, &actions) {
+                            Some(Action::Play) => {
+                                self.resume(pause);
+                                self.state = GameState::Playing;
+                                break;
+                            }
+                            Some(Action::Quit) => {
+                                self.state = GameState::Quit;
+                                break;
+                            }
+                            Some(Action::NextPage) => {
+                                help.next_page();
+                                println!("{}", help.render());
+                            }
+                            Some(Action::PreviousPage) => {
+                                help.previous_page();
+                                println!("{}", help.render());
+                            }
+                            _ => {}
                         }
                     }
                 }
@@ -556,39 +905,51 @@ impl Game {
     }
 
     fn handle_prove_execution_state(&mut self) {
-        let proving_alert_handle = Self::render_loader_in_new_thread(
-            "Proving this can take a few minutes...",
-            30000,
-            true,
-        );
+        // a proof for a game that closed would only be rejected by the leaderboard
+        if matches!(self.window_status(), Some(WindowStatus::Expired)) {
+            self.proof_completion_message = String::from(
+                "This game is over and its proofs can't be claimed anymore, play the current game instead.",
+            );
+            self.state = GameState::ProofComplete;
+            return;
+        }
 
-        let mut sp1_res: Result<String, _> = Err("SP1 not used".to_string());
+        // If it hasn't won, then don't include the last level as it wasn't completed
+        let completed_levels = if self.has_won {
+            self.levels_completion_log.len()
+        } else {
+            self.levels_completion_log.len() - 1
+        };
+
+        let mut sp1_res: Result<String, BeastError> =
+            Err(ProvingError::Prove("SP1 not used".to_string()).into());
 
         if self.proving_systems.contains(&SP1.to_string()) {
-            // If it hasn't won, then don't include the last level as it wasn't completed
-            let sp1_levels_completion_log = if self.has_won {
-                self.levels_completion_log.clone()
-            } else {
-                let mut levels_log = self.levels_completion_log.clone();
-                levels_log.pop();
-                levels_log
-            };
-            let sp1_address = self.address.clone();
-            let levels = self.game_match.get_levels_in_json();
-
-            let sp1_handle = thread::spawn(move || {
-                let res = sp1_prove(sp1_levels_completion_log, levels, sp1_address);
-                if let Ok(receipt) = res {
-                    sp1_save_proof(receipt).expect("To be able to write proof")
-                } else {
-                    panic!("Could prove program")
-                }
+            // completed levels are already being proven in the background so we only wait for them
+            self.prover.submit(
+                self.levels_completion_log[..completed_levels].to_vec(),
+                self.game_match.get_levels_in_json(),
+                self.address.clone(),
+            );
+            let proving_alert_handle = (!self.prover.is_ready(completed_levels)).then(|| {
+                Self::render_loader_in_new_thread(
+                    "Proving this can take a few minutes...",
+                    30000,
+                    true,
+                )
             });
-            sp1_res = sp1_handle.join().map_err(|_| "SP1 proving failed".to_string());
-        }
 
+            sp1_res = self
+                .prover
+                .wait_for(completed_levels)
+                .map(|proof| proof.path)
+                .map_err(BeastError::from);
 
-        let _ = proving_alert_handle.join();
+            if let Some(handle) = proving_alert_handle {
+                let _ = handle.join();
+            }
+            self.poll_prover();
+        }
 
         if self.has_won {
             println!("{}", self.render_winning_screen());
@@ -604,13 +965,12 @@ impl Game {
             successful_files.push(full_path.to_string_lossy().into_owned());
         }
 
-        self.proof_completion_message = if !successful_files.is_empty() {
-            format!(
+        self.proof_completion_message = match sp1_res {
+            Ok(_) => format!(
                 "Proof saved to {}. Submit it to https://zkarcade.com/games/beast and earn points!",
                 successful_files.join(", ")
-            )
-        } else {
-            "Could not prove program, try again...".to_string()
+            ),
+            Err(error) => format!("{error}, try again..."),
         };
         // Transition to proof complete state to show persistent message
         self.state = GameState::ProofComplete;
@@ -626,26 +986,32 @@ impl Game {
         
         // Show the proof completion message that stays on screen
         println!("\n{}\n", self.proof_completion_message);
-        println!("Press [SPACE] to play again, [Q] to quit, or [H] for help");
+        println!(
+            "Press {} to play again, {} to quit, or {} for help",
+            self.settings.keys.label(Action::Play),
+            self.settings.keys.label(Action::Quit),
+            self.settings.keys.label(Action::Help)
+        );
 
         loop {
             if event::poll(Duration::from_millis(100)).unwrap_or(false) {
                 if let Ok(Event::Key(key_event)) = event::read() {
                     if key_event.kind == KeyEventKind::Press {
-                        match key_event.code {
-                        KeyCode::Char(' ') => {
-                            self.start_new_game();
-                            break;
-                        }
-                        KeyCode::Char('q') | KeyCode::Char('Q') => {
-                            self.state = GameState::Quit;
-                            break;
-                        }
-                        KeyCode::Char('h') | KeyCode::Char('H') => {
-                            self.state = GameState::Help;
-                            break;
-                        }
-                        _ => {}
+                        let actions = [Action::Play, Action::Quit, Action::Help];
+                        match self.settings.keys.action(key_event.code, &actions) {
+                            Some(Action::Play) => {
+                                self.start_new_game();
+                                break;
+                            }
+                            Some(Action::Quit) => {
+                                self.state = GameState::Quit;
+                                break;
+                            }
+                            Some(Action::Help) => {
+                                self.state = GameState::Help;
+                                break;
+                            }
+                            _ => {}
                         }
                     }
                 }
@@ -653,6 +1019,19 @@ impl Game {
         }
     }
 
+    /// collect finished background proofs and show what the prover is doing in the footer
+    fn poll_prover(&mut self) {
+        self.prover.poll();
+        let window = self.window_status().map(render_window_status);
+        let status = [window, Some(self.prover.status().to_string())]
+            .into_iter()
+            .flatten()
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join("  ");
+        self.renderer.set_status(status);
+    }
+
     fn get_secs_remaining(&self) -> u64 {
         let elapsed = Instant::now().duration_since(self.level_start);
         let total_time = self.game_match.get_config(self.level).time;
@@ -681,62 +1060,31 @@ impl Game {
         output.push_str(" ╚═╝ ╚═╝ ╩ ╩ ╚═╝  ╩\n");
     }
 
-    fn render_footer(&self) -> String {
-        let mut output = String::new();
-        let secs_remaining = self.get_secs_remaining();
-        let minutes = secs_remaining / 60;
-        let seconds = secs_remaining % 60;
-        let elapsed = self.level_start.elapsed();
-        let tick_count = elapsed.as_millis() / TICK_DURATION.as_millis();
-        let timer_color = if tick_count % 2 == 0 && minutes == 0 && seconds < 20
-            || minutes == 0 && seconds == 0
-        {
-            "\x1b[31m"
-        } else {
-            ANSI_RESET_FONT
-        };
-
-        let lives = if self.player.lives == 1 {
-            format!("\x1B[31m{}{ANSI_RESET_FONT}", self.player.lives)
-        } else {
-            self.player.lives.to_string()
-        };
-
-        output.push_str("⌂⌂ Move [WASD/↑↓←→]                     ");
-        output.push_str("  Beasts: ");
-        output.push_str(&format!(
-            "{ANSI_BOLD}{:>2}{ANSI_RESET}",
-            (self.common_beasts.len() + self.super_beasts.len() + self.hatched_beasts.len())
-                .to_string()
-        ));
-        output.push_str("  Level: ");
-        output.push_str(&format!(
-            "{ANSI_BOLD}{:>2}{ANSI_RESET}",
-            self.level.to_string()
-        ));
-        output.push_str("  Time: ");
-        output.push_str(&format!(
-            "{ANSI_BOLD}{timer_color}{:02}:{:02}{ANSI_RESET}",
-            minutes, seconds
-        ));
-        output.push_str("  Lives: ");
-        output.push_str(&format!("{ANSI_BOLD}{lives}{ANSI_RESET}"));
-        output.push_str("  Score: ");
-        output.push_str(&format!("{ANSI_BOLD}{:>4}{ANSI_RESET}", self.player.score));
-        output.push_str(&format!(" {}\n\n", Tile::Player));
+    /// the values for the heads-up display in the footer
+    fn hud(&self) -> Hud {
+        Hud {
+            level: self.level,
+            beasts: self.common_beasts.len() + self.super_beasts.len() + self.hatched_beasts.len(),
+            secs_remaining: self.get_secs_remaining(),
+            lives: self.player.lives,
+            score: self.player.score,
+            tick: (self.level_start.elapsed().as_millis() / TICK_DURATION.as_millis()) as u64,
+        }
+    }
 
-        output
+    fn render_footer(&self) -> String {
+        self.renderer.render_footer(&self.hud())
     }
 
     fn render_top_frame() -> String {
         format!("\x1b[33m▛{}▜{ANSI_RESET_FONT}\n", "▀▀".repeat(BOARD_WIDTH))
     }
 
-    fn render_bottom_frame() -> String {
+    pub(crate) fn render_bottom_frame() -> String {
         format!("\x1b[33m▙{}▟{ANSI_RESET_FONT}\n", "▄▄".repeat(BOARD_WIDTH))
     }
 
-    fn render_intro() -> String {
+    fn render_intro(&self) -> String {
         let mut output = String::new();
         Self::render_header(&mut output);
         output.push_str(&Self::render_top_frame());
@@ -759,8 +1107,15 @@ impl Game {
         output.push_str(&format!("{ANSI_LEFT_BORDER}                                                                                                    {ANSI_RIGHT_BORDER}\n"));
         output.push_str(&format!("{ANSI_LEFT_BORDER}                                                                                                    {ANSI_RIGHT_BORDER}\n"));
         output.push_str(&format!("{ANSI_LEFT_BORDER}                                                                                                    {ANSI_RIGHT_BORDER}\n"));
-        output.push_str(&format!("{ANSI_LEFT_BORDER}                                     Press {ANSI_BOLD}[SPACE]{ANSI_RESET} key to start                                     {ANSI_RIGHT_BORDER}\n"));
-        output.push_str(&format!("{ANSI_LEFT_BORDER}                                         {ANSI_BOLD}[Q]{ANSI_RESET} Quit  {ANSI_BOLD}[H]{ANSI_RESET} Help  {ANSI_BOLD}                                       {ANSI_RIGHT_BORDER}\n"));
+        output.push_str(&render_centered_line(&format!(
+            "Press {ANSI_BOLD}{}{ANSI_RESET} key to start",
+            self.settings.keys.label(Action::Play)
+        )));
+        output.push_str(&render_centered_line(&format!(
+            "{ANSI_BOLD}{}{ANSI_RESET} Quit  {ANSI_BOLD}{}{ANSI_RESET} Help",
+            self.settings.keys.label(Action::Quit),
+            self.settings.keys.label(Action::Help)
+        )));
         output.push_str(&format!("{ANSI_LEFT_BORDER}                                                                                                    {ANSI_RIGHT_BORDER}\n"));
         output.push_str(&Self::render_bottom_frame());
         output.push_str("\n\n");
@@ -768,6 +1123,14 @@ impl Game {
         output
     }
 
+    /// a centered line telling the player which key triggers an action
+    fn render_key_line(&self, before: &str, action: Action, after: &str) -> String {
+        render_centered_line(&format!(
+            "{before}{ANSI_BOLD}{}{ANSI_RESET}{after}",
+            self.settings.keys.label(action)
+        ))
+    }
+
     fn render_death_screen(&self) -> String {
         let mut output = String::new();
         let top_pos = format!(
@@ -793,10 +1156,10 @@ impl Game {
         output.push_str(&format!("{ANSI_LEFT_BORDER}                                                                                                    {ANSI_RIGHT_BORDER}\n"));
         output.push_str(&format!("{ANSI_LEFT_BORDER}                                                                                                    {ANSI_RIGHT_BORDER}\n"));
         output.push_str(&format!("{ANSI_LEFT_BORDER}                                                                                                    {ANSI_RIGHT_BORDER}\n"));
-        output.push_str(&format!("{ANSI_LEFT_BORDER}                               PRESS {ANSI_BOLD}[ENTER]{ANSI_RESET} TO PROVE YOUR EXECUTION                                {ANSI_RIGHT_BORDER}\n"));
+        output.push_str(&self.render_key_line("PRESS ", Action::Prove, " TO PROVE YOUR EXECUTION"));
         output.push_str(&format!("{ANSI_LEFT_BORDER}                                                                                                    {ANSI_RIGHT_BORDER}\n"));
-        output.push_str(&format!("{ANSI_LEFT_BORDER}                                  Press {ANSI_BOLD}[SPACE]{ANSI_RESET} key to play again                                   {ANSI_RIGHT_BORDER}\n"));
-        output.push_str(&format!("{ANSI_LEFT_BORDER}                                     Press {ANSI_BOLD}[Q]{ANSI_RESET} to exit the game                                     {ANSI_RIGHT_BORDER}\n"));
+        output.push_str(&self.render_key_line("Press ", Action::Play, " key to play again"));
+        output.push_str(&self.render_key_line("Press ", Action::Quit, " to exit the game"));
         output.push_str(&format!("{ANSI_LEFT_BORDER}                                                                                                    {ANSI_RIGHT_BORDER}\n"));
         output.push_str(&Self::render_bottom_frame());
         output.push_str(&self.render_footer());
@@ -825,10 +1188,10 @@ impl Game {
         output.push_str(&format!("{ANSI_LEFT_BORDER}                                                                                                    {ANSI_RIGHT_BORDER}\n"));
         output.push_str(&format!("{ANSI_LEFT_BORDER}                                                                                                    {ANSI_RIGHT_BORDER}\n"));
         output.push_str(&format!("{ANSI_LEFT_BORDER}                                                                                                    {ANSI_RIGHT_BORDER}\n"));
-        output.push_str(&format!("{ANSI_LEFT_BORDER}                                PRESS {ANSI_BOLD}[ENTER]{ANSI_RESET} TO PROVE YOUR EXECUTION                               {ANSI_RIGHT_BORDER}\n"));
+        output.push_str(&self.render_key_line("PRESS ", Action::Prove, " TO PROVE YOUR EXECUTION"));
         output.push_str(&format!("{ANSI_LEFT_BORDER}                                                                                                    {ANSI_RIGHT_BORDER}\n"));
-        output.push_str(&format!("{ANSI_LEFT_BORDER}                                  Press {ANSI_BOLD}[SPACE]{ANSI_RESET} key to play again                                   {ANSI_RIGHT_BORDER}\n"));
-        output.push_str(&format!("{ANSI_LEFT_BORDER}                                     Press {ANSI_BOLD}[Q]{ANSI_RESET} to exit the game                                     {ANSI_RIGHT_BORDER}\n"));
+        output.push_str(&self.render_key_line("Press ", Action::Play, " key to play again"));
+        output.push_str(&self.render_key_line("Press ", Action::Quit, " to exit the game"));
         output.push_str(&format!("{ANSI_LEFT_BORDER}                                                                                                    {ANSI_RIGHT_BORDER}\n"));
         output.push_str(&Self::render_bottom_frame());
         output.push_str("\n\n");
@@ -836,54 +1199,48 @@ impl Game {
         output
     }
 
-    fn render_board(&self) -> String {
-        let top_pos = format!(
-            "\x1b[{}F",
-            ANSI_FRAME_SIZE + ANSI_BOARD_HEIGHT + ANSI_FRAME_SIZE + ANSI_FOOTER_HEIGHT
-        );
-        let bottom_pos = format!("\x1b[{}E", ANSI_FRAME_SIZE);
-        let mut output = String::new();
+    /// draw the board through the incremental renderer so only changed tiles are sent to the terminal
+    fn render_board(&mut self, flash: Flash) {
+        let frame = Frame::new(&self.board, self.hud()).with_flash(flash);
+        self.draw_frame(&frame);
+    }
 
-        output.push_str(&top_pos);
-        output.push_str(&self.board.render());
-        output.push_str(&Self::render_bottom_frame());
-        output.push_str(&self.render_footer());
-        output.push_str(&bottom_pos);
-        output
+    fn draw_frame(&mut self, frame: &Frame) {
+        let output = self.renderer.render(frame);
+        let mut stdout = io::stdout().lock();
+        let _ = stdout.write_all(output.as_bytes());
+        let _ = stdout.flush();
     }
 
     fn render_with_state(&mut self) {
-        match self.state {
+        let flash = match self.state {
             GameState::Dying(beat) => match beat {
                 Beat::One => {
                     self.state = GameState::Dying(Beat::Two);
-                    print!("\x1b[48;5;196m");
+                    Flash::PlayerHit(0)
                 }
                 Beat::Two => {
                     self.state = GameState::Dying(Beat::Three);
-                    print!("\x1b[48;5;208m");
+                    Flash::PlayerHit(1)
                 }
                 Beat::Three | Beat::Four | Beat::Five => {
                     self.state = GameState::Playing;
-                    print!("{ANSI_RESET_BG}");
+                    Flash::None
                 }
             },
             GameState::Killing(beat) => match beat {
                 Beat::One => {
                     self.state = GameState::Killing(Beat::Two);
-                    print!("\x1b[48;2;51;51;51m");
+                    Flash::BeastKilled
                 }
                 Beat::Two | Beat::Three | Beat::Four | Beat::Five => {
                     self.state = GameState::Playing;
-                    print!("{ANSI_RESET_BG}");
+                    Flash::None
                 }
             },
-            GameState::Playing => {
-                print!("{ANSI_RESET_BG}");
-            }
-            _ => {}
-        }
-        print!("{}", self.render_board());
+            _ => Flash::None,
+        };
+        self.render_board(flash);
     }
 
     fn render_loader_in_new_thread(
@@ -943,9 +1300,9 @@ impl Game {
                 if let Ok(Event::Key(key_event)) = event::read() {
                     if key_event.kind == KeyEventKind::Press {
                         match key_event.code {
-                        KeyCode::Char('y') | KeyCode::Char('Y') => return true,
-                        KeyCode::Char('n') | KeyCode::Char('N') => return false,
-                        _ => {}
+                            KeyCode::Char('y') | KeyCode::Char('Y') => return true,
+                            KeyCode::Char('n') | KeyCode::Char('N') => return false,
+                            _ => {}
                         }
                     }
                 }
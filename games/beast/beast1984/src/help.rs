@@ -2,7 +2,11 @@
 
 use std::fmt;
 
-use crate::game::{ANSI_BOARD_HEIGHT, ANSI_FOOTER_HEIGHT, ANSI_FRAME_SIZE};
+use crate::{
+    game::{ANSI_BOARD_HEIGHT, ANSI_FOOTER_HEIGHT, ANSI_FRAME_SIZE},
+    renderer::{render_centered_line, render_line},
+    settings::{Action, KeyBindings, Theme},
+};
 use game_logic::{
     ANSI_BOLD, ANSI_LEFT_BORDER, ANSI_RESET, ANSI_RIGHT_BORDER, LOGO, Tile,
     beasts::{Beast, CommonBeast, Egg, HatchedBeast, SuperBeast},
@@ -37,12 +41,18 @@ const ANSI_HELP_INDEX_HEIGHT: usize = 2;
 /// our help
 pub struct Help {
     page: Page,
+    keys: KeyBindings,
+    theme: Theme,
 }
 
 impl Help {
-    /// create a new instance of help
-    pub fn new() -> Self {
-        Self { page: Page::One }
+    /// create a new instance of help that shows the bindings and colors the player configured
+    pub fn new(keys: &KeyBindings, theme: &Theme) -> Self {
+        Self {
+            page: Page::One,
+            keys: keys.clone(),
+            theme: theme.clone(),
+        }
     }
 
     /// go to the next page
@@ -85,24 +95,37 @@ impl Help {
         output.push('\n');
         output.push_str(&format!("{ANSI_LEFT_BORDER}                                               {ANSI_BOLD}HELP{ANSI_RESET}                                                 {ANSI_RIGHT_BORDER}\n"));
         output.push_str(&format!("{ANSI_LEFT_BORDER}                                                                                                    {ANSI_RIGHT_BORDER}\n"));
-        output.push_str(&format!("{ANSI_LEFT_BORDER}                                                                                                    {ANSI_RIGHT_BORDER}\n"));
         output.push_str(&format!("{ANSI_LEFT_BORDER}  {ANSI_BOLD}GENERAL{ANSI_RESET}                                                                                           {ANSI_RIGHT_BORDER}\n"));
         output.push_str(&format!("{ANSI_LEFT_BORDER}                                                                                                    {ANSI_RIGHT_BORDER}\n"));
         output.push_str(&format!("{ANSI_LEFT_BORDER}  You must survive while {ANSI_BOLD}beasts{ANSI_RESET} attack you. The only way to fight back is to squish the beasts      {ANSI_RIGHT_BORDER}\n"));
         output.push_str(&format!("{ANSI_LEFT_BORDER}  between blocks. But there are different types of beasts that attack you the longer you survive.   {ANSI_RIGHT_BORDER}\n"));
         output.push_str(&format!("{ANSI_LEFT_BORDER}                                                                                                    {ANSI_RIGHT_BORDER}\n"));
-        output.push_str(&format!("{ANSI_LEFT_BORDER}  You are {} and you move around with the WASD/↑↓←→ keys on your keyboard.                               {ANSI_RIGHT_BORDER}\n", Tile::Player));
-        output.push_str(&format!("{ANSI_LEFT_BORDER}  You can push {} around the board.                                                                 {ANSI_RIGHT_BORDER}\n", Tile::Block));
-        output.push_str(&format!("{ANSI_LEFT_BORDER}  However, {} can't be moved.                                                                       {ANSI_RIGHT_BORDER}\n", Tile::StaticBlock));
+        output.push_str(&render_line(&format!(
+            "  You are {} and you move around with the {} keys on your keyboard.",
+            self.theme.paint(&Tile::Player),
+            self.keys.movement_label()
+        )));
+        output.push_str(&format!("{ANSI_LEFT_BORDER}  You can push {} around the board.                                                                 {ANSI_RIGHT_BORDER}\n", self.theme.paint(&Tile::Block)));
+        output.push_str(&format!("{ANSI_LEFT_BORDER}  However, {} can't be moved.                                                                       {ANSI_RIGHT_BORDER}\n", self.theme.paint(&Tile::StaticBlock)));
         output.push_str(&format!("{ANSI_LEFT_BORDER}                                                                                                    {ANSI_RIGHT_BORDER}\n"));
         output.push_str(&format!("{ANSI_LEFT_BORDER}  Your goal is to use the blocks to squish all beasts before the time runs out.                     {ANSI_RIGHT_BORDER}\n"));
         output.push_str(&format!("{ANSI_LEFT_BORDER}  Each level will introduce new Beasts and an ever changing environment.                            {ANSI_RIGHT_BORDER}\n"));
         output.push_str(&format!("{ANSI_LEFT_BORDER}                                                                                                    {ANSI_RIGHT_BORDER}\n"));
         output.push_str(&format!("{ANSI_LEFT_BORDER}  And you better hurry up because you only got a little time to survive in {ANSI_BOLD}BEAST{ANSI_RESET}.                   {ANSI_RIGHT_BORDER}\n"));
+        output.push_str(&render_line(&format!(
+            "  Need a break? Press {ANSI_BOLD}{}{ANSI_RESET} while playing to pause the game.",
+            self.keys.label(Action::Pause)
+        )));
         output.push_str(&format!("{ANSI_LEFT_BORDER}                                                                                                    {ANSI_RIGHT_BORDER}\n"));
         output.push_str(&self.render_pagination());
         output.push_str(&format!("{ANSI_LEFT_BORDER}                                                                                                    {ANSI_RIGHT_BORDER}\n"));
-        output.push_str(&format!("{ANSI_LEFT_BORDER}             {ANSI_BOLD}[SPACE]{ANSI_RESET} Play  {ANSI_BOLD}[Q]{ANSI_RESET} Quit  {ANSI_BOLD}[S]{ANSI_RESET} Highscores  {ANSI_BOLD}[A]{ANSI_RESET} Previous Page  {ANSI_BOLD}[D]{ANSI_RESET} Next Page               {ANSI_RIGHT_BORDER}\n"));
+        output.push_str(&render_centered_line(&format!(
+            "{ANSI_BOLD}{}{ANSI_RESET} Play  {ANSI_BOLD}{}{ANSI_RESET} Quit  {ANSI_BOLD}{}{ANSI_RESET} Previous Page  {ANSI_BOLD}{}{ANSI_RESET} Next Page",
+            self.keys.label(Action::Play),
+            self.keys.label(Action::Quit),
+            self.keys.label(Action::PreviousPage),
+            self.keys.label(Action::NextPage)
+        )));
         output.push_str(&bottom_pos);
 
         output
@@ -122,16 +145,16 @@ impl Help {
         output.push_str(&top_pos);
         output.push_str(&format!("{ANSI_LEFT_BORDER}  {ANSI_BOLD}ENEMIES{ANSI_RESET}                                                                                           {ANSI_RIGHT_BORDER}\n"));
         output.push_str(&format!("{ANSI_LEFT_BORDER}                                                                                                    {ANSI_RIGHT_BORDER}\n"));
-        output.push_str(&format!("{ANSI_LEFT_BORDER}  The {ANSI_BOLD}Common Beast{ANSI_RESET} {}                                                                               {ANSI_RIGHT_BORDER}\n", Tile::CommonBeast));
+        output.push_str(&format!("{ANSI_LEFT_BORDER}  The {ANSI_BOLD}Common Beast{ANSI_RESET} {}                                                                               {ANSI_RIGHT_BORDER}\n", self.theme.paint(&Tile::CommonBeast)));
         output.push_str(&format!("{ANSI_LEFT_BORDER}  It's the beast that attacks you first and in large numbers. Don't worry though, it isn't super    {ANSI_RIGHT_BORDER}\n"));
         output.push_str(&format!("{ANSI_LEFT_BORDER}  smart and often gets stuck. You can kill it by squishing it against any block or the board frame. {ANSI_RIGHT_BORDER}\n"));
         output.push_str(&format!("{ANSI_LEFT_BORDER}                                                                                                    {ANSI_RIGHT_BORDER}\n"));
-        output.push_str(&format!("{ANSI_LEFT_BORDER}  The {ANSI_BOLD}Super Beast{ANSI_RESET} {}                                                                                {ANSI_RIGHT_BORDER}\n", Tile::SuperBeast));
+        output.push_str(&format!("{ANSI_LEFT_BORDER}  The {ANSI_BOLD}Super Beast{ANSI_RESET} {}                                                                                {ANSI_RIGHT_BORDER}\n", self.theme.paint(&Tile::SuperBeast)));
         output.push_str(&format!("{ANSI_LEFT_BORDER}  This beast is vicious and smart and will find you if you leave an opening.                        {ANSI_RIGHT_BORDER}\n"));
-        output.push_str(&format!("{ANSI_LEFT_BORDER}  It can only be killed by squishing it against a {}.                                               {ANSI_RIGHT_BORDER}\n", Tile::StaticBlock));
+        output.push_str(&format!("{ANSI_LEFT_BORDER}  It can only be killed by squishing it against a {}.                                               {ANSI_RIGHT_BORDER}\n", self.theme.paint(&Tile::StaticBlock)));
         output.push_str(&format!("{ANSI_LEFT_BORDER}                                                                                                    {ANSI_RIGHT_BORDER}\n"));
-        output.push_str(&format!("{ANSI_LEFT_BORDER}  The {ANSI_BOLD}Egg{ANSI_RESET} {} and the {ANSI_BOLD}Hatched Beast{ANSI_RESET} {}                                                               {ANSI_RIGHT_BORDER}\n", Tile::Egg, Tile::HatchedBeast));
-        output.push_str(&format!("{ANSI_LEFT_BORDER}  Towards the end you will encounter eggs which hatch into Hatched Beasts. These beasts can push {} {ANSI_RIGHT_BORDER}\n", Tile::Block));
+        output.push_str(&format!("{ANSI_LEFT_BORDER}  The {ANSI_BOLD}Egg{ANSI_RESET} {} and the {ANSI_BOLD}Hatched Beast{ANSI_RESET} {}                                                               {ANSI_RIGHT_BORDER}\n", self.theme.paint(&Tile::Egg), self.theme.paint(&Tile::HatchedBeast)));
+        output.push_str(&format!("{ANSI_LEFT_BORDER}  Towards the end you will encounter eggs which hatch into Hatched Beasts. These beasts can push {} {ANSI_RIGHT_BORDER}\n", self.theme.paint(&Tile::Block)));
         output.push_str(&format!("{ANSI_LEFT_BORDER}  and will try to squish YOU with them. They can be killed like the common beasts though.           {ANSI_RIGHT_BORDER}\n"));
         output.push_str(&format!("{ANSI_LEFT_BORDER}                                                                                                    {ANSI_RIGHT_BORDER}\n"));
         output.push_str(&self.render_pagination());
@@ -160,10 +183,10 @@ impl Help {
         output.push_str(&format!("{ANSI_LEFT_BORDER}                                                                                                    {ANSI_RIGHT_BORDER}\n"));
         output.push_str(&format!("{ANSI_LEFT_BORDER}  Beast  | Score for squishing                                                                      {ANSI_RIGHT_BORDER}\n"));
         output.push_str(&format!("{ANSI_LEFT_BORDER}  ----------------------------                                                                      {ANSI_RIGHT_BORDER}\n"));
-        output.push_str(&format!("{ANSI_LEFT_BORDER}  {}     | {}                                                                                        {ANSI_RIGHT_BORDER}\n", Tile::CommonBeast, CommonBeast::get_score()));
-        output.push_str(&format!("{ANSI_LEFT_BORDER}  {}     | {}                                                                                        {ANSI_RIGHT_BORDER}\n", Tile::SuperBeast, SuperBeast::get_score()));
-        output.push_str(&format!("{ANSI_LEFT_BORDER}  {}     | {}                                                                                        {ANSI_RIGHT_BORDER}\n", Tile::Egg, Egg::get_score()));
-        output.push_str(&format!("{ANSI_LEFT_BORDER}  {}     | {}                                                                                        {ANSI_RIGHT_BORDER}\n", Tile::HatchedBeast, HatchedBeast::get_score()));
+        output.push_str(&format!("{ANSI_LEFT_BORDER}  {}     | {}                                                                                        {ANSI_RIGHT_BORDER}\n", self.theme.paint(&Tile::CommonBeast), CommonBeast::get_score()));
+        output.push_str(&format!("{ANSI_LEFT_BORDER}  {}     | {}                                                                                        {ANSI_RIGHT_BORDER}\n", self.theme.paint(&Tile::SuperBeast), SuperBeast::get_score()));
+        output.push_str(&format!("{ANSI_LEFT_BORDER}  {}     | {}                                                                                        {ANSI_RIGHT_BORDER}\n", self.theme.paint(&Tile::Egg), Egg::get_score()));
+        output.push_str(&format!("{ANSI_LEFT_BORDER}  {}     | {}                                                                                        {ANSI_RIGHT_BORDER}\n", self.theme.paint(&Tile::HatchedBeast), HatchedBeast::get_score()));
         output.push_str(&format!("{ANSI_LEFT_BORDER}                                                                                                    {ANSI_RIGHT_BORDER}\n"));
         output.push_str(&format!("{ANSI_LEFT_BORDER}                                                                                                    {ANSI_RIGHT_BORDER}\n"));
         output.push_str(&self.render_pagination());
@@ -0,0 +1,783 @@
+//! this module contains the leaderboard contract binding and the calls the submit tool makes to it
+
+use std::{fmt, fs, path::Path, str::FromStr};
+
+use aligned_sdk::common::types::AlignedVerificationData;
+use alloy::{
+    eips::BlockNumberOrTag,
+    primitives::{Address, B256, Bytes, TxHash, U256, keccak256},
+    providers::Provider,
+    sol,
+    sol_types::SolValue,
+};
+use serde::Deserialize;
+
+use crate::{consistency::ContractGame, error::BeastError, proof_file::PublicValues};
+
+sol!(
+    #[allow(clippy::too_many_arguments)]
+    #[sol(rpc, all_derives)]
+    Leaderboard,
+    "cmd/abi/Leaderboard.json"
+);
+
+/// a game as it is listed in the `levels/leaderboard_<network>.json` files, in the order of `beastGames`
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LeaderboardGame {
+    pub starts_at_time: String,
+    pub ends_at_time: String,
+    pub game_config: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct LeaderboardFile {
+    games: Vec<LeaderboardGame>,
+}
+
+/// read the games of a leaderboard levels file
+pub fn read_leaderboard_games(path: impl AsRef<Path>) -> Result<Vec<LeaderboardGame>, BeastError> {
+    let path = path.as_ref();
+    let content = fs::read_to_string(path)
+        .map_err(|e| BeastError::Leaderboard(format!("could not read {}: {e}", path.display())))?;
+    let file: LeaderboardFile = serde_json::from_str(&content)
+        .map_err(|e| BeastError::Leaderboard(format!("invalid {}: {e}", path.display())))?;
+    Ok(file.games)
+}
+
+fn parse_u256(value: &str) -> Option<U256> {
+    U256::from_str(value).ok()
+}
+
+/// the index of the game a proof of `game_config` can be claimed for at `now`
+///
+/// Games overlap by the submission offset so the latest matching game that is still open wins.
+pub fn claimable_game_index(
+    games: &[LeaderboardGame],
+    game_config: U256,
+    now: u64,
+) -> Option<usize> {
+    let now = U256::from(now);
+
+    games.iter().enumerate().rev().find_map(|(idx, game)| {
+        let starts_at = parse_u256(&game.starts_at_time)?;
+        let ends_at = parse_u256(&game.ends_at_time)?;
+        let config = parse_u256(&game.game_config)?;
+
+        (config == game_config && starts_at <= now && now < ends_at).then_some(idx)
+    })
+}
+
+/// the revert a claim of `game_config` runs into at `now` when no game can take it
+///
+/// The latest game of `games` with the config of the proof tells whether it ended or didn't start yet,
+/// without one the proof is for another game than the current one.
+pub fn unclaimable_game_revert(
+    games: &[LeaderboardGame],
+    current: Option<&Leaderboard::BeastGame>,
+    game_config: U256,
+    now: u64,
+) -> Option<ClaimRevert> {
+    let matching = games
+        .iter()
+        .rev()
+        .find(|game| parse_u256(&game.game_config) == Some(game_config));
+    if let Some(game) = matching {
+        let starts_at_time = parse_u256(&game.starts_at_time)?;
+        let ends_at_time = parse_u256(&game.ends_at_time)?;
+        return Some(if U256::from(now) < starts_at_time {
+            ClaimRevert::GameNotStarted {
+                starts_at_time,
+                now,
+            }
+        } else {
+            ClaimRevert::GameEnded { ends_at_time, now }
+        });
+    }
+
+    current.map(|current| ClaimRevert::InvalidGame {
+        expected: current.gameConfig,
+        actual: game_config,
+    })
+}
+
+/// the timestamp of the latest block, the time the contract checks the game window against
+async fn latest_block_timestamp<P: Provider>(
+    leaderboard: &Leaderboard::LeaderboardInstance<P>,
+) -> Result<u64, BeastError> {
+    Ok(leaderboard
+        .provider()
+        .get_block_by_number(BlockNumberOrTag::Latest)
+        .await
+        .map_err(|e| BeastError::Leaderboard(format!("could not read the latest block: {e}")))?
+        .ok_or_else(|| BeastError::Leaderboard(String::from("there is no latest block")))?
+        .header
+        .timestamp)
+}
+
+/// find the index of the game a proof of `game_config` has to be claimed for
+///
+/// The current game on chain is tried first, proofs of a previous game that can still be claimed are
+/// looked up in `games`. A game found in `games` is checked against the contract before it is used.
+/// When no game can take the proof the revert the claim would run into is returned.
+pub async fn find_game_index<P: Provider>(
+    leaderboard: &Leaderboard::LeaderboardInstance<P>,
+    game_config: U256,
+    games: &[LeaderboardGame],
+) -> Result<U256, BeastError> {
+    let current = leaderboard.getCurrentBeastGame().call().await.ok();
+    if let Some(current) = current
+        .as_ref()
+        .filter(|current| current._0.gameConfig == game_config)
+    {
+        return Ok(current.idx);
+    }
+
+    let now = latest_block_timestamp(leaderboard).await?;
+    let Some(idx) = claimable_game_index(games, game_config, now) else {
+        let current = current.as_ref().map(|current| &current._0);
+        return Err(
+            match unclaimable_game_revert(games, current, game_config, now) {
+                Some(revert) => BeastError::ClaimWouldRevert(revert),
+                None => BeastError::Leaderboard(format!(
+                    "there is no open game for the game config {game_config:#x} of this proof"
+                )),
+            },
+        );
+    };
+    let idx = U256::from(idx);
+
+    let game = leaderboard
+        .beastGames(idx)
+        .call()
+        .await
+        .map_err(|e| BeastError::Leaderboard(format!("could not read game {idx}: {e}")))?;
+    if game.gameConfig != game_config {
+        return Err(BeastError::ClaimWouldRevert(ClaimRevert::InvalidGame {
+            expected: game.gameConfig,
+            actual: game_config,
+        }));
+    }
+
+    Ok(idx)
+}
+
+/// read every game of `beastGames`, the contract doesn't expose their count so games are read until the
+/// index is past the end
+pub async fn read_beast_games<P: Provider>(
+    leaderboard: &Leaderboard::LeaderboardInstance<P>,
+) -> Result<Vec<ContractGame>, BeastError> {
+    let mut games = Vec::new();
+    loop {
+        let idx = U256::from(games.len());
+        // reading past the end of the games reverts, any other error is the rpc failing
+        match leaderboard.beastGames(idx).call().await {
+            Ok(game) => games.push(ContractGame {
+                starts_at: u64::try_from(game.startsAtTime).unwrap_or(u64::MAX),
+                ends_at: u64::try_from(game.endsAtTime).unwrap_or(u64::MAX),
+                config: game.gameConfig.to_be_bytes(),
+            }),
+            Err(e) if e.as_revert_data().is_some() => return Ok(games),
+            Err(e) => {
+                return Err(BeastError::Leaderboard(format!(
+                    "could not read game {idx}: {e}"
+                )));
+            }
+        }
+    }
+}
+
+/// the key the leaderboard stores the level a user completed in a game under
+pub fn beast_key(user: Address, game_config: U256) -> B256 {
+    keccak256((user, game_config).abi_encode_params())
+}
+
+/// a revert `claimBeastPoints` would run into, named after the error of the contract
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClaimRevert {
+    /// the proof was made for another address than the one that signs the claim
+    UserAddressMismatch { expected: Address, actual: Address },
+    /// the whitelist is on and the user holds none of the nfts
+    UserIsNotWhitelisted(Address),
+    /// there is no game at this index, the contract panics
+    GameIndexOutOfBounds(U256),
+    /// the game closed before the latest block
+    GameEnded { ends_at_time: U256, now: u64 },
+    /// the game opens after the latest block
+    GameNotStarted { starts_at_time: U256, now: u64 },
+    /// the game has another config than the one the proof was made for
+    InvalidGame { expected: U256, actual: U256 },
+    /// the user already claimed this level or a higher one in this game
+    UserHasAlreadyCompletedThisLevel { completed: U256, proven: u16 },
+}
+
+impl fmt::Display for ClaimRevert {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ClaimRevert::UserAddressMismatch { expected, actual } => write!(
+                f,
+                "UserAddressMismatch: the proof is for {expected} but the claim would be signed by {actual}"
+            ),
+            ClaimRevert::UserIsNotWhitelisted(user) => {
+                write!(f, "UserIsNotWhitelisted: {user} is not on the whitelist")
+            }
+            ClaimRevert::GameIndexOutOfBounds(index) => {
+                write!(f, "panic: there is no game with the index {index}")
+            }
+            ClaimRevert::GameEnded { ends_at_time, now } => write!(
+                f,
+                "GameEnded: the game ended at {ends_at_time}, the latest block is at {now}"
+            ),
+            ClaimRevert::GameNotStarted {
+                starts_at_time,
+                now,
+            } => write!(
+                f,
+                "GameNotStarted: the game starts at {starts_at_time}, the latest block is at {now}"
+            ),
+            ClaimRevert::InvalidGame { expected, actual } => write!(
+                f,
+                "InvalidGame: the game has the config {expected:#x} but the proof is for {actual:#x}"
+            ),
+            ClaimRevert::UserHasAlreadyCompletedThisLevel { completed, proven } => write!(
+                f,
+                "UserHasAlreadyCompletedThisLevel: level {completed} is already claimed, the proof is for level {proven}"
+            ),
+        }
+    }
+}
+
+/// what the leaderboard knows about a claim before it is sent
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClaimState {
+    /// the address that signs the claim
+    pub sender: Address,
+    /// false only when the whitelist is on and the sender isn't on it
+    pub whitelisted: bool,
+    /// the game at the claimed index, if there is one
+    pub game: Option<Leaderboard::BeastGame>,
+    /// the timestamp of the latest block
+    pub block_timestamp: u64,
+    /// the level the sender already claimed for the config of the proof
+    pub level_completed: U256,
+}
+
+/// the revert `claimBeastPoints` would run into, in the order the contract checks them
+///
+/// The inclusion of the proof in an aligned batch isn't checked here since it is only known after
+/// the proof was paid for.
+pub fn check_claim(
+    values: &PublicValues,
+    game_index: U256,
+    state: &ClaimState,
+) -> Result<(), ClaimRevert> {
+    let user = Address::from(values.address);
+    let game_config = U256::from_be_bytes(values.game_config);
+
+    if user != state.sender {
+        return Err(ClaimRevert::UserAddressMismatch {
+            expected: user,
+            actual: state.sender,
+        });
+    }
+    if !state.whitelisted {
+        return Err(ClaimRevert::UserIsNotWhitelisted(user));
+    }
+
+    let game = state
+        .game
+        .as_ref()
+        .ok_or(ClaimRevert::GameIndexOutOfBounds(game_index))?;
+    let now = U256::from(state.block_timestamp);
+    if now >= game.endsAtTime {
+        return Err(ClaimRevert::GameEnded {
+            ends_at_time: game.endsAtTime,
+            now: state.block_timestamp,
+        });
+    }
+    if now < game.startsAtTime {
+        return Err(ClaimRevert::GameNotStarted {
+            starts_at_time: game.startsAtTime,
+            now: state.block_timestamp,
+        });
+    }
+    if game.gameConfig != game_config {
+        return Err(ClaimRevert::InvalidGame {
+            expected: game.gameConfig,
+            actual: game_config,
+        });
+    }
+
+    if U256::from(values.level) <= state.level_completed {
+        return Err(ClaimRevert::UserHasAlreadyCompletedThisLevel {
+            completed: state.level_completed,
+            proven: values.level,
+        });
+    }
+
+    Ok(())
+}
+
+/// read what the leaderboard knows about a claim with view calls
+pub async fn read_claim_state<P: Provider>(
+    leaderboard: &Leaderboard::LeaderboardInstance<P>,
+    sender: Address,
+    game_index: U256,
+    game_config: U256,
+) -> Result<ClaimState, BeastError> {
+    let use_whitelist = leaderboard
+        .useWhitelist()
+        .call()
+        .await
+        .map_err(|e| BeastError::Leaderboard(format!("could not read the whitelist: {e}")))?;
+    let whitelisted = !use_whitelist
+        || leaderboard
+            .isUserWhitelisted(sender)
+            .call()
+            .await
+            .map_err(|e| BeastError::Leaderboard(format!("could not read the whitelist: {e}")))?;
+
+    // reading past the end of the games reverts, any other error is the rpc failing
+    let game = match leaderboard.beastGames(game_index).call().await {
+        Ok(game) => Some(Leaderboard::BeastGame {
+            startsAtTime: game.startsAtTime,
+            endsAtTime: game.endsAtTime,
+            gameConfig: game.gameConfig,
+        }),
+        Err(e) if e.as_revert_data().is_some() => None,
+        Err(e) => {
+            return Err(BeastError::Leaderboard(format!(
+                "could not read game {game_index}: {e}"
+            )));
+        }
+    };
+
+    let block_timestamp = latest_block_timestamp(leaderboard).await?;
+
+    let level_completed = leaderboard
+        .getUserBeastLevelCompleted(beast_key(sender, game_config))
+        .call()
+        .await
+        .map_err(|e| BeastError::Leaderboard(format!("could not read the completed level: {e}")))?;
+
+    Ok(ClaimState {
+        sender,
+        whitelisted,
+        game,
+        block_timestamp,
+        level_completed,
+    })
+}
+
+/// check with view calls that `sender` could claim `values` for the game at `game_index`
+pub async fn dry_run_claim<P: Provider>(
+    leaderboard: &Leaderboard::LeaderboardInstance<P>,
+    sender: Address,
+    game_index: U256,
+    values: &PublicValues,
+) -> Result<(), BeastError> {
+    let state = read_claim_state(
+        leaderboard,
+        sender,
+        game_index,
+        U256::from_be_bytes(values.game_config),
+    )
+    .await?;
+
+    check_claim(values, game_index, &state).map_err(BeastError::ClaimWouldRevert)
+}
+
+/// where a user stands on the leaderboard
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UserStanding {
+    pub address: Address,
+    pub score: U256,
+    /// the level the user claimed in the current game, if there is one
+    pub level_completed: Option<U256>,
+}
+
+/// what the leaderboard shows right now
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LeaderboardSummary {
+    /// the index and the game that is open at the latest block
+    pub current_game: Option<(U256, Leaderboard::BeastGame)>,
+    /// the best users with their score, best first
+    pub top10: Vec<(Address, U256)>,
+    pub user: Option<UserStanding>,
+}
+
+impl LeaderboardSummary {
+    /// the place of the user in the top 10, starting at 1
+    pub fn rank(&self) -> Option<usize> {
+        let user = self.user.as_ref()?;
+        self.top10
+            .iter()
+            .position(|(address, _)| *address == user.address)
+            .map(|idx| idx + 1)
+    }
+}
+
+/// read the current game, the top 10 and the standing of `user` with view calls
+pub async fn read_leaderboard_summary<P: Provider>(
+    leaderboard: &Leaderboard::LeaderboardInstance<P>,
+    user: Option<Address>,
+) -> Result<LeaderboardSummary, BeastError> {
+    // the contract reverts with NoActiveBeastGame when there is no game right now
+    let current_game = match leaderboard.getCurrentBeastGame().call().await {
+        Ok(current) => Some((current.idx, current._0)),
+        Err(e) if e.as_revert_data().is_some() => None,
+        Err(e) => {
+            return Err(BeastError::Leaderboard(format!(
+                "could not read the current game: {e}"
+            )));
+        }
+    };
+
+    let user_score = |address| async move {
+        leaderboard.getUserScore(address).call().await.map_err(|e| {
+            BeastError::Leaderboard(format!("could not read the score of {address}: {e}"))
+        })
+    };
+
+    let top10_addresses = leaderboard
+        .getTop10Score()
+        .call()
+        .await
+        .map_err(|e| BeastError::Leaderboard(format!("could not read the top 10: {e}")))?;
+    let mut top10 = Vec::new();
+    // empty places are the zero address
+    for address in top10_addresses
+        .into_iter()
+        .filter(|address| !address.is_zero())
+    {
+        top10.push((address, user_score(address).await?));
+    }
+
+    let user = match user {
+        Some(address) => {
+            let level_completed = match &current_game {
+                Some((_, game)) => Some(
+                    leaderboard
+                        .getUserBeastLevelCompleted(beast_key(address, game.gameConfig))
+                        .call()
+                        .await
+                        .map_err(|e| {
+                            BeastError::Leaderboard(format!(
+                                "could not read the completed level: {e}"
+                            ))
+                        })?,
+                ),
+                None => None,
+            };
+
+            Some(UserStanding {
+                address,
+                score: user_score(address).await?,
+                level_completed,
+            })
+        }
+        None => None,
+    };
+
+    Ok(LeaderboardSummary {
+        current_game,
+        top10,
+        user,
+    })
+}
+
+/// what the leaderboard needs from aligned to check that a proof was verified
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchInclusion {
+    pub proof_commitment: [u8; 32],
+    pub proof_generator_addr: [u8; 20],
+    pub batch_merkle_root: [u8; 32],
+    pub merkle_path: Vec<[u8; 32]>,
+    pub index_in_batch: usize,
+}
+
+impl From<&AlignedVerificationData> for BatchInclusion {
+    fn from(data: &AlignedVerificationData) -> Self {
+        Self {
+            proof_commitment: data.verification_data_commitment.proof_commitment,
+            proof_generator_addr: data.verification_data_commitment.proof_generator_addr,
+            batch_merkle_root: data.batch_merkle_root,
+            merkle_path: data.batch_inclusion_proof.merkle_path.clone(),
+            index_in_batch: data.index_in_batch,
+        }
+    }
+}
+
+/// claim the points of a proof that was verified on aligned and return the transaction hash
+pub async fn claim_beast_points<P: Provider>(
+    leaderboard: &Leaderboard::LeaderboardInstance<P>,
+    game_index: U256,
+    inclusion: &BatchInclusion,
+    pub_input: Vec<u8>,
+) -> Result<TxHash, BeastError> {
+    let merkle_path = Bytes::from(inclusion.merkle_path.concat());
+
+    let pending = leaderboard
+        .claimBeastPoints(
+            game_index,
+            inclusion.proof_commitment.into(),
+            pub_input.into(),
+            inclusion.proof_generator_addr.into(),
+            inclusion.batch_merkle_root.into(),
+            merkle_path,
+            U256::from(inclusion.index_in_batch),
+        )
+        .send()
+        .await
+        .map_err(|e| BeastError::Leaderboard(format!("could not send the claim: {e}")))?;
+
+    let receipt = pending
+        .get_receipt()
+        .await
+        .map_err(|e| BeastError::Leaderboard(format!("could not get the claim receipt: {e}")))?;
+    if !receipt.status() {
+        return Err(BeastError::Leaderboard(format!(
+            "the claim {} reverted",
+            receipt.transaction_hash
+        )));
+    }
+
+    Ok(receipt.transaction_hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn game(starts_at: u64, ends_at: u64, game_config: u64) -> LeaderboardGame {
+        LeaderboardGame {
+            starts_at_time: format!("{starts_at:#x}"),
+            ends_at_time: format!("{ends_at:#x}"),
+            game_config: format!("{game_config:#066x}"),
+        }
+    }
+
+    #[test]
+    fn check_claim_test() {
+        let user = Address::repeat_byte(1);
+        let game_config: U256 = U256::from(42) << 160;
+        let values = PublicValues {
+            level: 3,
+            game_config: game_config.to_be_bytes(),
+            address: user.into_array(),
+        };
+        let state = ClaimState {
+            sender: user,
+            whitelisted: true,
+            game: Some(Leaderboard::BeastGame {
+                startsAtTime: U256::from(100),
+                endsAtTime: U256::from(200),
+                gameConfig: game_config,
+            }),
+            block_timestamp: 150,
+            level_completed: U256::from(2),
+        };
+        let index = U256::from(1);
+
+        assert_eq!(
+            check_claim(&values, index, &state),
+            Ok(()),
+            "A higher level in an open game of the same config can be claimed"
+        );
+        assert_eq!(
+            check_claim(
+                &values,
+                index,
+                &ClaimState {
+                    sender: Address::repeat_byte(2),
+                    block_timestamp: 300,
+                    ..state.clone()
+                }
+            ),
+            Err(ClaimRevert::UserAddressMismatch {
+                expected: user,
+                actual: Address::repeat_byte(2),
+            }),
+            "The address is checked first like in the contract"
+        );
+        assert_eq!(
+            check_claim(
+                &values,
+                index,
+                &ClaimState {
+                    whitelisted: false,
+                    ..state.clone()
+                }
+            ),
+            Err(ClaimRevert::UserIsNotWhitelisted(user)),
+            "A user that isn't whitelisted can't claim"
+        );
+        assert_eq!(
+            check_claim(
+                &values,
+                index,
+                &ClaimState {
+                    game: None,
+                    ..state.clone()
+                }
+            ),
+            Err(ClaimRevert::GameIndexOutOfBounds(index)),
+            "A game that doesn't exist can't be claimed"
+        );
+        assert_eq!(
+            check_claim(
+                &values,
+                index,
+                &ClaimState {
+                    block_timestamp: 200,
+                    ..state.clone()
+                }
+            ),
+            Err(ClaimRevert::GameEnded {
+                ends_at_time: U256::from(200),
+                now: 200,
+            }),
+            "The game is closed at its end time"
+        );
+        assert_eq!(
+            check_claim(
+                &values,
+                index,
+                &ClaimState {
+                    block_timestamp: 99,
+                    ..state.clone()
+                }
+            ),
+            Err(ClaimRevert::GameNotStarted {
+                starts_at_time: U256::from(100),
+                now: 99,
+            }),
+            "The game can't be claimed before it starts"
+        );
+        assert_eq!(
+            check_claim(
+                &PublicValues {
+                    game_config: [0; 32],
+                    ..values.clone()
+                },
+                index,
+                &state
+            ),
+            Err(ClaimRevert::InvalidGame {
+                expected: game_config,
+                actual: U256::ZERO,
+            }),
+            "The config of the proof must be the one of the game"
+        );
+        assert_eq!(
+            check_claim(
+                &values,
+                index,
+                &ClaimState {
+                    level_completed: U256::from(3),
+                    ..state.clone()
+                }
+            ),
+            Err(ClaimRevert::UserHasAlreadyCompletedThisLevel {
+                completed: U256::from(3),
+                proven: 3,
+            }),
+            "A level that was already claimed can't be claimed again"
+        );
+    }
+
+    #[test]
+    fn rank_test() {
+        let user = Address::repeat_byte(2);
+        let mut summary = LeaderboardSummary {
+            current_game: None,
+            top10: vec![
+                (Address::repeat_byte(1), U256::from(300)),
+                (user, U256::from(200)),
+            ],
+            user: Some(UserStanding {
+                address: user,
+                score: U256::from(200),
+                level_completed: None,
+            }),
+        };
+
+        assert_eq!(summary.rank(), Some(2), "The rank starts at 1");
+
+        summary.top10.pop();
+        assert_eq!(
+            summary.rank(),
+            None,
+            "A user outside the top 10 has no rank"
+        );
+
+        summary.user = None;
+        assert_eq!(summary.rank(), None, "There is no rank without a user");
+    }
+
+    #[test]
+    fn claimable_game_index_test() {
+        let games = vec![game(100, 250, 1), game(200, 350, 2), game(300, 450, 1)];
+
+        assert_eq!(
+            claimable_game_index(&games, U256::from(1), 220),
+            Some(0),
+            "A previous game that is still open can be claimed"
+        );
+        assert_eq!(
+            claimable_game_index(&games, U256::from(1), 320),
+            Some(2),
+            "The latest open game wins"
+        );
+        assert_eq!(
+            claimable_game_index(&games, U256::from(2), 400),
+            None,
+            "A closed game can't be claimed"
+        );
+        assert_eq!(
+            claimable_game_index(&games, U256::from(3), 220),
+            None,
+            "An unknown game config can't be claimed"
+        );
+    }
+
+    #[test]
+    fn unclaimable_game_revert_test() {
+        let games = vec![game(100, 250, 1), game(200, 350, 2), game(300, 450, 1)];
+        let current = Leaderboard::BeastGame {
+            startsAtTime: U256::from(200),
+            endsAtTime: U256::from(350),
+            gameConfig: U256::from(2),
+        };
+
+        assert_eq!(
+            unclaimable_game_revert(&games, Some(&current), U256::from(1), 280),
+            Some(ClaimRevert::GameNotStarted {
+                starts_at_time: U256::from(300),
+                now: 280
+            }),
+            "The latest game of the config didn't start yet"
+        );
+        assert_eq!(
+            unclaimable_game_revert(&games, Some(&current), U256::from(1), 500),
+            Some(ClaimRevert::GameEnded {
+                ends_at_time: U256::from(450),
+                now: 500
+            }),
+            "The latest game of the config ended"
+        );
+        assert_eq!(
+            unclaimable_game_revert(&games, Some(&current), U256::from(3), 220),
+            Some(ClaimRevert::InvalidGame {
+                expected: U256::from(2),
+                actual: U256::from(3)
+            }),
+            "A config without a game is not the current game"
+        );
+        assert_eq!(
+            unclaimable_game_revert(&[], None, U256::from(3), 220),
+            None,
+            "Without the current game there is nothing to compare to"
+        );
+    }
+}
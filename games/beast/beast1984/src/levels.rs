@@ -4,6 +4,8 @@ use reqwest::blocking::Client;
 use std::time::Duration;
 use game_logic::common::game::GameJson;
 
+use crate::error::BeastError;
+
 enum FileHosting {
     LOCAL,
     WEB,
@@ -53,38 +55,40 @@ const fn game_file() -> GameFile {
     }
 }
 
-fn read_local_file(path: &str) -> String {
+fn read_local_file(path: &str) -> Result<String, BeastError> {
     println!("Reading game levels from local file: {}", path);
-    let file = File::open(path).expect("Cannot open game levels file");
+    let file = File::open(path)
+        .map_err(|e| BeastError::LevelsFetch(format!("cannot open {path}: {e}")))?;
     let mut reader = BufReader::new(file);
     let mut content = String::new();
     reader
         .read_to_string(&mut content)
-        .expect("Failed to read game levels file");
-    content
+        .map_err(|e| BeastError::LevelsFetch(format!("cannot read {path}: {e}")))?;
+    Ok(content)
 }
 
-fn fetch_web_file(url: &str) -> String {
+fn fetch_web_file(url: &str) -> Result<String, BeastError> {
     println!("Fetching game levels from web: {}", url);
     let client = Client::builder()
         .timeout(Duration::from_secs(60))
         .build()
-        .expect("Failed to build HTTP client");
+        .map_err(|e| BeastError::LevelsFetch(format!("failed to build HTTP client: {e}")))?;
     let response = client
         .get(url)
         .send()
-        .expect("Failed to send HTTP request");
+        .and_then(|response| response.error_for_status())
+        .map_err(|e| BeastError::LevelsFetch(format!("request to {url} failed: {e}")))?;
     response
         .text()
-        .expect("Failed to read response text")
+        .map_err(|e| BeastError::LevelsFetch(format!("failed to read response text: {e}")))
 }
 
-pub fn get_game_levels() -> Vec<GameJson> {
+pub fn get_game_levels() -> Result<Vec<GameJson>, BeastError> {
     let json_content = match GAME_FILE.hosting {
-        FileHosting::LOCAL => read_local_file(GAME_FILE.location),
-        FileHosting::WEB => fetch_web_file(GAME_FILE.location),
+        FileHosting::LOCAL => read_local_file(GAME_FILE.location)?,
+        FileHosting::WEB => fetch_web_file(GAME_FILE.location)?,
     };
 
     // Parse JSON content
-    serde_json::from_str(&json_content).expect("Invalid JSON format")
-}
\ No newline at end of file
+    serde_json::from_str(&json_content).map_err(BeastError::LevelsParse)
+}
@@ -8,12 +8,21 @@
 //! > from Dan Baker, Alan Brown, Mark Hamilton and Derrick Shadel.
 
 pub mod aligned_client;
+pub mod background_prover;
+pub mod chain_client;
+pub mod consistency;
+pub mod error;
+pub mod ethereum;
 pub mod game;
 pub mod help;
+pub mod leaderboard;
+pub mod proof_file;
+pub mod renderer;
+pub mod settings;
 pub mod sp1_prover;
 pub mod start;
+pub mod submit_config;
 pub mod stty;
-mod ethereum;
 mod levels;
 
 pub use start::*;
@@ -0,0 +1,463 @@
+//! this module contains the proof file container that is written after proving and read before submitting
+//!
+//! All integers are little endian, strings are prefixed with their `u8` length and blobs with their
+//! `u32` length.
+//!
+//! ```text
+//! magic                  8 bytes   "BEAST84\0"
+//! format version         u16       1
+//! proving system         u8        aligned ProvingSystemId
+//! game                   u8 + utf8 "beast1984"
+//! network                u8 + utf8 the network the game was built for
+//! address                20 bytes  the player address
+//! level reached          u16       the last level the proof covers
+//! timestamp              u64       unix seconds when the file was written
+//! program vk commitment  32 bytes  keccak(ELF || proving system)
+//! proof                  u32 + ..  bincode encoded proof
+//! public values          u32 + ..  level, game config and address as 32 byte words
+//! ```
+//!
+//! The program itself isn't part of the file, it is identified by its vk commitment instead.
+
+use std::{fmt, fs, path::Path};
+
+/// the first bytes of every proof file
+pub const MAGIC: [u8; 8] = *b"BEAST84\0";
+/// the format version this crate writes
+pub const FORMAT_VERSION: u16 = 1;
+/// the game written into the header
+pub const GAME: &str = "beast1984";
+/// the size of the committed public values, three 32 byte words
+pub const PUBLIC_VALUES_LEN: usize = 96;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProofFileError {
+    Io(String),
+    BadMagic,
+    UnsupportedVersion(u16),
+    Truncated(&'static str),
+    InvalidString(&'static str),
+    TooLong(&'static str),
+    TrailingBytes(usize),
+    PublicValues(String),
+    HeaderMismatch(&'static str),
+}
+
+impl fmt::Display for ProofFileError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ProofFileError::Io(e) => write!(f, "could not access the proof file: {e}"),
+            ProofFileError::BadMagic => write!(f, "this is not a beast1984 proof file"),
+            ProofFileError::UnsupportedVersion(version) => write!(
+                f,
+                "proof file version {version} is not supported, this build reads version {FORMAT_VERSION}"
+            ),
+            ProofFileError::Truncated(field) => write!(f, "the proof file ends inside the {field}"),
+            ProofFileError::InvalidString(field) => write!(f, "the {field} is not valid utf8"),
+            ProofFileError::TooLong(field) => write!(f, "the {field} is too long to be written"),
+            ProofFileError::TrailingBytes(len) => {
+                write!(f, "the proof file has {len} unexpected bytes at the end")
+            }
+            ProofFileError::PublicValues(e) => write!(f, "invalid public values: {e}"),
+            ProofFileError::HeaderMismatch(field) => {
+                write!(
+                    f,
+                    "the {field} in the header doesn't match the public values"
+                )
+            }
+        }
+    }
+}
+
+/// the values committed by the zkvm program
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PublicValues {
+    /// the last level the proof covers
+    pub level: u16,
+    /// the packed configuration of the game the levels belong to
+    pub game_config: [u8; 32],
+    /// the player address
+    pub address: [u8; 20],
+}
+
+impl PublicValues {
+    /// decode the three 32 byte words committed by the program
+    pub fn decode(bytes: &[u8]) -> Result<Self, ProofFileError> {
+        if bytes.len() != PUBLIC_VALUES_LEN {
+            return Err(ProofFileError::PublicValues(format!(
+                "expected {PUBLIC_VALUES_LEN} bytes, got {}",
+                bytes.len()
+            )));
+        }
+
+        let (level, rest) = bytes.split_at(32);
+        let (game_config, address) = rest.split_at(32);
+
+        if level[..30].iter().any(|byte| *byte != 0) {
+            return Err(ProofFileError::PublicValues(String::from(
+                "the level doesn't fit in 16 bits",
+            )));
+        }
+        if address[..12].iter().any(|byte| *byte != 0) {
+            return Err(ProofFileError::PublicValues(String::from(
+                "the address isn't left padded with zeros",
+            )));
+        }
+
+        Ok(Self {
+            level: u16::from_be_bytes([level[30], level[31]]),
+            game_config: game_config.try_into().expect("The slice is 32 bytes long"),
+            address: address[12..]
+                .try_into()
+                .expect("The slice is 20 bytes long"),
+        })
+    }
+
+    /// encode the values the same way the program commits them
+    pub fn encode(&self) -> Vec<u8> {
+        let mut bytes = vec![0u8; PUBLIC_VALUES_LEN];
+        bytes[30..32].copy_from_slice(&self.level.to_be_bytes());
+        bytes[32..64].copy_from_slice(&self.game_config);
+        bytes[76..96].copy_from_slice(&self.address);
+        bytes
+    }
+
+    /// the `[blocks, static_blocks, common_beasts, super_beasts]` of every level in the game config
+    pub fn level_configs(&self) -> Vec<[u8; 4]> {
+        self.game_config
+            .chunks_exact(4)
+            .map(|config| [config[0], config[1], config[2], config[3]])
+            .take_while(|config| config.iter().any(|value| *value != 0))
+            .collect()
+    }
+}
+
+/// the name of an aligned proving system id
+pub fn proving_system_name(id: u8) -> &'static str {
+    match id {
+        0 => "GnarkPlonkBls12_381",
+        1 => "GnarkPlonkBn254",
+        2 => "GnarkGroth16Bn254",
+        3 => "SP1",
+        4 => "Risc0",
+        5 => "CircomGroth16Bn256",
+        _ => "unknown",
+    }
+}
+
+/// the metadata at the start of a proof file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofHeader {
+    /// the format version the file was written with
+    pub version: u16,
+    /// the aligned proving system id of the proof
+    pub proving_system: u8,
+    /// the game the proof belongs to
+    pub game: String,
+    /// the network the game was built for
+    pub network: String,
+    /// the player address
+    pub address: [u8; 20],
+    /// the last level the proof covers
+    pub level: u16,
+    /// unix seconds when the file was written
+    pub timestamp: u64,
+    /// keccak(ELF || proving system) of the program that was proven
+    pub program_vk_commitment: [u8; 32],
+}
+
+/// a proof together with its header and public values
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofFile {
+    pub header: ProofHeader,
+    /// the bincode encoded proof
+    pub proof: Vec<u8>,
+    /// the values committed by the program
+    pub public_values: Vec<u8>,
+}
+
+impl ProofFile {
+    /// build a proof file for a proof, the level and address are taken from the public values
+    pub fn new(
+        proving_system: u8,
+        network: &str,
+        program_vk_commitment: [u8; 32],
+        timestamp: u64,
+        proof: Vec<u8>,
+        public_values: Vec<u8>,
+    ) -> Result<Self, ProofFileError> {
+        let values = PublicValues::decode(&public_values)?;
+
+        Ok(Self {
+            header: ProofHeader {
+                version: FORMAT_VERSION,
+                proving_system,
+                game: String::from(GAME),
+                network: String::from(network),
+                address: values.address,
+                level: values.level,
+                timestamp,
+                program_vk_commitment,
+            },
+            proof,
+            public_values,
+        })
+    }
+
+    /// decode the public values of the proof
+    pub fn decoded_public_values(&self) -> Result<PublicValues, ProofFileError> {
+        PublicValues::decode(&self.public_values)
+    }
+
+    /// encode the file
+    pub fn to_bytes(&self) -> Result<Vec<u8>, ProofFileError> {
+        let mut buffer = Vec::new();
+
+        buffer.extend_from_slice(&MAGIC);
+        buffer.extend_from_slice(&self.header.version.to_le_bytes());
+        buffer.push(self.header.proving_system);
+        write_string(&mut buffer, &self.header.game, "game")?;
+        write_string(&mut buffer, &self.header.network, "network")?;
+        buffer.extend_from_slice(&self.header.address);
+        buffer.extend_from_slice(&self.header.level.to_le_bytes());
+        buffer.extend_from_slice(&self.header.timestamp.to_le_bytes());
+        buffer.extend_from_slice(&self.header.program_vk_commitment);
+        write_chunk(&mut buffer, &self.proof, "proof")?;
+        write_chunk(&mut buffer, &self.public_values, "public values")?;
+
+        Ok(buffer)
+    }
+
+    /// decode a file and check that the header agrees with the public values
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ProofFileError> {
+        let mut reader = Reader { bytes };
+
+        if reader.take(MAGIC.len(), "magic").ok() != Some(&MAGIC[..]) {
+            return Err(ProofFileError::BadMagic);
+        }
+        let version = reader.u16("format version")?;
+        if version != FORMAT_VERSION {
+            return Err(ProofFileError::UnsupportedVersion(version));
+        }
+
+        let header = ProofHeader {
+            version,
+            proving_system: reader.take(1, "proving system")?[0],
+            game: reader.string("game")?,
+            network: reader.string("network")?,
+            address: reader.array("address")?,
+            level: reader.u16("level")?,
+            timestamp: u64::from_le_bytes(reader.array("timestamp")?),
+            program_vk_commitment: reader.array("program vk commitment")?,
+        };
+        let proof = reader.chunk("proof")?.to_vec();
+        let public_values = reader.chunk("public values")?.to_vec();
+
+        if !reader.bytes.is_empty() {
+            return Err(ProofFileError::TrailingBytes(reader.bytes.len()));
+        }
+
+        let file = Self {
+            header,
+            proof,
+            public_values,
+        };
+        let values = file.decoded_public_values()?;
+        if values.level != file.header.level {
+            return Err(ProofFileError::HeaderMismatch("level"));
+        }
+        if values.address != file.header.address {
+            return Err(ProofFileError::HeaderMismatch("address"));
+        }
+
+        Ok(file)
+    }
+
+    /// read and decode a file from disk
+    pub fn read(path: impl AsRef<Path>) -> Result<Self, ProofFileError> {
+        let bytes = fs::read(path).map_err(|e| ProofFileError::Io(e.to_string()))?;
+        Self::from_bytes(&bytes)
+    }
+
+    /// encode and write the file to disk
+    pub fn write(&self, path: impl AsRef<Path>) -> Result<(), ProofFileError> {
+        fs::write(path, self.to_bytes()?).map_err(|e| ProofFileError::Io(e.to_string()))
+    }
+}
+
+fn write_string(
+    buffer: &mut Vec<u8>,
+    value: &str,
+    field: &'static str,
+) -> Result<(), ProofFileError> {
+    let len = u8::try_from(value.len()).map_err(|_| ProofFileError::TooLong(field))?;
+    buffer.push(len);
+    buffer.extend_from_slice(value.as_bytes());
+    Ok(())
+}
+
+fn write_chunk(
+    buffer: &mut Vec<u8>,
+    chunk: &[u8],
+    field: &'static str,
+) -> Result<(), ProofFileError> {
+    // Note: the length is stored as `u32` assuming a proof never exceeds 4GB. Increasing it means
+    // bumping the format version and updating the reader on the SubmitProof component of the web.
+    let len = u32::try_from(chunk.len()).map_err(|_| ProofFileError::TooLong(field))?;
+    buffer.extend_from_slice(&len.to_le_bytes());
+    buffer.extend_from_slice(chunk);
+    Ok(())
+}
+
+/// reads fields from the front of a byte slice
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize, field: &'static str) -> Result<&'a [u8], ProofFileError> {
+        if self.bytes.len() < len {
+            return Err(ProofFileError::Truncated(field));
+        }
+        let (value, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(value)
+    }
+
+    fn array<const N: usize>(&mut self, field: &'static str) -> Result<[u8; N], ProofFileError> {
+        Ok(self
+            .take(N, field)?
+            .try_into()
+            .expect("take returns exactly N bytes"))
+    }
+
+    fn u16(&mut self, field: &'static str) -> Result<u16, ProofFileError> {
+        Ok(u16::from_le_bytes(self.array(field)?))
+    }
+
+    fn string(&mut self, field: &'static str) -> Result<String, ProofFileError> {
+        let len = self.take(1, field)?[0] as usize;
+        let value = self.take(len, field)?;
+        String::from_utf8(value.to_vec()).map_err(|_| ProofFileError::InvalidString(field))
+    }
+
+    fn chunk(&mut self, field: &'static str) -> Result<&'a [u8], ProofFileError> {
+        let len = u32::from_le_bytes(self.array(field)?) as usize;
+        self.take(len, field)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn public_values() -> PublicValues {
+        let mut game_config = [0u8; 32];
+        game_config[..12].copy_from_slice(&[50, 5, 3, 0, 40, 10, 5, 1, 30, 15, 6, 2]);
+
+        PublicValues {
+            level: 2,
+            game_config,
+            address: [0xab; 20],
+        }
+    }
+
+    fn proof_file() -> ProofFile {
+        ProofFile::new(
+            3,
+            "sepolia",
+            [7; 32],
+            1_761_523_200,
+            vec![1, 2, 3],
+            public_values().encode(),
+        )
+        .expect("The public values are valid")
+    }
+
+    #[test]
+    fn roundtrip_test() {
+        let file = proof_file();
+        let bytes = file.to_bytes().unwrap();
+
+        assert_eq!(&bytes[..8], &MAGIC, "The file starts with the magic");
+        assert_eq!(
+            ProofFile::from_bytes(&bytes),
+            Ok(file.clone()),
+            "A written file reads back the same"
+        );
+        assert_eq!(
+            file.header.level, 2,
+            "The level is taken from the public values"
+        );
+        assert_eq!(
+            file.header.address, [0xab; 20],
+            "The address is taken from the public values"
+        );
+        assert_eq!(
+            file.decoded_public_values().unwrap().level_configs(),
+            vec![[50, 5, 3, 0], [40, 10, 5, 1], [30, 15, 6, 2]],
+            "Every level in the game config is decoded"
+        );
+    }
+
+    #[test]
+    fn bad_files_test() {
+        let bytes = proof_file().to_bytes().unwrap();
+
+        assert_eq!(
+            ProofFile::from_bytes(&[3, 0, 0, 0, 0]),
+            Err(ProofFileError::BadMagic),
+            "A file in the old format is rejected"
+        );
+
+        let mut newer = bytes.clone();
+        newer[8] = 2;
+        assert_eq!(
+            ProofFile::from_bytes(&newer),
+            Err(ProofFileError::UnsupportedVersion(2)),
+            "A newer format version is rejected"
+        );
+
+        assert_eq!(
+            ProofFile::from_bytes(&bytes[..bytes.len() - 1]),
+            Err(ProofFileError::Truncated("public values")),
+            "A truncated file is rejected"
+        );
+
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert_eq!(
+            ProofFile::from_bytes(&trailing),
+            Err(ProofFileError::TrailingBytes(1)),
+            "Extra bytes are rejected"
+        );
+
+        let mut file = proof_file();
+        file.header.level = 3;
+        assert_eq!(
+            ProofFile::from_bytes(&file.to_bytes().unwrap()),
+            Err(ProofFileError::HeaderMismatch("level")),
+            "A header that lies about the level is rejected"
+        );
+    }
+
+    #[test]
+    fn public_values_test() {
+        assert_eq!(
+            PublicValues::decode(&public_values().encode()),
+            Ok(public_values()),
+            "Encoded public values decode the same"
+        );
+        assert!(
+            PublicValues::decode(&[0; 64]).is_err(),
+            "Public values need three words"
+        );
+
+        let mut unpadded = public_values().encode();
+        unpadded[64] = 1;
+        assert!(
+            PublicValues::decode(&unpadded).is_err(),
+            "The address needs to be left padded"
+        );
+    }
+}
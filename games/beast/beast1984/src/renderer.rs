@@ -0,0 +1,534 @@
+//! this module contains the ANSI terminal implementation of the [Renderer] trait
+//!
+//! Instead of printing the entire board on every tick we keep the last frame we drew and only emit
+//! cursor movements and tiles for the cells that changed. The cursor always rests on the line below
+//! the footer so all movements are relative to that line, just like the full render.
+
+use std::fmt::Write;
+
+use crate::{
+    game::{Game, ANSI_BOARD_HEIGHT, ANSI_FOOTER_HEIGHT, ANSI_FRAME_SIZE},
+    settings::{KeyBindings, Theme},
+};
+use game_logic::{
+    common::game::WindowStatus,
+    render::{
+        ansi::{
+            ANSI_BOLD, ANSI_LEFT_BORDER, ANSI_RESET, ANSI_RESET_BG, ANSI_RESET_FONT,
+            ANSI_RIGHT_BORDER,
+        },
+        Flash, Frame, Hud, Modal, Renderer,
+    },
+    Tile, BOARD_WIDTH,
+};
+
+/// how many lines the cursor has to go up from its resting position to reach the first board row
+const ANSI_TOP_OFFSET: usize =
+    ANSI_FRAME_SIZE + ANSI_BOARD_HEIGHT + ANSI_FRAME_SIZE + ANSI_FOOTER_HEIGHT;
+
+/// the width inside the left and right frame borders
+const ANSI_INNER_WIDTH: usize = BOARD_WIDTH * 2;
+
+/// how many columns a string takes up on screen, escape sequences take up none
+fn visible_width(text: &str) -> usize {
+    let mut width = 0;
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // skip until the final byte of the sequence
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            width += 1;
+        }
+    }
+    width
+}
+
+/// a line inside the board frame with the content aligned left
+pub(crate) fn render_line(content: &str) -> String {
+    let padding = ANSI_INNER_WIDTH.saturating_sub(visible_width(content));
+    format!("{ANSI_LEFT_BORDER}{content}{:padding$}{ANSI_RIGHT_BORDER}\n", "")
+}
+
+/// a line inside the board frame with the content centered
+pub(crate) fn render_centered_line(content: &str) -> String {
+    let padding = ANSI_INNER_WIDTH.saturating_sub(visible_width(content));
+    render_line(&format!("{:left$}{content}", "", left = padding / 2))
+}
+
+/// the frame we last put on screen together with its rendered footer
+#[derive(Debug, Clone)]
+struct DrawnFrame {
+    frame: Frame,
+    footer: String,
+}
+
+/// a renderer that remembers what is on screen and only redraws the difference
+#[derive(Debug)]
+pub struct TerminalRenderer {
+    previous: Option<DrawnFrame>,
+    theme: Theme,
+    movement_label: String,
+    status: String,
+}
+
+impl TerminalRenderer {
+    /// create a new renderer with the default theme and keys, the first frame will always be a full redraw
+    pub fn new() -> Self {
+        Self::with_settings(Theme::default(), &KeyBindings::default())
+    }
+
+    /// create a new renderer that paints tiles with a theme and shows the given movement keys
+    pub fn with_settings(theme: Theme, keys: &KeyBindings) -> Self {
+        Self {
+            previous: None,
+            theme,
+            movement_label: keys.movement_label(),
+            status: String::new(),
+        }
+    }
+
+    /// set the status line shown below the HUD, like the progress of a background proof
+    pub fn set_status(&mut self, status: String) {
+        self.status = status.chars().take(ANSI_INNER_WIDTH).collect();
+    }
+
+    /// the background color escape sequence for a flash
+    fn background(flash: Flash) -> &'static str {
+        match flash {
+            Flash::None => ANSI_RESET_BG,
+            Flash::PlayerHit(0) => "\x1b[48;5;196m",
+            Flash::PlayerHit(_) => "\x1b[48;5;208m",
+            Flash::BeastKilled => "\x1b[48;2;51;51;51m",
+        }
+    }
+
+    /// render the footer with the HUD values
+    pub fn render_footer(&self, hud: &Hud) -> String {
+        let mut output = String::new();
+        let minutes = hud.secs_remaining / 60;
+        let seconds = hud.secs_remaining % 60;
+        let timer_color = if hud.tick % 2 == 0 && minutes == 0 && seconds < 20
+            || minutes == 0 && seconds == 0
+        {
+            "\x1b[31m"
+        } else {
+            ANSI_RESET_FONT
+        };
+
+        let lives = if hud.lives == 1 {
+            format!("\x1B[31m{}{ANSI_RESET_FONT}", hud.lives)
+        } else {
+            hud.lives.to_string()
+        };
+
+        output.push_str(&format!("{:<40}", format!("⌂⌂ Move [{}]", self.movement_label)));
+        output.push_str("  Beasts: ");
+        output.push_str(&format!("{ANSI_BOLD}{:>2}{ANSI_RESET}", hud.beasts.to_string()));
+        output.push_str("  Level: ");
+        output.push_str(&format!("{ANSI_BOLD}{:>2}{ANSI_RESET}", hud.level.to_string()));
+        output.push_str("  Time: ");
+        output.push_str(&format!(
+            "{ANSI_BOLD}{timer_color}{:02}:{:02}{ANSI_RESET}",
+            minutes, seconds
+        ));
+        output.push_str("  Lives: ");
+        output.push_str(&format!("{ANSI_BOLD}{lives}{ANSI_RESET}"));
+        output.push_str("  Score: ");
+        output.push_str(&format!("{ANSI_BOLD}{:>4}{ANSI_RESET}", hud.score));
+        output.push_str(&format!(" {}\n", self.theme.paint(&Tile::Player)));
+        output.push_str(&format!("{}\n", self.status));
+
+        output
+    }
+
+    /// render a modal box centered over the board
+    pub fn render_modal(modal: &Modal) -> String {
+        let width = modal
+            .lines
+            .iter()
+            .map(|line| line.chars().count())
+            .chain(std::iter::once(modal.title.chars().count()))
+            .max()
+            .unwrap_or(0)
+            + 2;
+        let height = modal.lines.len() + 3;
+
+        let lines_up = ((ANSI_BOARD_HEIGHT + ANSI_FRAME_SIZE) / 2) + ANSI_FOOTER_HEIGHT + height;
+        let left_pad = format!(
+            "\x1b[{:.0}C",
+            ((BOARD_WIDTH * 2 + ANSI_FRAME_SIZE * 2) / 2).saturating_sub((width + 2) / 2)
+        );
+
+        let mut output = format!("\x1b[{lines_up}F{left_pad}┌{:─<width$}┐\n", "");
+        output.push_str(&format!(
+            "{left_pad}│{ANSI_BOLD}{:^width$}{ANSI_RESET}│\n",
+            modal.title
+        ));
+        for line in &modal.lines {
+            output.push_str(&format!("{left_pad}│{:^width$}│\n", line));
+        }
+        output.push_str(&format!("{left_pad}└{:─<width$}┘\n", ""));
+        output.push_str(&format!("\x1b[{}E", lines_up - height));
+
+        output
+    }
+
+    /// render the board rows with their frame borders in the themed colors
+    fn render_board(&self, frame: &Frame) -> String {
+        let mut output = String::new();
+
+        for row in frame.cells.iter() {
+            output.push_str(ANSI_LEFT_BORDER);
+            for tile in row.iter() {
+                output.push_str(&self.theme.paint(tile));
+            }
+            output.push_str(ANSI_RIGHT_BORDER);
+            output.push('\n');
+        }
+        output.push_str(ANSI_RESET_BG);
+
+        output
+    }
+
+    fn render_full(&self, frame: &Frame, footer: &str) -> String {
+        let mut output = String::new();
+
+        output.push_str(Self::background(frame.flash));
+        output.push_str(&format!("\x1b[{ANSI_TOP_OFFSET}F"));
+        output.push_str(&self.render_board(frame));
+        output.push_str(&Game::render_bottom_frame());
+        output.push_str(footer);
+        output.push_str(&format!("\x1b[{}E", ANSI_FRAME_SIZE));
+        if let Some(modal) = &frame.modal {
+            output.push_str(&Self::render_modal(modal));
+        }
+
+        output
+    }
+
+    fn render_diff(&self, previous: &DrawnFrame, frame: &Frame, footer: &str) -> String {
+        let mut output = String::new();
+
+        for (row_idx, (row, previous_row)) in frame
+            .cells
+            .iter()
+            .zip(previous.frame.cells.iter())
+            .enumerate()
+        {
+            if row == previous_row {
+                continue;
+            }
+
+            let lines_up = ANSI_TOP_OFFSET - row_idx;
+            let _ = write!(output, "\x1b[{lines_up}F");
+            for (column_idx, (tile, previous_tile)) in row.iter().zip(previous_row.iter()).enumerate()
+            {
+                if tile != previous_tile {
+                    // every tile is two characters wide and the columns are 1-based
+                    let column = ANSI_FRAME_SIZE + column_idx * 2 + 1;
+                    let _ = write!(output, "\x1b[{column}G{}", self.theme.paint(tile));
+                }
+            }
+            let _ = write!(output, "\x1b[{lines_up}E");
+        }
+
+        if footer != previous.footer {
+            // every footer line is cleared and rewritten in place without printing new lines
+            let lines_up = ANSI_FRAME_SIZE + ANSI_FOOTER_HEIGHT;
+            let _ = write!(output, "\x1b[{lines_up}F");
+            for line in footer.lines() {
+                let _ = write!(output, "{line}\x1b[K\x1b[1E");
+            }
+            let lines_down = lines_up.saturating_sub(footer.lines().count());
+            if lines_down > 0 {
+                let _ = write!(output, "\x1b[{lines_down}E");
+            }
+        }
+
+        if output.is_empty() {
+            return output;
+        }
+
+        // cells inherit whatever background is active so we set it before and reset it after
+        format!("{}{output}{ANSI_RESET_BG}", Self::background(frame.flash))
+    }
+}
+
+impl Default for TerminalRenderer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Renderer for TerminalRenderer {
+    type Output = String;
+
+    /// render the frame, only emitting what changed since the last call
+    fn render(&mut self, frame: &Frame) -> String {
+        let footer = self.render_footer(&frame.hud);
+        let output = match &self.previous {
+            // a modal covers tiles so any change to it redraws everything underneath
+            Some(previous)
+                if previous.frame.flash == frame.flash
+                    && previous.frame.modal.is_none()
+                    && frame.modal.is_none() =>
+            {
+                self.render_diff(previous, frame, &footer)
+            }
+            Some(previous) if previous.frame == *frame => String::new(),
+            _ => self.render_full(frame, &footer),
+        };
+
+        self.previous = Some(DrawnFrame {
+            frame: frame.clone(),
+            footer,
+        });
+
+        output
+    }
+
+    /// forget what is on screen so the next frame is a full redraw, call this whenever something else
+    /// drew over the board (help, alerts, resizes)
+    fn invalidate(&mut self) {
+        self.previous = None;
+    }
+}
+
+/// a number of seconds as a countdown like `1d 02:03:04`, days are left out when there are none
+pub fn format_countdown(secs: u64) -> String {
+    let days = secs / (24 * 3600);
+    let clock = format!(
+        "{:02}:{:02}:{:02}",
+        (secs / 3600) % 24,
+        (secs / 60) % 60,
+        secs % 60
+    );
+
+    if days > 0 {
+        format!("{days}d {clock}")
+    } else {
+        clock
+    }
+}
+
+/// the part of the status line that tells the player how long the game can be played and claimed
+pub fn render_window_status(status: WindowStatus) -> String {
+    match status {
+        WindowStatus::Open { secs_to_play, .. } => {
+            format!("Game ends in {}", format_countdown(secs_to_play))
+        }
+        WindowStatus::Closing { secs_to_submit } => format!(
+            "⚠ Claims close in {}, a proof may not make it",
+            format_countdown(secs_to_submit)
+        ),
+        WindowStatus::RolledOver { secs_to_submit } => format!(
+            "⚠ A new game started, claims for this one close in {}",
+            format_countdown(secs_to_submit)
+        ),
+        WindowStatus::Expired => String::from("⚠ This game is over, its proofs can't be claimed"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use game_logic::{board::Board, common::levels::Level, render::ansi, BOARD_HEIGHT};
+
+    fn frame(board: &Board) -> Frame {
+        Frame::new(
+            board,
+            Hud {
+                level: Level::One,
+                beasts: 3,
+                secs_remaining: 100,
+                lives: 5,
+                score: 0,
+                tick: 0,
+            },
+        )
+    }
+
+    fn empty_board() -> Board {
+        Board::new([[Tile::Empty; BOARD_WIDTH]; BOARD_HEIGHT])
+    }
+
+    #[test]
+    fn first_frame_is_a_full_redraw_test() {
+        let mut renderer = TerminalRenderer::new();
+        let board = empty_board();
+
+        let output = renderer.render(&frame(&board));
+        assert!(
+            output.contains(&ansi::render_board(&board)),
+            "The first frame contains the whole board"
+        );
+    }
+
+    #[test]
+    fn unchanged_frame_renders_nothing_test() {
+        let mut renderer = TerminalRenderer::new();
+        let board = empty_board();
+
+        renderer.render(&frame(&board));
+        assert_eq!(
+            renderer.render(&frame(&board)),
+            "",
+            "Nothing is emitted when nothing changed"
+        );
+    }
+
+    #[test]
+    fn changed_tile_is_redrawn_test() {
+        let mut renderer = TerminalRenderer::new();
+        let mut board = empty_board();
+
+        renderer.render(&frame(&board));
+        board.buffer[2][3] = Tile::Player;
+        let output = renderer.render(&frame(&board));
+
+        assert_eq!(
+            output,
+            format!(
+                "{ANSI_RESET_BG}\x1b[{}F\x1b[8G{}\x1b[{}E{ANSI_RESET_BG}",
+                ANSI_TOP_OFFSET - 2,
+                Tile::Player,
+                ANSI_TOP_OFFSET - 2
+            ),
+            "Only the changed tile is redrawn"
+        );
+    }
+
+    #[test]
+    fn changed_footer_is_redrawn_test() {
+        let mut renderer = TerminalRenderer::new();
+        let board = empty_board();
+
+        renderer.render(&frame(&board));
+        let mut next = frame(&board);
+        next.hud.score = 12;
+        let output = renderer.render(&next);
+
+        assert!(output.contains("  12"), "The footer is redrawn");
+        assert!(!output.contains('\n'), "The footer is redrawn in place");
+    }
+
+    #[test]
+    fn changed_status_is_redrawn_test() {
+        let mut renderer = TerminalRenderer::new();
+        let board = empty_board();
+
+        renderer.render(&frame(&board));
+        renderer.set_status(String::from("Proving"));
+        let output = renderer.render(&frame(&board));
+
+        assert!(
+            output.contains("\x1b[1EProving\x1b[K"),
+            "The status is drawn on the line below the HUD"
+        );
+        assert!(!output.contains('\n'), "The status is redrawn in place");
+        assert!(
+            output.ends_with(&format!("\x1b[1E\x1b[1E{ANSI_RESET_BG}")),
+            "The cursor goes back to its resting line"
+        );
+    }
+
+    #[test]
+    fn themed_tiles_and_movement_keys_test() {
+        let theme = Theme {
+            player: String::from("38;5;214"),
+            ..Theme::default()
+        };
+        let keys = KeyBindings {
+            up: vec![],
+            ..KeyBindings::default()
+        };
+        let mut renderer = TerminalRenderer::with_settings(theme, &keys);
+        let mut board = empty_board();
+        board.buffer[0][0] = Tile::Player;
+
+        let output = renderer.render(&frame(&board));
+        assert!(
+            output.contains(&format!("\x1b[38;5;214m{}", Tile::Player.raw_symbol())),
+            "The player is painted in the themed color"
+        );
+        assert!(output.contains("Move [ASD/←↓→]"), "The footer shows the configured keys");
+    }
+
+    #[test]
+    fn framed_lines_fill_the_board_width_test() {
+        let line = render_centered_line(&format!("{ANSI_BOLD}[SPACE]{ANSI_RESET} Play"));
+
+        assert_eq!(
+            visible_width(line.trim_end_matches('\n')),
+            BOARD_WIDTH * 2 + ANSI_FRAME_SIZE * 2,
+            "Escape sequences don't count towards the width"
+        );
+        assert!(line.ends_with(&format!("{ANSI_RIGHT_BORDER}\n")), "The line is framed");
+    }
+
+    #[test]
+    fn invalidate_flash_and_modal_force_full_redraw_test() {
+        let mut renderer = TerminalRenderer::new();
+        let board = empty_board();
+        let full_board = ansi::render_board(&board);
+
+        renderer.render(&frame(&board));
+        renderer.invalidate();
+        assert!(
+            renderer.render(&frame(&board)).contains(&full_board),
+            "Invalidating forces a full redraw"
+        );
+        assert!(
+            renderer
+                .render(&frame(&board).with_flash(Flash::PlayerHit(0)))
+                .contains(&full_board),
+            "A new flash forces a full redraw"
+        );
+        let modal = Modal {
+            title: String::from("PAUSED"),
+            lines: vec![],
+        };
+        let output = renderer.render(&frame(&board).with_modal(modal.clone()));
+        assert!(
+            output.contains(&full_board) && output.contains("PAUSED"),
+            "A modal redraws the board and is drawn on top"
+        );
+        assert_eq!(
+            renderer.render(&frame(&board).with_modal(modal)),
+            "",
+            "An unchanged modal frame renders nothing"
+        );
+    }
+
+    #[test]
+    fn render_window_status_test() {
+        assert_eq!(
+            format_countdown(3 * 24 * 3600 + 2 * 3600 + 3 * 60 + 4),
+            "3d 02:03:04",
+            "Days are shown in front of the clock"
+        );
+        assert_eq!(
+            format_countdown(59),
+            "00:00:59",
+            "Days are left out when there are none"
+        );
+        assert_eq!(
+            render_window_status(WindowStatus::Open {
+                secs_to_play: 90,
+                secs_to_submit: 900,
+            }),
+            "Game ends in 00:01:30",
+            "An open game counts down to the next game"
+        );
+        assert_eq!(
+            render_window_status(WindowStatus::RolledOver { secs_to_submit: 60 }),
+            "⚠ A new game started, claims for this one close in 00:01:00",
+            "A rolled over game counts down to the end of the claims"
+        );
+    }
+}
@@ -0,0 +1,466 @@
+//! this module contains the player settings that are read from a RON file in the user config dir
+//!
+//! The file lives at `<config dir>/beast1984/settings.ron` (`$XDG_CONFIG_HOME` or `~/.config` on unix,
+//! `%APPDATA%` on windows) and can be moved with the `BEAST_SETTINGS` env var. Every field is
+//! optional so a file only needs to contain what should differ from the defaults:
+//!
+//! ```ron
+//! (
+//!     keys: (
+//!         up: ["Up", "k"],
+//!         down: ["Down", "j"],
+//!         left: ["Left", "h"],
+//!         right: ["Right", "l"],
+//!         help: ["?"],
+//!     ),
+//!     theme: (
+//!         player: "38;5;214",
+//!     ),
+//!     network: Some("holesky"),
+//!     address: Some("0x0000000000000000000000000000000000000000"),
+//!     keystore: Some("/home/player/.foundry/keystores/beast"),
+//! )
+//! ```
+
+use std::{env, fs, io, path::PathBuf};
+
+use crossterm::event::KeyCode;
+use game_logic::Tile;
+use serde::{Deserialize, Serialize};
+
+use crate::error::BeastError;
+
+/// the env var that overrides where the settings file is read from
+pub const SETTINGS_ENV_VAR: &str = "BEAST_SETTINGS";
+
+/// everything a player can do with a key
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    /// move the player up
+    Up,
+    /// move the player down
+    Down,
+    /// move the player left
+    Left,
+    /// move the player right
+    Right,
+    /// start or resume playing
+    Play,
+    /// prove the execution of the last game
+    Prove,
+    /// pause or resume the game
+    Pause,
+    /// open the help
+    Help,
+    /// quit the game
+    Quit,
+    /// go to the next help page
+    NextPage,
+    /// go to the previous help page
+    PreviousPage,
+}
+
+/// a single key on the keyboard, written as a name ("Up", "Space", "Enter") or a single character
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Key(KeyCode);
+
+impl Key {
+    /// does a pressed key code match this key, characters are matched case insensitive
+    pub fn matches(&self, code: KeyCode) -> bool {
+        match (self.0, code) {
+            (KeyCode::Char(key), KeyCode::Char(pressed)) => {
+                key.to_lowercase().eq(pressed.to_lowercase())
+            }
+            (key, pressed) => key == pressed,
+        }
+    }
+
+    /// the label shown to the player, like `W` or `↑`
+    pub fn label(&self) -> String {
+        match self.0 {
+            KeyCode::Up => String::from("↑"),
+            KeyCode::Down => String::from("↓"),
+            KeyCode::Left => String::from("←"),
+            KeyCode::Right => String::from("→"),
+            KeyCode::Char(' ') => String::from("SPACE"),
+            KeyCode::Char(c) => c.to_uppercase().collect(),
+            KeyCode::Enter => String::from("ENTER"),
+            KeyCode::Esc => String::from("ESC"),
+            KeyCode::Tab => String::from("TAB"),
+            KeyCode::Backspace => String::from("BACKSPACE"),
+            _ => String::from("?"),
+        }
+    }
+}
+
+impl TryFrom<String> for Key {
+    type Error = String;
+
+    fn try_from(name: String) -> Result<Self, Self::Error> {
+        let code = match name.to_lowercase().as_str() {
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "space" => KeyCode::Char(' '),
+            "enter" => KeyCode::Enter,
+            "esc" => KeyCode::Esc,
+            "tab" => KeyCode::Tab,
+            "backspace" => KeyCode::Backspace,
+            _ => {
+                let mut chars = name.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => KeyCode::Char(c),
+                    _ => return Err(format!("unknown key '{name}'")),
+                }
+            }
+        };
+
+        Ok(Self(code))
+    }
+}
+
+impl From<Key> for String {
+    fn from(key: Key) -> Self {
+        match key.0 {
+            KeyCode::Up => String::from("Up"),
+            KeyCode::Down => String::from("Down"),
+            KeyCode::Left => String::from("Left"),
+            KeyCode::Right => String::from("Right"),
+            KeyCode::Char(' ') => String::from("Space"),
+            KeyCode::Char(c) => c.to_string(),
+            KeyCode::Enter => String::from("Enter"),
+            KeyCode::Esc => String::from("Esc"),
+            KeyCode::Tab => String::from("Tab"),
+            KeyCode::Backspace => String::from("Backspace"),
+            _ => String::new(),
+        }
+    }
+}
+
+/// the keys bound to each [Action]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KeyBindings {
+    pub up: Vec<Key>,
+    pub down: Vec<Key>,
+    pub left: Vec<Key>,
+    pub right: Vec<Key>,
+    pub play: Vec<Key>,
+    pub prove: Vec<Key>,
+    pub pause: Vec<Key>,
+    pub help: Vec<Key>,
+    pub quit: Vec<Key>,
+    pub next_page: Vec<Key>,
+    pub previous_page: Vec<Key>,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        let keys = |codes: &[KeyCode]| codes.iter().map(|code| Key(*code)).collect::<Vec<Key>>();
+
+        Self {
+            up: keys(&[KeyCode::Char('w'), KeyCode::Up]),
+            down: keys(&[KeyCode::Char('s'), KeyCode::Down]),
+            left: keys(&[KeyCode::Char('a'), KeyCode::Left]),
+            right: keys(&[KeyCode::Char('d'), KeyCode::Right]),
+            play: keys(&[KeyCode::Char(' ')]),
+            prove: keys(&[KeyCode::Enter]),
+            pause: keys(&[KeyCode::Char('p'), KeyCode::Esc]),
+            help: keys(&[KeyCode::Char('h')]),
+            quit: keys(&[KeyCode::Char('q')]),
+            next_page: keys(&[KeyCode::Char('d'), KeyCode::Right]),
+            previous_page: keys(&[KeyCode::Char('a'), KeyCode::Left]),
+        }
+    }
+}
+
+impl KeyBindings {
+    /// the keys bound to an action
+    pub fn keys(&self, action: Action) -> &[Key] {
+        match action {
+            Action::Up => &self.up,
+            Action::Down => &self.down,
+            Action::Left => &self.left,
+            Action::Right => &self.right,
+            Action::Play => &self.play,
+            Action::Prove => &self.prove,
+            Action::Pause => &self.pause,
+            Action::Help => &self.help,
+            Action::Quit => &self.quit,
+            Action::NextPage => &self.next_page,
+            Action::PreviousPage => &self.previous_page,
+        }
+    }
+
+    /// find the action for a pressed key among the actions available on the current screen,
+    /// the first action in the list wins when a key is bound twice
+    pub fn action(&self, code: KeyCode, available: &[Action]) -> Option<Action> {
+        available
+            .iter()
+            .copied()
+            .find(|action| self.keys(*action).iter().any(|key| key.matches(code)))
+    }
+
+    /// the label of an action like `[Q]` or `[D/→]`
+    pub fn label(&self, action: Action) -> String {
+        let keys = self
+            .keys(action)
+            .iter()
+            .map(Key::label)
+            .collect::<Vec<String>>()
+            .join("/");
+        format!("[{keys}]")
+    }
+
+    /// the label of all movement keys grouped like `WASD/↑←↓→`
+    pub fn movement_label(&self) -> String {
+        let directions = [&self.up, &self.left, &self.down, &self.right];
+        let groups = directions.iter().map(|keys| keys.len()).max().unwrap_or(0);
+
+        (0..groups)
+            .map(|idx| {
+                directions
+                    .iter()
+                    .map(|keys| keys.get(idx).map(Key::label).unwrap_or_default())
+                    .collect::<String>()
+            })
+            .collect::<Vec<String>>()
+            .join("/")
+    }
+}
+
+/// the colors of each tile as ANSI SGR parameters, like `"36"` or `"38;5;214"`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Theme {
+    pub block: String,
+    pub static_block: String,
+    pub player: String,
+    pub common_beast: String,
+    pub super_beast: String,
+    pub egg: String,
+    pub egg_hatching: String,
+    pub hatched_beast: String,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            block: String::from("32"),
+            static_block: String::from("33"),
+            player: String::from("36"),
+            common_beast: String::from("31"),
+            super_beast: String::from("31"),
+            egg: String::from("31"),
+            egg_hatching: String::from("35"),
+            hatched_beast: String::from("31"),
+        }
+    }
+}
+
+impl Theme {
+    /// the SGR parameters for a tile
+    fn color(&self, tile: &Tile) -> Option<&str> {
+        match tile {
+            Tile::Empty => None,
+            Tile::Block => Some(&self.block),
+            Tile::StaticBlock => Some(&self.static_block),
+            Tile::Player => Some(&self.player),
+            Tile::CommonBeast => Some(&self.common_beast),
+            Tile::SuperBeast => Some(&self.super_beast),
+            Tile::Egg => Some(&self.egg),
+            Tile::EggHatching => Some(&self.egg_hatching),
+            Tile::HatchedBeast => Some(&self.hatched_beast),
+        }
+    }
+
+    /// render a tile with its themed color
+    pub fn paint(&self, tile: &Tile) -> String {
+        match self.color(tile) {
+            Some(color) => format!("\x1b[{color}m{}\x1b[39m", tile.raw_symbol()),
+            None => tile.raw_symbol().to_string(),
+        }
+    }
+
+    /// make sure every color is a valid SGR parameter list so we never print broken escape sequences
+    fn validate(&self) -> Result<(), String> {
+        let tiles = [
+            Tile::Block,
+            Tile::StaticBlock,
+            Tile::Player,
+            Tile::CommonBeast,
+            Tile::SuperBeast,
+            Tile::Egg,
+            Tile::EggHatching,
+            Tile::HatchedBeast,
+        ];
+
+        for tile in tiles {
+            let color = self.color(&tile).unwrap_or_default();
+            if color.is_empty() || !color.chars().all(|c| c.is_ascii_digit() || c == ';') {
+                return Err(format!("invalid color '{color}' for {tile:?}"));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// the player settings
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// the key bindings
+    pub keys: KeyBindings,
+    /// the tile colors
+    pub theme: Theme,
+    /// the network used when submitting proofs if none is given
+    pub network: Option<String>,
+    /// the address offered when the game starts
+    pub address: Option<String>,
+    /// a JSON keystore whose address the player plays with, instead of typing it
+    pub keystore: Option<String>,
+}
+
+impl Settings {
+    /// where the settings file is read from
+    pub fn path() -> Option<PathBuf> {
+        if let Some(path) = env::var_os(SETTINGS_ENV_VAR) {
+            return Some(PathBuf::from(path));
+        }
+
+        #[cfg(windows)]
+        let config_dir = env::var_os("APPDATA").map(PathBuf::from);
+
+        #[cfg(not(windows))]
+        let config_dir = env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")));
+
+        config_dir.map(|dir| dir.join("beast1984").join("settings.ron"))
+    }
+
+    /// read the settings file, a missing file gives the default settings
+    pub fn load() -> Result<Self, BeastError> {
+        let Some(path) = Self::path() else {
+            return Ok(Self::default());
+        };
+
+        match fs::read_to_string(&path) {
+            Ok(contents) => Self::parse(&contents)
+                .map_err(|error| BeastError::Settings(format!("{}: {error}", path.display()))),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(error) => Err(BeastError::Settings(format!("{}: {error}", path.display()))),
+        }
+    }
+
+    /// parse settings from a RON string
+    pub fn parse(contents: &str) -> Result<Self, String> {
+        let settings: Self = ron::from_str(contents).map_err(|error| error.to_string())?;
+        settings.theme.validate()?;
+        Ok(settings)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_partial_settings_test() {
+        let settings = Settings::parse(
+            r#"(keys: (up: ["Up", "k"], help: ["?"]), theme: (player: "38;5;214"), network: Some("holesky"))"#,
+        )
+        .expect("The settings are valid");
+
+        assert_eq!(
+            settings.keys.up,
+            vec![Key(KeyCode::Up), Key(KeyCode::Char('k'))],
+            "The up keys are read"
+        );
+        assert_eq!(
+            settings.keys.down,
+            KeyBindings::default().down,
+            "Missing bindings fall back to the defaults"
+        );
+        assert_eq!(
+            settings.theme.paint(&Tile::Player),
+            format!("\x1b[38;5;214m{}\x1b[39m", Tile::Player.raw_symbol()),
+            "The player is painted in the themed color"
+        );
+        assert_eq!(
+            settings.network.as_deref(),
+            Some("holesky"),
+            "The network is read"
+        );
+        assert_eq!(settings.address, None, "The address stays unset");
+    }
+
+    #[test]
+    fn parse_invalid_settings_test() {
+        assert!(
+            Settings::parse(r#"(keys: (up: ["PageUp"]))"#).is_err(),
+            "Unknown key names are rejected"
+        );
+        assert!(
+            Settings::parse(r#"(theme: (block: "32m"))"#).is_err(),
+            "Colors can't inject escape sequences"
+        );
+    }
+
+    #[test]
+    fn default_theme_matches_tile_display_test() {
+        let theme = Theme::default();
+        for tile in [Tile::Empty, Tile::Block, Tile::Player, Tile::EggHatching] {
+            assert_eq!(
+                theme.paint(&tile),
+                tile.to_string(),
+                "The default theme looks like the original game"
+            );
+        }
+    }
+
+    #[test]
+    fn action_lookup_test() {
+        let keys = KeyBindings::default();
+
+        assert_eq!(
+            keys.action(KeyCode::Char('W'), &[Action::Up, Action::Quit]),
+            Some(Action::Up),
+            "Characters match case insensitive"
+        );
+        assert_eq!(
+            keys.action(KeyCode::Char('d'), &[Action::NextPage, Action::Right]),
+            Some(Action::NextPage),
+            "The first available action wins"
+        );
+        assert_eq!(
+            keys.action(KeyCode::Char('q'), &[Action::Up]),
+            None,
+            "Actions that aren't available are ignored"
+        );
+    }
+
+    #[test]
+    fn labels_test() {
+        let keys = KeyBindings::default();
+
+        assert_eq!(
+            keys.movement_label(),
+            "WASD/↑←↓→",
+            "The movement keys are grouped"
+        );
+        assert_eq!(
+            keys.label(Action::Play),
+            "[SPACE]",
+            "Named keys are spelled out"
+        );
+        assert_eq!(
+            keys.label(Action::NextPage),
+            "[D/→]",
+            "Multiple keys are joined"
+        );
+    }
+}
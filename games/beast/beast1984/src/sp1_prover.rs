@@ -1,18 +1,27 @@
 use aligned_sdk::common::types::ProvingSystemId;
-use alloy::hex;
+use alloy::{hex, primitives::Keccak256};
 use chrono::Utc;
 use game_logic::{
     common::levels::LevelJson,
     proving::{LevelLog, ProgramInput},
 };
 use sp1_sdk::{EnvProver, ProverClient, SP1ProofWithPublicValues, SP1Stdin};
-use std::sync::LazyLock;
+use std::{env, fmt, fs, path::PathBuf, sync::LazyLock};
 
-const BEAST_1984_PROGRAM_ELF: &[u8] = include_bytes!("../sp1_program/elf/beast_1984_program");
+use crate::{
+    ethereum,
+    proof_file::{ProofFile, PublicValues, proving_system_name},
+};
+
+/// the zkvm program, aligned needs it to verify our proofs
+pub const BEAST_1984_PROGRAM_ELF: &[u8] = include_bytes!("../sp1_program/elf/beast_1984_program");
 static SP1_PROVER_CLIENT: LazyLock<EnvProver> = LazyLock::new(ProverClient::from_env);
 
 const SP1_PROVING_SYSTEM: [u8; 1] = [ProvingSystemId::SP1 as u8];
 
+/// the env var that overrides where proofs are cached
+pub const PROOF_CACHE_ENV_VAR: &str = "BEAST_PROOF_CACHE";
+
 #[derive(Debug, Clone)]
 pub enum ProvingError {
     WriteInput(String),
@@ -22,25 +31,112 @@ pub enum ProvingError {
     SavingProof(String),
 }
 
-pub fn prove(
+impl fmt::Display for ProvingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ProvingError::WriteInput(e) => write!(f, "invalid program input: {e}"),
+            ProvingError::BuildExecutor(e) => write!(f, "could not build the executor: {e}"),
+            ProvingError::Prove(e) => write!(f, "proving failed: {e}"),
+            ProvingError::Verification(e) => write!(f, "proof verification failed: {e}"),
+            ProvingError::SavingProof(e) => write!(f, "could not save the proof: {e}"),
+        }
+    }
+}
+
+/// build the input of the zkvm program for a run
+pub fn program_input(
     levels_log: Vec<LevelLog>,
     levels: Vec<LevelJson>,
-    address: String,
-) -> Result<SP1ProofWithPublicValues, ProvingError> {
-    let mut stdin = SP1Stdin::new();
-
+    address: &str,
+) -> Result<ProgramInput, ProvingError> {
     let address_bytes =
         hex::decode(address).map_err(|e| ProvingError::WriteInput(e.to_string()))?;
-    // write input data
-    let input = ProgramInput {
+
+    Ok(ProgramInput {
         levels,
         levels_log,
         address: address_bytes,
-    };
-    stdin.write(&input);
+    })
+}
+
+/// the key of a run in the proof cache, the hash of the program input together with the program ELF
+pub fn cache_key(input: &ProgramInput) -> Result<String, ProvingError> {
+    let input_bytes =
+        bincode::serialize(input).map_err(|e| ProvingError::WriteInput(e.to_string()))?;
+
+    let mut hasher = Keccak256::new();
+    hasher.update(&input_bytes);
+    hasher.update(BEAST_1984_PROGRAM_ELF);
+    Ok(hex::encode(hasher.finalize()))
+}
+
+/// the directory proofs are cached in
+pub fn cache_dir() -> Option<PathBuf> {
+    if let Some(path) = env::var_os(PROOF_CACHE_ENV_VAR) {
+        return Some(PathBuf::from(path));
+    }
+
+    #[cfg(windows)]
+    let cache_dir = env::var_os("LOCALAPPDATA").map(PathBuf::from);
+
+    #[cfg(not(windows))]
+    let cache_dir = env::var_os("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")));
+
+    cache_dir.map(|dir| dir.join("beast1984").join("proofs"))
+}
+
+/// where the proof of a run is cached
+pub fn cached_proof_path(input: &ProgramInput) -> Option<PathBuf> {
+    let key = cache_key(input).ok()?;
+    cache_dir().map(|dir| dir.join(format!("{key}.proof")))
+}
+
+/// has this run already been proven with the current program
+pub fn is_proven(input: &ProgramInput) -> bool {
+    cached_proof_path(input).is_some_and(|path| path.exists())
+}
+
+/// read the cached proof of a run, a missing or unreadable cache entry is treated as not proven
+pub fn cached_proof(input: &ProgramInput) -> Option<SP1ProofWithPublicValues> {
+    let bytes = fs::read(cached_proof_path(input)?).ok()?;
+    bincode::deserialize(&bytes).ok()
+}
+
+/// store a proof in the cache, writing to a temporary file first so a crash never leaves half a proof
+fn cache_proof(input: &ProgramInput, proof: &SP1ProofWithPublicValues) -> Result<(), ProvingError> {
+    let path = cached_proof_path(input)
+        .ok_or_else(|| ProvingError::SavingProof(String::from("no cache directory")))?;
+    let bytes = bincode::serialize(proof).map_err(|e| ProvingError::SavingProof(e.to_string()))?;
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| ProvingError::SavingProof(e.to_string()))?;
+    }
+    let tmp_path = path.with_extension("tmp");
+    fs::write(&tmp_path, bytes).map_err(|e| ProvingError::SavingProof(e.to_string()))?;
+    fs::rename(&tmp_path, &path).map_err(|e| ProvingError::SavingProof(e.to_string()))
+}
+
+pub fn prove(
+    levels_log: Vec<LevelLog>,
+    levels: Vec<LevelJson>,
+    address: String,
+) -> Result<SP1ProofWithPublicValues, ProvingError> {
+    let input = program_input(levels_log, levels, &address)?;
 
     let client = &*SP1_PROVER_CLIENT;
     let (pk, vk) = client.setup(BEAST_1984_PROGRAM_ELF);
+
+    // a cached proof is only reused if it still verifies, otherwise we prove again
+    if let Some(proof) = cached_proof(&input).filter(|proof| client.verify(proof, &vk).is_ok()) {
+        return Ok(proof);
+    }
+
+    let mut stdin = SP1Stdin::new();
+    // write input data
+    stdin.write(&input);
+
     let proof = client
         .prove(&pk, &stdin)
         .compressed()
@@ -51,37 +147,178 @@ pub fn prove(
         .verify(&proof, &vk)
         .map_err(|e| ProvingError::Verification(e.to_string()))?;
 
+    // failing to cache only means the next prove takes longer
+    let _ = cache_proof(&input, &proof);
+
     Ok(proof)
 }
 
-fn write_chunk(buf: &mut Vec<u8>, chunk: &[u8]) {
-    // Note: Here we use `u32` to store the length of the chunk assuming that the length of the generated
-    // proof file will not exceed 4GB. In case the length exceeds this limit, you would need to increase
-    // the size of the length field to `u64`, not only here but also in the file reading logic, on the
-    // SubmitProofBeast component.
-    let len = chunk.len() as u32;
-    buf.extend_from_slice(&len.to_le_bytes());
-    buf.extend_from_slice(chunk);
+/// keccak(ELF || proving system), the commitment aligned and the leaderboard identify the program by
+pub fn program_vk_commitment() -> [u8; 32] {
+    let mut hasher = Keccak256::new();
+    hasher.update(BEAST_1984_PROGRAM_ELF);
+    hasher.update(SP1_PROVING_SYSTEM);
+    hasher.finalize().0
 }
 
-pub fn save_proof(proof: SP1ProofWithPublicValues) -> Result<String, ProvingError> {
-    let proving_system_id = SP1_PROVING_SYSTEM;
-    let proof_data = bincode::serialize(&proof).expect("Failed to serialize the proof");
-    let proof_id = BEAST_1984_PROGRAM_ELF.to_vec();
-    let public_inputs = proof.public_values.to_vec();
+/// check a proof file against the embedded program, returns the decoded public values if it is valid
+pub fn verify_proof_file(file: &ProofFile) -> Result<PublicValues, ProvingError> {
+    if file.header.proving_system != ProvingSystemId::SP1 as u8 {
+        return Err(ProvingError::Verification(format!(
+            "the proof was generated with {}, expected SP1",
+            proving_system_name(file.header.proving_system)
+        )));
+    }
+    if file.header.program_vk_commitment != program_vk_commitment() {
+        return Err(ProvingError::Verification(format!(
+            "the program vk commitment 0x{} doesn't match this program 0x{}",
+            hex::encode(file.header.program_vk_commitment),
+            hex::encode(program_vk_commitment())
+        )));
+    }
+
+    let proof: SP1ProofWithPublicValues = bincode::deserialize(&file.proof)
+        .map_err(|e| ProvingError::Verification(format!("the proof can't be decoded: {e}")))?;
+    if proof.public_values.as_slice() != file.public_values.as_slice() {
+        return Err(ProvingError::Verification(String::from(
+            "the public values of the file don't match the ones in the proof",
+        )));
+    }
 
-    let mut buffer = Vec::new();
+    let client = &*SP1_PROVER_CLIENT;
+    let (_, vk) = client.setup(BEAST_1984_PROGRAM_ELF);
+    client
+        .verify(&proof, &vk)
+        .map_err(|e| ProvingError::Verification(e.to_string()))?;
 
-    buffer.extend_from_slice(&proving_system_id);
+    file.decoded_public_values()
+        .map_err(|e| ProvingError::Verification(e.to_string()))
+}
 
-    write_chunk(&mut buffer, &proof_data);
-    write_chunk(&mut buffer, &proof_id);
-    write_chunk(&mut buffer, &public_inputs);
+pub fn save_proof(proof: SP1ProofWithPublicValues) -> Result<String, ProvingError> {
+    let proof_data =
+        bincode::serialize(&proof).map_err(|e| ProvingError::SavingProof(e.to_string()))?;
+    let now = Utc::now();
 
-    let timestamp = Utc::now().format("%Y-%m-%d_%H-%M-%S").to_string();
-    let filename = format!("sp1_solution_{}.bin", timestamp);
+    let file = ProofFile::new(
+        ProvingSystemId::SP1 as u8,
+        ethereum::network_name(),
+        program_vk_commitment(),
+        now.timestamp() as u64,
+        proof_data,
+        proof.public_values.to_vec(),
+    )
+    .map_err(|e| ProvingError::SavingProof(e.to_string()))?;
 
-    std::fs::write(&filename, buffer).map_err(|e| ProvingError::SavingProof(e.to_string()))?;
+    let filename = format!("sp1_solution_{}.bin", now.format("%Y-%m-%d_%H-%M-%S"));
+    file.write(&filename)
+        .map_err(|e| ProvingError::SavingProof(e.to_string()))?;
 
     Ok(filename)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use game_logic::common::{
+        game::{GameJson, encode_game_config},
+        levels::Level,
+    };
+
+    fn input(address: &str) -> ProgramInput {
+        let levels_log = vec![LevelLog {
+            level: Level::One,
+            board: vec![],
+            game_log: vec![],
+        }];
+
+        program_input(levels_log, vec![], address).expect("The address is valid hex")
+    }
+
+    #[test]
+    fn cache_key_test() {
+        let address = "0x0000000000000000000000000000000000000001";
+
+        assert_eq!(
+            cache_key(&input(address)).unwrap(),
+            cache_key(&input(address)).unwrap(),
+            "The same run has the same key"
+        );
+        assert_ne!(
+            cache_key(&input(address)).unwrap(),
+            cache_key(&input("0x0000000000000000000000000000000000000002")).unwrap(),
+            "Another address is another run"
+        );
+        assert_eq!(
+            cache_key(&input(address)).unwrap().len(),
+            64,
+            "The key is a hex encoded 32 byte hash"
+        );
+    }
+
+    #[test]
+    fn verify_rejects_other_program_test() {
+        let public_values = PublicValues {
+            level: 1,
+            game_config: [0; 32],
+            address: [1; 20],
+        };
+        let file = |proving_system: u8, vk_commitment: [u8; 32]| {
+            ProofFile::new(
+                proving_system,
+                "devnet",
+                vk_commitment,
+                0,
+                vec![],
+                public_values.encode(),
+            )
+            .expect("The public values are valid")
+        };
+
+        assert!(
+            verify_proof_file(&file(ProvingSystemId::Risc0 as u8, program_vk_commitment()))
+                .is_err(),
+            "A proof of another proving system is rejected"
+        );
+        assert!(
+            verify_proof_file(&file(ProvingSystemId::SP1 as u8, [0; 32])).is_err(),
+            "A proof of another program is rejected"
+        );
+        assert!(
+            verify_proof_file(&file(ProvingSystemId::SP1 as u8, program_vk_commitment())).is_err(),
+            "A proof that can't be decoded is rejected"
+        );
+    }
+
+    #[test]
+    fn committed_game_config_test() {
+        // the program packs the config on its own, it has to commit what the levels file and the
+        // leaderboard were given or every proof is rejected with InvalidGame
+        let games: Vec<GameJson> = serde_json::from_str(include_str!("../../levels/devnet.json"))
+            .expect("The devnet levels are valid");
+        let levels = games[0].levels.clone();
+        let input = program_input(
+            vec![],
+            levels.clone(),
+            "0x0000000000000000000000000000000000000001",
+        )
+        .expect("The address is valid hex");
+        let mut stdin = SP1Stdin::new();
+        stdin.write(&input);
+
+        let (public_values, _) = ProverClient::builder()
+            .cpu()
+            .build()
+            .execute(BEAST_1984_PROGRAM_ELF, &stdin)
+            .run()
+            .expect("The program runs without levels to prove");
+        let public_values =
+            PublicValues::decode(public_values.as_slice()).expect("The public values are valid");
+
+        assert_eq!(
+            public_values.game_config,
+            encode_game_config(&levels),
+            "The program commits the config of the levels file"
+        );
+    }
+}
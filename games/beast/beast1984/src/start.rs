@@ -11,7 +11,7 @@
 
 use std::{env, io::{self, Write}};
 
-use crate::{game, stty};
+use crate::{error::BeastError, game, stty};
 use dotenv::dotenv;
 use game_logic::{ANSI_RESET_FONT, BOARD_HEIGHT, BOARD_WIDTH};
 
@@ -198,15 +198,21 @@ pub fn start() {
     dotenv().ok();
     
     // Wrap game execution in error handling
-    let result = std::panic::catch_unwind(|| {
-        let mut game = crate::game::Game::new();
+    let result = std::panic::catch_unwind(|| -> Result<(), BeastError> {
+        let mut game = crate::game::Game::new()?;
         game.play();
+        Ok(())
     });
-    
+
     match result {
-        Ok(_) => {
+        Ok(Ok(_)) => {
             // Game completed normally
         }
+        Ok(Err(e)) => {
+            // the game and its raw mode guard have been dropped at this point so the terminal is restored
+            eprintln!("\x1B[31mERROR:{ANSI_RESET_FONT} {e}");
+            pause_and_exit(1);
+        }
         Err(e) => {
             eprintln!("\x1B[31mERROR:{ANSI_RESET_FONT} Game crashed with error:");
             if let Some(s) = e.downcast_ref::<&str>() {
@@ -0,0 +1,497 @@
+//! this module contains the options of the submit tool and the signer it submits with
+//!
+//! Every option can be passed as a flag, flags win over the env vars which win over the settings
+//! file. The env vars are read from the process and from the env file, if there is one, so the tool
+//! can be run without any file on disk:
+//!
+//! ```shell
+//! submit_solution sp1_solution_*.bin --network holesky --chain-id 17000 \
+//!     --rpc-url https://ethereum-holesky-rpc.publicnode.com --leaderboard 0x... \
+//!     --keystore ~/.foundry/keystores/beast
+//! ```
+//!
+//! The signer is read from an encrypted JSON keystore (`--keystore` or `KEYSTORE_PATH`) whose
+//! password is prompted for, or read from `KEYSTORE_PASSWORD` when there is no terminal. A plaintext
+//! `WALLET_PRIVATE_KEY` is only used when no keystore is given.
+
+use std::{
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use alloy::{primitives::U256, signers::local::PrivateKeySigner};
+use dialoguer::Password;
+
+use crate::{
+    aligned_client::{FeeEstimation, FeeStrategy},
+    chain_client::split_rpc_urls,
+    error::BeastError,
+};
+
+/// the env file that is read when no `--env-file` is passed, it doesn't have to exist
+pub const DEFAULT_ENV_FILE: &str = "./beast1984/cmd/.env";
+
+pub const USAGE: &str = "Usage: submit_solution <sp1_solution_*.bin> [game index] [options]
+
+Options (each falls back to the env var in brackets):
+  --game-index <index>          the leaderboard game to claim for, looked up when missing
+  --network <network>           mainnet|holesky|holesky-stage|devnet (NETWORK, then the settings file)
+  --chain-id <id>               (CHAIN_ID)
+  --rpc-url <url[,url...]>      fallbacks are read from in order (ETH_RPC_URL)
+  --leaderboard <address>       the leaderboard contract (LEADERBOARD_CONTRACT_ADDRESS)
+  --keystore <path>             an encrypted JSON keystore to sign with (KEYSTORE_PATH)
+  --fee-estimation <estimation> default|instant|<proofs in batch> (FEE_ESTIMATION)
+  --max-fee-wei <wei>           the most to pay for the verification (MAX_FEE_WEI)
+  --env-file <path>             read the env vars from this file (./beast1984/cmd/.env)
+  --dry-run                     check that the claim would go through without paying for it
+  --resubmit                    send the proof again instead of waiting for the batch it was sent in
+  --help                        print this help";
+
+/// load the env vars of `path`, or of the default env file if it exists
+pub fn load_env_file(path: Option<&Path>) -> Result<(), BeastError> {
+    let path = match path {
+        Some(path) => path,
+        None if Path::new(DEFAULT_ENV_FILE).exists() => Path::new(DEFAULT_ENV_FILE),
+        None => return Ok(()),
+    };
+
+    dotenv::from_path(path)
+        .map_err(|e| BeastError::SubmitConfig(format!("could not read {}: {e}", path.display())))
+}
+
+/// the options as they were passed on the command line
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SubmitArgs {
+    pub proof_path: Option<String>,
+    pub game_index: Option<U256>,
+    pub network: Option<String>,
+    pub chain_id: Option<u64>,
+    pub rpc_url: Option<String>,
+    pub leaderboard: Option<String>,
+    pub keystore: Option<PathBuf>,
+    pub fee_estimation: Option<FeeEstimation>,
+    pub max_fee_wei: Option<u128>,
+    pub env_file: Option<PathBuf>,
+    pub dry_run: bool,
+    pub resubmit: bool,
+    pub help: bool,
+}
+
+fn parse_value<T: FromStr>(flag: &str, value: &str) -> Result<T, BeastError> {
+    value
+        .parse()
+        .map_err(|_| BeastError::SubmitConfig(format!("invalid value \"{value}\" for {flag}")))
+}
+
+fn parse_game_index(value: &str) -> Result<U256, BeastError> {
+    U256::from_str(value).map_err(|_| {
+        BeastError::SubmitConfig(format!("the game index \"{value}\" must be a number"))
+    })
+}
+
+impl SubmitArgs {
+    /// parse the arguments without the binary name, flags take their value as `--flag value` or
+    /// `--flag=value`, the proof and the game index can also be passed positionally
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, BeastError> {
+        let mut parsed = SubmitArgs::default();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            if arg == "--help" || arg == "-h" {
+                parsed.help = true;
+                continue;
+            }
+            if arg == "--dry-run" {
+                parsed.dry_run = true;
+                continue;
+            }
+            if arg == "--resubmit" {
+                parsed.resubmit = true;
+                continue;
+            }
+
+            let Some(flag) = arg.strip_prefix("--") else {
+                if parsed.proof_path.is_none() {
+                    parsed.proof_path = Some(arg);
+                } else if parsed.game_index.is_none() {
+                    parsed.game_index = Some(parse_game_index(&arg)?);
+                } else {
+                    return Err(BeastError::SubmitConfig(format!(
+                        "unexpected argument \"{arg}\""
+                    )));
+                }
+                continue;
+            };
+
+            let (flag, value) = match flag.split_once('=') {
+                Some((flag, value)) => (flag, value.to_string()),
+                None => (
+                    flag,
+                    args.next().ok_or_else(|| {
+                        BeastError::SubmitConfig(format!("--{flag} needs a value"))
+                    })?,
+                ),
+            };
+
+            match flag {
+                "game-index" => parsed.game_index = Some(parse_game_index(&value)?),
+                "network" => parsed.network = Some(value),
+                "chain-id" => parsed.chain_id = Some(parse_value("--chain-id", &value)?),
+                "rpc-url" => parsed.rpc_url = Some(value),
+                "leaderboard" => parsed.leaderboard = Some(value),
+                "keystore" => parsed.keystore = Some(PathBuf::from(value)),
+                "fee-estimation" => {
+                    parsed.fee_estimation = Some(value.parse().map_err(BeastError::SubmitConfig)?)
+                }
+                "max-fee-wei" => parsed.max_fee_wei = Some(parse_value("--max-fee-wei", &value)?),
+                "env-file" => parsed.env_file = Some(PathBuf::from(value)),
+                flag => {
+                    return Err(BeastError::SubmitConfig(format!("unknown flag --{flag}")));
+                }
+            }
+        }
+
+        Ok(parsed)
+    }
+}
+
+/// where the key to sign with comes from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SignerSource {
+    /// an encrypted JSON keystore, the password is prompted for
+    Keystore(PathBuf),
+    /// a plaintext private key from the env
+    PrivateKey(String),
+}
+
+/// read a keystore with a password from `KEYSTORE_PASSWORD` or the terminal
+fn decrypt_keystore(path: &Path, password: Option<String>) -> Result<PrivateKeySigner, BeastError> {
+    let password = match password {
+        Some(password) => password,
+        None => Password::new()
+            .with_prompt(format!("Password for {}", path.display()))
+            .interact()
+            .map_err(|e| BeastError::SubmitConfig(format!("could not read the password: {e}")))?,
+    };
+
+    PrivateKeySigner::decrypt_keystore(path, password)
+        .map_err(|e| BeastError::SubmitConfig(format!("could not decrypt {}: {e}", path.display())))
+}
+
+impl SignerSource {
+    /// load the signer, `env` is used to look up `KEYSTORE_PASSWORD`
+    pub fn load(
+        &self,
+        env: impl Fn(&str) -> Option<String>,
+    ) -> Result<PrivateKeySigner, BeastError> {
+        match self {
+            SignerSource::Keystore(path) => decrypt_keystore(path, env("KEYSTORE_PASSWORD")),
+            SignerSource::PrivateKey(key) => PrivateKeySigner::from_str(key)
+                .map_err(|e| BeastError::SubmitConfig(format!("invalid private key: {e}"))),
+        }
+    }
+}
+
+/// everything the submit tool needs, after the flags, the env and the settings were merged
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubmitConfig {
+    pub proof_path: String,
+    pub game_index: Option<U256>,
+    pub network: String,
+    pub chain_id: u64,
+    pub rpc_urls: Vec<String>,
+    pub leaderboard: String,
+    pub signer: SignerSource,
+    pub fee_strategy: FeeStrategy,
+    pub dry_run: bool,
+    pub resubmit: bool,
+}
+
+fn required(value: Option<String>, what: &str) -> Result<String, BeastError> {
+    value.ok_or_else(|| BeastError::SubmitConfig(format!("{what} must be set")))
+}
+
+impl SubmitConfig {
+    /// merge the flags with the env vars looked up with `env` and the network of the settings file
+    pub fn resolve(
+        args: SubmitArgs,
+        env: impl Fn(&str) -> Option<String>,
+        settings_network: Option<String>,
+    ) -> Result<Self, BeastError> {
+        let proof_path = required(args.proof_path, "the proof file")?;
+        let network = required(
+            args.network.or_else(|| env("NETWORK")).or(settings_network),
+            "--network or NETWORK",
+        )?;
+        let chain_id = match args.chain_id {
+            Some(chain_id) => chain_id,
+            None => parse_value(
+                "CHAIN_ID",
+                &required(env("CHAIN_ID"), "--chain-id or CHAIN_ID")?,
+            )?,
+        };
+        let rpc_urls = split_rpc_urls(&required(
+            args.rpc_url.or_else(|| env("ETH_RPC_URL")),
+            "--rpc-url or ETH_RPC_URL",
+        )?);
+        if rpc_urls.is_empty() {
+            return Err(BeastError::SubmitConfig(String::from(
+                "--rpc-url or ETH_RPC_URL has no url",
+            )));
+        }
+        let leaderboard = required(
+            args.leaderboard
+                .or_else(|| env("LEADERBOARD_CONTRACT_ADDRESS")),
+            "--leaderboard or LEADERBOARD_CONTRACT_ADDRESS",
+        )?;
+
+        let signer = match args
+            .keystore
+            .or_else(|| env("KEYSTORE_PATH").map(PathBuf::from))
+        {
+            Some(path) => SignerSource::Keystore(path),
+            None => SignerSource::PrivateKey(required(
+                env("WALLET_PRIVATE_KEY"),
+                "--keystore, KEYSTORE_PATH or WALLET_PRIVATE_KEY",
+            )?),
+        };
+
+        let estimation = match args.fee_estimation {
+            Some(estimation) => estimation,
+            None => env("FEE_ESTIMATION")
+                .map(|estimation| estimation.parse().map_err(BeastError::SubmitConfig))
+                .transpose()?
+                .unwrap_or(FeeEstimation::Instant),
+        };
+        let max_fee_wei = match args.max_fee_wei {
+            Some(max_fee_wei) => Some(max_fee_wei),
+            None => env("MAX_FEE_WEI")
+                .map(|max_fee_wei| parse_value("MAX_FEE_WEI", &max_fee_wei))
+                .transpose()?,
+        };
+
+        Ok(Self {
+            proof_path,
+            game_index: args.game_index,
+            network,
+            chain_id,
+            rpc_urls,
+            leaderboard,
+            signer,
+            fee_strategy: FeeStrategy {
+                estimation,
+                max_fee_wei,
+            },
+            dry_run: args.dry_run,
+            resubmit: args.resubmit,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn args(args: &[&str]) -> Result<SubmitArgs, BeastError> {
+        SubmitArgs::parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    fn env(vars: &[(&'static str, &'static str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: HashMap<&str, &str> = vars.iter().copied().collect();
+        move |name| vars.get(name).map(|value| value.to_string())
+    }
+
+    #[test]
+    fn parse_test() {
+        assert_eq!(
+            args(&["proof.bin", "3"]).unwrap(),
+            SubmitArgs {
+                proof_path: Some(String::from("proof.bin")),
+                game_index: Some(U256::from(3)),
+                ..SubmitArgs::default()
+            },
+            "The proof and the game index can be passed positionally"
+        );
+
+        let parsed = args(&[
+            "--keystore",
+            "key.json",
+            "proof.bin",
+            "--chain-id=17000",
+            "--fee-estimation",
+            "4",
+            "--game-index",
+            "2",
+        ])
+        .unwrap();
+        assert_eq!(
+            parsed.keystore,
+            Some(PathBuf::from("key.json")),
+            "A flag takes the next argument as its value"
+        );
+        assert_eq!(
+            parsed.chain_id,
+            Some(17000),
+            "A flag can be joined to its value"
+        );
+        assert_eq!(
+            parsed.fee_estimation,
+            Some(FeeEstimation::BatchOf(4)),
+            "The fee estimation is parsed"
+        );
+        assert_eq!(
+            parsed.game_index,
+            Some(U256::from(2)),
+            "The game index can be passed as a flag"
+        );
+        assert_eq!(
+            parsed.proof_path,
+            Some(String::from("proof.bin")),
+            "The proof can be passed between flags"
+        );
+
+        assert!(
+            args(&["--help"]).unwrap().help,
+            "Help is a flag without a value"
+        );
+        assert!(
+            args(&["proof.bin", "--resubmit"]).unwrap().resubmit,
+            "Resubmit is a flag without a value"
+        );
+        assert!(
+            args(&["--rpc-url"]).is_err(),
+            "A flag without a value is rejected"
+        );
+        assert!(
+            args(&["--private-key", "0x01"]).is_err(),
+            "An unknown flag is rejected"
+        );
+        assert!(
+            args(&["proof.bin", "first"]).is_err(),
+            "The game index must be a number"
+        );
+        assert!(
+            args(&["a", "1", "b"]).is_err(),
+            "A third positional argument is rejected"
+        );
+    }
+
+    #[test]
+    fn resolve_test() {
+        let full_env = env(&[
+            ("NETWORK", "holesky"),
+            ("CHAIN_ID", "17000"),
+            ("ETH_RPC_URL", "https://env.rpc"),
+            ("LEADERBOARD_CONTRACT_ADDRESS", "0xenv"),
+            ("WALLET_PRIVATE_KEY", "0xkey"),
+            ("MAX_FEE_WEI", "1000"),
+        ]);
+
+        let config = SubmitConfig::resolve(
+            args(&[
+                "proof.bin",
+                "--rpc-url",
+                "https://flag.rpc",
+                "--keystore",
+                "key.json",
+            ])
+            .unwrap(),
+            &full_env,
+            Some(String::from("mainnet")),
+        )
+        .unwrap();
+        assert_eq!(
+            config.rpc_urls,
+            vec!["https://flag.rpc"],
+            "A flag wins over the env"
+        );
+        assert_eq!(config.network, "holesky", "The env wins over the settings");
+        assert_eq!(
+            config.signer,
+            SignerSource::Keystore(PathBuf::from("key.json")),
+            "A keystore wins over the private key"
+        );
+        assert_eq!(
+            config.fee_strategy,
+            FeeStrategy {
+                estimation: FeeEstimation::Instant,
+                max_fee_wei: Some(1000),
+            },
+            "The fee strategy is read from the env"
+        );
+
+        let config = SubmitConfig::resolve(args(&["proof.bin"]).unwrap(), &full_env, None).unwrap();
+        assert_eq!(
+            config.signer,
+            SignerSource::PrivateKey(String::from("0xkey")),
+            "The private key is used without a keystore"
+        );
+
+        let config = SubmitConfig::resolve(
+            args(&[
+                "proof.bin",
+                "--network=devnet",
+                "--chain-id=31337",
+                "--rpc-url=http://localhost:8545",
+                "--leaderboard=0xflag",
+                "--keystore=key.json",
+            ])
+            .unwrap(),
+            env(&[]),
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            (config.network.as_str(), config.chain_id),
+            ("devnet", 31337),
+            "Everything can be passed as flags without an env"
+        );
+
+        assert!(
+            SubmitConfig::resolve(
+                args(&["proof.bin", "--keystore", "key.json"]).unwrap(),
+                env(&[]),
+                Some(String::from("holesky"))
+            )
+            .is_err(),
+            "Missing options are reported"
+        );
+        assert!(
+            SubmitConfig::resolve(
+                args(&["proof.bin"]).unwrap(),
+                env(&[
+                    ("NETWORK", "holesky"),
+                    ("CHAIN_ID", "17000"),
+                    ("ETH_RPC_URL", "https://env.rpc"),
+                    ("LEADERBOARD_CONTRACT_ADDRESS", "0xenv"),
+                ]),
+                None
+            )
+            .is_err(),
+            "A signer is required"
+        );
+
+        let config = SubmitConfig::resolve(
+            args(&["proof.bin", "--rpc-url", "https://a.rpc, https://b.rpc"]).unwrap(),
+            &full_env,
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            config.rpc_urls,
+            vec!["https://a.rpc", "https://b.rpc"],
+            "A list of rpc urls is split into fallbacks"
+        );
+        assert!(
+            SubmitConfig::resolve(
+                args(&["proof.bin", "--rpc-url", ","]).unwrap(),
+                &full_env,
+                None
+            )
+            .is_err(),
+            "A list without urls is rejected"
+        );
+    }
+}
@@ -0,0 +1,290 @@
+//! claims a proof on a local anvil chain against the leaderboard contract and a mock aligned service
+//! manager that only accepts the exact batch inclusion it was deployed with
+//!
+//! Needs anvil and the contracts built with `forge build` in `contracts`, run it with
+//! `cargo test --test claim_beast_points -- --ignored`
+
+use std::{
+    fs,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use alloy::{
+    hex,
+    network::{EthereumWallet, TransactionBuilder},
+    node_bindings::Anvil,
+    primitives::{Address, Bytes, FixedBytes, U256, keccak256},
+    providers::{Provider, ProviderBuilder},
+    rpc::types::TransactionRequest,
+    signers::local::PrivateKeySigner,
+    sol_types::{SolCall, SolValue},
+};
+use beast1984::{
+    error::BeastError,
+    leaderboard::{
+        BatchInclusion, ClaimRevert, Leaderboard, LeaderboardGame, beast_key, claim_beast_points,
+        dry_run_claim, find_game_index, read_leaderboard_summary,
+    },
+    proof_file::PublicValues,
+};
+
+fn artifact_bytecode(file: &str, contract: &str) -> Vec<u8> {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../../../contracts/out")
+        .join(file)
+        .join(format!("{contract}.json"));
+    let artifact = fs::read_to_string(&path).unwrap_or_else(|e| {
+        panic!(
+            "{}: {e}, run forge build in contracts first",
+            path.display()
+        )
+    });
+    let artifact: serde_json::Value =
+        serde_json::from_str(&artifact).expect("The artifact is valid json");
+
+    hex::decode(
+        artifact["bytecode"]["object"]
+            .as_str()
+            .expect("The artifact has a bytecode"),
+    )
+    .expect("The bytecode is valid hex")
+}
+
+async fn deploy(provider: &impl Provider, code: Vec<u8>) -> Address {
+    let tx = TransactionRequest::default().with_deploy_code(code);
+    provider
+        .send_transaction(tx)
+        .await
+        .expect("The deployment is sent")
+        .get_receipt()
+        .await
+        .expect("The deployment is mined")
+        .contract_address
+        .expect("The contract is deployed")
+}
+
+fn beast_game(starts_at: u64, ends_at: u64, game_config: U256) -> Leaderboard::BeastGame {
+    Leaderboard::BeastGame {
+        endsAtTime: U256::from(ends_at),
+        gameConfig: game_config,
+        startsAtTime: U256::from(starts_at),
+    }
+}
+
+#[tokio::test]
+#[ignore = "needs anvil and the contracts built with forge"]
+async fn claim_beast_points_test() {
+    let anvil = Anvil::new().try_spawn().expect("anvil is installed");
+    let signer: PrivateKeySigner = anvil.keys()[0].clone().into();
+    let user = signer.address();
+    let provider = ProviderBuilder::new()
+        .wallet(EthereumWallet::from(signer))
+        .connect_http(anvil.endpoint_url());
+
+    let game_config = U256::from_be_slice(&[50, 5, 3, 0, 40, 10, 5, 1, 30, 15, 6, 2]) << 160;
+    let other_config = U256::from_be_slice(&[60, 5, 3, 0, 50, 10, 5, 1, 40, 15, 6, 2]) << 160;
+    let values = PublicValues {
+        level: 2,
+        game_config: game_config.to_be_bytes(),
+        address: user.into_array(),
+    };
+    let public_values = values.encode();
+    let inclusion = BatchInclusion {
+        proof_commitment: [1; 32],
+        proof_generator_addr: user.into_array(),
+        batch_merkle_root: [2; 32],
+        merkle_path: vec![[3; 32], [4; 32]],
+        index_in_batch: 5,
+    };
+
+    let mock_args = (
+        FixedBytes::from(inclusion.proof_commitment),
+        keccak256(&public_values),
+        FixedBytes::from(inclusion.proof_generator_addr),
+        FixedBytes::from(inclusion.batch_merkle_root),
+        keccak256(inclusion.merkle_path.concat()),
+        U256::from(inclusion.index_in_batch),
+    );
+    let mock = deploy(
+        &provider,
+        [
+            artifact_bytecode("MockAlignedServiceManager.sol", "MockAlignedServiceManager"),
+            mock_args.abi_encode_params(),
+        ]
+        .concat(),
+    )
+    .await;
+
+    // the game of the proof is still open but the current game already moved on
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("The clock is after the epoch")
+        .as_secs();
+    let games = vec![
+        beast_game(now - 7200, now + 3600, other_config),
+        beast_game(now - 3600, now + 3600, game_config),
+        beast_game(now - 60, now + 7200, other_config),
+        beast_game(now + 3600, now + 10800, game_config),
+    ];
+    let games_file: Vec<LeaderboardGame> = games
+        .iter()
+        .map(|game| LeaderboardGame {
+            starts_at_time: format!("{:#x}", game.startsAtTime),
+            ends_at_time: format!("{:#x}", game.endsAtTime),
+            game_config: format!("{:#066x}", game.gameConfig),
+        })
+        .collect();
+
+    let implementation = deploy(
+        &provider,
+        artifact_bytecode("Leaderboard.sol", "Leaderboard"),
+    )
+    .await;
+    let initialize = Leaderboard::initializeCall {
+        owner: user,
+        _alignedServiceManager: mock,
+        _alignedBatcherPaymentService: mock,
+        _zkArcadeNft: Address::ZERO,
+        _beastGames: games,
+        _parityGames: vec![],
+        _useWhitelist: false,
+        _beastVkCommitment: FixedBytes::from([7; 32]),
+        _parityVkCommitment: FixedBytes::ZERO,
+    }
+    .abi_encode();
+    let proxy = deploy(
+        &provider,
+        [
+            artifact_bytecode("ERC1967Proxy.sol", "ERC1967Proxy"),
+            (implementation, Bytes::from(initialize)).abi_encode_params(),
+        ]
+        .concat(),
+    )
+    .await;
+    let leaderboard = Leaderboard::new(proxy, provider.clone());
+
+    let game_index = find_game_index(&leaderboard, game_config, &games_file)
+        .await
+        .expect("The game of the proof is found");
+    assert_eq!(
+        game_index,
+        U256::from(1),
+        "The open game of the proof is picked over the current one"
+    );
+    assert!(
+        matches!(
+            find_game_index(&leaderboard, U256::from(3), &games_file).await,
+            Err(BeastError::ClaimWouldRevert(
+                ClaimRevert::InvalidGame { .. }
+            ))
+        ),
+        "A proof without a game is reported like the claim would revert"
+    );
+
+    let dry_run = |sender, index: u64| {
+        let leaderboard = leaderboard.clone();
+        let values = values.clone();
+        async move { dry_run_claim(&leaderboard, sender, U256::from(index), &values).await }
+    };
+    let revert = |result: Result<(), BeastError>| match result {
+        Err(BeastError::ClaimWouldRevert(revert)) => Some(revert),
+        Err(e) => panic!("The dry run can read the leaderboard: {e}"),
+        Ok(()) => None,
+    };
+    assert_eq!(
+        revert(dry_run(user, 1).await),
+        None,
+        "The dry run lets a valid claim through"
+    );
+    assert_eq!(
+        revert(dry_run(Address::repeat_byte(9), 1).await),
+        Some(ClaimRevert::UserAddressMismatch {
+            expected: user,
+            actual: Address::repeat_byte(9),
+        }),
+        "The dry run catches a claim signed by another address"
+    );
+    assert!(
+        matches!(
+            revert(dry_run(user, 0).await),
+            Some(ClaimRevert::InvalidGame { .. })
+        ),
+        "The dry run catches a game of another config"
+    );
+    assert!(
+        matches!(
+            revert(dry_run(user, 3).await),
+            Some(ClaimRevert::GameNotStarted { .. })
+        ),
+        "The dry run catches a game that hasn't started"
+    );
+    assert_eq!(
+        revert(dry_run(user, 4).await),
+        Some(ClaimRevert::GameIndexOutOfBounds(U256::from(4))),
+        "The dry run catches a game that doesn't exist"
+    );
+
+    assert!(
+        claim_beast_points(
+            &leaderboard,
+            U256::from(0),
+            &inclusion,
+            public_values.clone()
+        )
+        .await
+        .is_err(),
+        "Claiming for another game reverts"
+    );
+
+    let mut wrong_path = inclusion.clone();
+    wrong_path.merkle_path.reverse();
+    assert!(
+        claim_beast_points(&leaderboard, game_index, &wrong_path, public_values.clone())
+            .await
+            .is_err(),
+        "A merkle path that isn't the verified one is rejected"
+    );
+
+    claim_beast_points(&leaderboard, game_index, &inclusion, public_values)
+        .await
+        .expect("The claim goes through");
+    assert_eq!(
+        revert(dry_run(user, 1).await),
+        Some(ClaimRevert::UserHasAlreadyCompletedThisLevel {
+            completed: U256::from(2),
+            proven: 2,
+        }),
+        "The dry run catches a level that was already claimed"
+    );
+
+    assert_eq!(
+        leaderboard
+            .getUserBeastLevelCompleted(beast_key(user, game_config))
+            .call()
+            .await
+            .expect("The level can be read"),
+        U256::from(2),
+        "The claimed level is stored for the game"
+    );
+
+    let summary = read_leaderboard_summary(&leaderboard, Some(user))
+        .await
+        .expect("The leaderboard can be read");
+    assert_eq!(
+        summary.current_game.map(|(idx, _)| idx),
+        Some(U256::from(2)),
+        "The latest open game is the current one"
+    );
+    assert_eq!(
+        summary.top10,
+        vec![(user, U256::from(2 * 60000))],
+        "The user is the only one in the top 10 with the points of the claim"
+    );
+    assert_eq!(summary.rank(), Some(1), "The user is ranked first");
+    assert_eq!(
+        summary.user.and_then(|user| user.level_completed),
+        Some(U256::ZERO),
+        "Nothing was claimed in the current game"
+    );
+}
@@ -1,9 +1,24 @@
-use std::{fs::File, io::Write};
+use std::{fs, fs::File, io::Write};
 
-use game_logic::common::{game::GameJson, levels::LevelJson};
-use primitive_types::U256;
-use rand::Rng;
-use serde::{Deserialize, Serialize};
+use campaign::{
+    games_in_weeks, generate_campaign, parse_windows, validate_windows, AppendedGames,
+    GameGenerator, GameWindow, Leaderboard, Schedule, ScheduledGame, DEFAULT_WINDOWS,
+};
+use game_logic::common::{
+    game::{encode_game_config, GameJson, LEVELS_PER_GAME},
+    levels::LevelJson,
+};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use serde::Serialize;
+
+/// written next to the seed, the same seed only gives the same campaign with the same generator and rng,
+/// ChaCha8Rng is used since its output is fixed across versions unlike StdRng
+const GENERATOR: &str = concat!(
+    "gen_levels ",
+    env!("CARGO_PKG_VERSION"),
+    " with rand_chacha 0.9 ChaCha8Rng"
+);
 
 fn base_template() -> Vec<LevelJson> {
     vec![
@@ -45,47 +60,27 @@ fn base_template() -> Vec<LevelJson> {
 
 fn extrapolate_level(prev: &LevelJson, level_index: usize, rng: &mut impl Rng) -> LevelJson {
     let factor = 1.0 + (level_index as f32 * 0.05); // progressive difficulty increase
-    let variation = |base: u8, max_variation: u8| -> u8 {
-        let mut rng = rand::rng();
+    let mut variation = |base: u8, max_variation: u8| -> u8 {
         let var = rng.random_range(0..=max_variation);
         base.saturating_add(var)
     };
+    let static_blocks = variation(prev.static_blocks, 5);
+    let common_beasts = variation(prev.common_beasts, 3);
+    let beast_starting_distance = variation(prev.beast_starting_distance, 5);
 
     LevelJson {
-        blocks: (prev.blocks as f32 * 0.98) as u8 - rng.random_range(0..3),
-        static_blocks: variation(prev.static_blocks, 5),
-        common_beasts: variation(prev.common_beasts, 3),
-        super_beasts: prev.super_beasts + rng.random_range(0..=2),
+        blocks: ((prev.blocks as f32 * 0.98) as u8).saturating_sub(rng.random_range(0..3)),
+        static_blocks,
+        common_beasts,
+        super_beasts: prev.super_beasts.saturating_add(rng.random_range(0..=2)),
         eggs: 0,
         egg_hatching_time: prev.egg_hatching_time,
-        beast_starting_distance: variation(prev.beast_starting_distance, 5),
+        beast_starting_distance,
         time: (prev.time as f32 * factor.min(1.5)) as u64,
         completion_score: prev.completion_score + 3 + rng.random_range(0..=3),
     }
 }
 
-fn encode_game_config(game_levels: &[LevelJson]) -> [u8; 32] {
-    let mut levels: [[u8; 4]; 3] = [[0u8; 4]; 3];
-
-    for i in 0..game_levels.len() {
-        let level = &game_levels[i];
-        levels[i][0] = level.blocks;
-        levels[i][1] = level.static_blocks;
-        levels[i][2] = level.common_beasts;
-        levels[i][3] = level.super_beasts;
-    }
-
-    let mut packed_game = [0u8; 32];
-    for (i, level) in levels.iter().enumerate() {
-        packed_game[i * 4] = level[0];
-        packed_game[i * 4 + 1] = level[1];
-        packed_game[i * 4 + 2] = level[2];
-        packed_game[i * 4 + 3] = level[3];
-    }
-
-    packed_game
-}
-
 fn generate_game_levels(levels_per_game: usize, rng: &mut impl Rng) -> Vec<LevelJson> {
     let base = base_template();
     let mut levels = vec![];
@@ -102,7 +97,6 @@ fn generate_game_levels(levels_per_game: usize, rng: &mut impl Rng) -> Vec<Level
             level.static_blocks = fuzz(level.static_blocks);
             level.common_beasts = fuzz(level.common_beasts);
             level.super_beasts = fuzz(level.super_beasts);
-            level.completion_score = level.completion_score;
             levels.push(level);
         } else {
             let prev = levels.last().unwrap();
@@ -113,104 +107,495 @@ fn generate_game_levels(levels_per_game: usize, rng: &mut impl Rng) -> Vec<Level
     levels
 }
 
-#[derive(Serialize, Deserialize)]
-#[allow(non_snake_case)]
-struct LeaderboardLevel {
-    endsAtTime: String,
-    gameConfig: String,
-    startsAtTime: String,
+/// the beast levels of a campaign, the same seed always gives the same levels
+struct BeastLevels {
+    rng: ChaCha8Rng,
 }
 
-#[derive(Serialize, Deserialize)]
-struct LeaderboardConfig {
-    games: Vec<LeaderboardLevel>,
+impl GameGenerator for BeastLevels {
+    type Levels = Vec<LevelJson>;
+
+    fn generate_levels(&mut self) -> Vec<LevelJson> {
+        generate_game_levels(LEVELS_PER_GAME, &mut self.rng)
+    }
+
+    fn encode_config(&self, levels: &Vec<LevelJson>) -> [u8; 32] {
+        encode_game_config(levels)
+    }
 }
 
-fn main() {
-    let args: Vec<String> = std::env::args().collect();
-    if args.len() != 5 {
-        eprintln!(
-            "Usage: {} <campaign_weeks_amount> <submission_offset_in_minutes> <network> <start_time_utc>",
-            args[0]
-        );
-        std::process::exit(1);
+type BeastGame = ScheduledGame<Vec<LevelJson>>;
+
+/// generate the games of a campaign, the same seed always gives the same games
+fn generate_games(schedule: &Schedule, seed: u64) -> Vec<BeastGame> {
+    generate_campaign(
+        schedule,
+        &mut BeastLevels {
+            rng: ChaCha8Rng::seed_from_u64(seed),
+        },
+    )
+}
+
+/// the game as the game and the web read it from the levels file, with the seed it was generated from
+fn game_json(game: &BeastGame, seed: u64) -> GameJson {
+    GameJson {
+        levels: game.levels.clone(),
+        game_config: hex::encode(game.config),
+        from_time: game.window.starts_at,
+        to_time: game.window.ends_at,
+        seed: Some(seed),
+        generator: Some(GENERATOR.to_string()),
     }
+}
 
-    let weeks_amount: u64 = args[1].parse().expect("Invalid total campaign in weeks");
-    let submission_offset_minutes: u64 =
-        args[2].parse().expect("Invalid submission offset minutes");
-    let network: String = args[3].parse().expect("Invalid network");
-    let start_time_utc: u64 = args[4].parse().expect("Invalid start time");
+fn leaderboard_config(games: &[BeastGame], seed: u64) -> Leaderboard {
+    Leaderboard {
+        seed: Some(seed),
+        generator: Some(GENERATOR.to_string()),
+        ..Leaderboard::new(games)
+    }
+}
 
-    // Get the time in seconds from the parameter
-    let mut start_timestamp = std::time::Duration::from_secs(start_time_utc).as_secs();
+/// the next game starts when the last game stops being played, proofs are still taken for it during the
+/// submission offset like between the games of a single run
+fn append_start_time(last_game: &GameJson, submission_offset: u64) -> u64 {
+    last_game.to_time.saturating_sub(submission_offset)
+}
 
-    let four_days_seconds = 4 * 24 * 3600;
-    let three_days_seconds = 3 * 24 * 3600;
+/// every gap or overlap between the games, the new games are checked against the last previous game
+fn validate_schedule(previous_games: &[GameJson], games: &[GameJson], overlap: u64) -> Vec<String> {
+    let window = |game: &GameJson| GameWindow {
+        starts_at: game.from_time,
+        ends_at: game.to_time,
+    };
+    let windows = previous_games
+        .last()
+        .into_iter()
+        .chain(games)
+        .map(window)
+        .collect::<Vec<_>>();
 
-    // Generate a sequence of seconds per game alternating between 4 and 3 days
-    let mut seconds_per_game_sequence = vec![];
-    for i in 0..(weeks_amount * 2) {
-        if i % 2 == 0 {
-            seconds_per_game_sequence.push(four_days_seconds);
-        } else {
-            seconds_per_game_sequence.push(three_days_seconds);
+    validate_windows(&windows, overlap, previous_games.len().saturating_sub(1))
+        .err()
+        .unwrap_or_default()
+        .iter()
+        .map(ToString::to_string)
+        .collect()
+}
+
+/// every problem of every game, as a line of the report, the games are numbered from `first_game`
+fn validate_games(games: &[GameJson], first_game: usize) -> Vec<String> {
+    games
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, game)| {
+            game.validate()
+                .err()
+                .map(|errors| (first_game + idx, errors))
+        })
+        .flat_map(|(idx, errors)| {
+            errors
+                .into_iter()
+                .map(move |error| format!("game {}: {}", idx, error))
+        })
+        .collect()
+}
+
+const USAGE: &str = "--network <network> (--weeks <weeks> | --games <games>) (--start <start_time_utc> | --append) [--submission-offset <minutes>] [--windows <lengths>] [--seed <seed>]
+
+  --network            the levels are written to levels/<network>.json and levels/leaderboard_<network>.json
+  --weeks              generate as many games as it takes to fill the weeks
+  --games              generate this many games
+  --start              when the first game starts, in seconds since the epoch
+  --append             add the games after the last game of levels/<network>.json, the new games are
+                       also written to levels/leaderboard_<network>_append.json for addBeastGames
+  --submission-offset  how long proofs are still taken after a game ended, 720 by default
+  --windows            how long the games last, like 4d,3d (the default), 12h or 90m, repeated in order
+  --seed               the seed of the levels, a random one is used and printed when it is not set";
+
+/// how many games to generate
+#[derive(Debug, PartialEq)]
+enum GameCount {
+    Weeks(u64),
+    Games(usize),
+}
+
+/// the options of a gen_levels run
+#[derive(Debug, PartialEq)]
+struct Args {
+    network: String,
+    count: GameCount,
+    /// when the first game starts, `None` when appending
+    start_time: Option<u64>,
+    append: bool,
+    submission_offset_minutes: u64,
+    /// the window lengths in seconds
+    windows: Vec<u64>,
+    seed: Option<u64>,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let mut network = None;
+    let mut count = None;
+    let mut start_time = None;
+    let mut append = false;
+    let mut submission_offset_minutes = 720;
+    let mut windows = DEFAULT_WINDOWS.to_vec();
+    let mut seed = None;
+
+    while let Some(arg) = args.next() {
+        if arg == "--append" {
+            append = true;
+            continue;
+        }
+        let (flag, value) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => {
+                (flag.to_string(), Some(value.to_string()))
+            }
+            _ if arg.starts_with("--") => (arg, args.next()),
+            _ => return Err(format!("unexpected argument \"{}\"", arg)),
+        };
+        let value = value.ok_or_else(|| format!("{} needs a value", flag))?;
+        let invalid = || format!("invalid value \"{}\" for {}", value, flag);
+        match flag.as_str() {
+            "--network" => network = Some(value),
+            "--weeks" => count = Some(GameCount::Weeks(value.parse().map_err(|_| invalid())?)),
+            "--games" => count = Some(GameCount::Games(value.parse().map_err(|_| invalid())?)),
+            "--start" => start_time = Some(value.parse().map_err(|_| invalid())?),
+            "--submission-offset" => {
+                submission_offset_minutes = value.parse().map_err(|_| invalid())?
+            }
+            "--windows" => windows = parse_windows(&value).ok_or_else(invalid)?,
+            "--seed" => seed = Some(value.parse().map_err(|_| invalid())?),
+            _ => return Err(format!("unknown flag {}", flag)),
         }
     }
 
-    let levels_per_game: usize = 3;
+    if append == start_time.is_some() {
+        return Err(String::from(
+            "either --start or --append is needed, but not both",
+        ));
+    }
 
-    let mut rng = rand::rng();
-    let games: Vec<GameJson> = (0..seconds_per_game_sequence.len())
-        .map(|i| {
-            let levels = generate_game_levels(levels_per_game, &mut rng);
-            let from_time = start_timestamp;
-            let to_time = start_timestamp + seconds_per_game_sequence[i];
-            start_timestamp = to_time;
+    Ok(Args {
+        network: network.ok_or("--network is needed")?,
+        count: count.ok_or("--weeks or --games is needed")?,
+        start_time,
+        append,
+        submission_offset_minutes,
+        windows,
+        seed,
+    })
+}
 
-            GameJson {
-                from_time: from_time,
-                to_time: to_time + submission_offset_minutes * 60,
-                game_config: hex::encode(encode_game_config(&levels)),
-                levels,
-            }
-        })
-        .collect();
+fn exit_with_usage(program: &str, error: &str) -> ! {
+    eprintln!("{}\nUsage: {} {}", error, program, USAGE);
+    std::process::exit(1);
+}
 
-    let json = serde_json::to_string_pretty(&games).expect("Failed to serialize");
+fn read_json<T: serde::de::DeserializeOwned>(path: &str) -> T {
+    let content = fs::read_to_string(path)
+        .unwrap_or_else(|e| panic!("Unable to read {} to append to it: {}", path, e));
+    serde_json::from_str(&content).unwrap_or_else(|e| panic!("Invalid {}: {}", path, e))
+}
 
-    let mut file = File::create(format!("levels/{}.json", network)).expect("Unable to create file");
+fn write_json(path: &str, value: &impl Serialize) {
+    let json = serde_json::to_string_pretty(value).expect("Failed to serialize");
+    let mut file = File::create(path).expect("Unable to create file");
     file.write_all(json.as_bytes())
         .expect("Unable to write to file");
-    println!("Levels written to levels/{}.json", network);
-
-    let mut leaderboard_file = File::create(format!("levels/leaderboard_{}.json", network))
-        .expect("Unable to create file");
-    let leaderboard_config = LeaderboardConfig {
-        games: games
-            .iter()
-            .map(|game| {
-                let mut start_buf = [0u8; 32];
-                let mut end_buf = [0u8; 32];
-
-                U256::from(game.from_time).to_big_endian(&mut start_buf);
-                U256::from(game.to_time).to_big_endian(&mut end_buf);
-
-                LeaderboardLevel {
-                    startsAtTime: format!("0x{}", hex::encode(start_buf)),
-                    endsAtTime: format!("0x{}", hex::encode(end_buf)),
-                    gameConfig: format!("0x{}", game.game_config.clone()),
-                }
-            })
-            .collect(),
+}
+
+fn main() {
+    let mut args = std::env::args();
+    let program = args.next().unwrap_or_else(|| String::from("gen_levels"));
+    let args = parse_args(args).unwrap_or_else(|error| exit_with_usage(&program, &error));
+
+    let levels_path = format!("levels/{}.json", args.network);
+    let leaderboard_path = format!("levels/leaderboard_{}.json", args.network);
+
+    // an appended campaign goes on where the last game of the existing one ends
+    let (previous_games, previous_leaderboard) = if args.append {
+        (
+            read_json::<Vec<GameJson>>(&levels_path),
+            Some(read_json::<Leaderboard>(&leaderboard_path)),
+        )
+    } else {
+        (vec![], None)
     };
-    let leaderboard_config_json =
-        serde_json::to_string_pretty(&leaderboard_config).expect("Failed to serialize");
-    leaderboard_file
-        .write_all(leaderboard_config_json.as_bytes())
-        .expect("Unable to write to file");
+    let submission_offset = args.submission_offset_minutes * 60;
+    let start_time = match (args.start_time, previous_games.last()) {
+        (Some(start_time), _) => start_time,
+        (None, Some(last_game)) => append_start_time(last_game, submission_offset),
+        (None, None) => {
+            eprintln!("There are no games in {} to append to", levels_path);
+            std::process::exit(1);
+        }
+    };
+
+    let schedule = Schedule {
+        start_time,
+        games: match args.count {
+            GameCount::Weeks(weeks) => games_in_weeks(weeks, &args.windows),
+            GameCount::Games(games) => games,
+        },
+        windows: args.windows,
+        submission_offset,
+    };
+
+    // a campaign without a seed gets a random one, it is written down so the campaign can be regenerated
+    let seed = args.seed.unwrap_or_else(|| rand::rng().random());
+    println!("Generating levels with seed {} ({})", seed, GENERATOR);
+
+    let campaign = generate_games(&schedule, seed);
+    let games = campaign
+        .iter()
+        .map(|game| game_json(game, seed))
+        .collect::<Vec<_>>();
+
+    // nothing is written when a single level is broken, so a bad campaign never gets deployed
+    let mut errors = validate_schedule(&previous_games, &games, schedule.submission_offset);
+    errors.extend(validate_games(&games, previous_games.len()));
+    if !errors.is_empty() {
+        eprintln!("The campaign generated with seed {} is not valid:", seed);
+        for error in &errors {
+            eprintln!("  {}", error);
+        }
+        std::process::exit(1);
+    }
+
+    let new_entries = leaderboard_config(&campaign, seed);
+    let leaderboard = match previous_leaderboard {
+        Some(mut leaderboard) => {
+            leaderboard.appended.push(AppendedGames {
+                first_game: previous_games.len(),
+                seed,
+                generator: GENERATOR.to_string(),
+            });
+            leaderboard.games.extend(new_entries.games.iter().cloned());
+            leaderboard
+        }
+        None => leaderboard_config(&campaign, seed),
+    };
+    let all_games = previous_games.into_iter().chain(games).collect::<Vec<_>>();
+
+    write_json(&levels_path, &all_games);
+    println!("Levels written to {}", levels_path);
+
+    write_json(&leaderboard_path, &leaderboard);
     println!(
-        "Leaderboard for contract config written to levels/leaderboard_{}.json",
-        network
+        "Leaderboard for contract config written to {}",
+        leaderboard_path
     );
+
+    if args.append {
+        let append_path = format!("levels/leaderboard_{}_append.json", args.network);
+        write_json(&append_path, &new_entries);
+        println!(
+            "The {} new games for addBeastGames written to {}",
+            new_entries.games.len(),
+            append_path
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schedule(games: usize) -> Schedule {
+        Schedule {
+            start_time: 1761523200,
+            games,
+            windows: DEFAULT_WINDOWS.to_vec(),
+            submission_offset: 720 * 60,
+        }
+    }
+
+    fn jsons(games: &[BeastGame]) -> Vec<GameJson> {
+        games.iter().map(|game| game_json(game, 42)).collect()
+    }
+
+    fn args(args: &str) -> Result<Args, String> {
+        parse_args(args.split_whitespace().map(String::from))
+    }
+
+    #[test]
+    fn generate_games_test() {
+        let games =
+            serde_json::to_string_pretty(&jsons(&generate_games(&schedule(4), 42))).unwrap();
+        assert_eq!(
+            games,
+            serde_json::to_string_pretty(&jsons(&generate_games(&schedule(4), 42))).unwrap(),
+            "The same seed generates the same levels"
+        );
+        assert_ne!(
+            games,
+            serde_json::to_string_pretty(&jsons(&generate_games(&schedule(4), 43))).unwrap(),
+            "Another seed generates other levels"
+        );
+
+        let game = &jsons(&generate_games(&schedule(1), 42))[0];
+        assert_eq!(
+            (game.from_time, game.to_time),
+            (1761523200, 1761523200 + 4 * 24 * 3600 + 720 * 60),
+            "The game is played in its window"
+        );
+        assert_eq!(
+            game.game_config,
+            hex::encode(encode_game_config(&game.levels)),
+            "The config encodes the levels"
+        );
+        assert_eq!(
+            game.game_config, "c80a0500b41e08029a3208050000000000000000000000000000000000000000",
+            "The levels of a seed don't change with the version of rand"
+        );
+        assert_eq!(
+            (game.seed, game.generator.as_deref()),
+            (Some(42), Some(GENERATOR)),
+            "The levels file records the seed and the generator"
+        );
+
+        let config: serde_json::Value =
+            serde_json::to_value(leaderboard_config(&generate_games(&schedule(4), 42), 42))
+                .unwrap();
+        assert_eq!(config["seed"], 42, "The seed is recorded");
+        assert_eq!(config["generator"], GENERATOR, "The generator is recorded");
+        assert_eq!(
+            config["games"].as_array().map(Vec::len),
+            Some(4),
+            "Every game is listed"
+        );
+        assert!(
+            config.get("appended").is_none(),
+            "A new campaign has nothing appended"
+        );
+    }
+
+    #[test]
+    fn append_start_time_test() {
+        let window = |game: &GameJson| (game.from_time, game.to_time);
+        let campaign = jsons(&generate_games(&schedule(4), 42));
+
+        let previous = jsons(&generate_games(&schedule(2), 42));
+        let appended = jsons(&generate_games(
+            &Schedule {
+                start_time: append_start_time(&previous[1], 720 * 60),
+                ..schedule(2)
+            },
+            7,
+        ));
+        assert_eq!(
+            previous
+                .iter()
+                .chain(&appended)
+                .map(window)
+                .collect::<Vec<_>>(),
+            campaign.iter().map(window).collect::<Vec<_>>(),
+            "Appended games are scheduled like the games of a single run"
+        );
+        assert!(
+            validate_schedule(&previous, &appended, 720 * 60).is_empty(),
+            "Appended games follow the previous ones"
+        );
+    }
+
+    #[test]
+    fn parse_args_test() {
+        assert_eq!(
+            args("--network devnet --weeks 5 --start 1761523200"),
+            Ok(Args {
+                network: String::from("devnet"),
+                count: GameCount::Weeks(5),
+                start_time: Some(1761523200),
+                append: false,
+                submission_offset_minutes: 720,
+                windows: DEFAULT_WINDOWS.to_vec(),
+                seed: None,
+            }),
+            "The defaults are used"
+        );
+        assert_eq!(
+            args("--append --games=2 --network=sepolia --windows=1d,12h --submission-offset 30 --seed 7"),
+            Ok(Args {
+                network: String::from("sepolia"),
+                count: GameCount::Games(2),
+                start_time: None,
+                append: true,
+                submission_offset_minutes: 30,
+                windows: vec![24 * 3600, 12 * 3600],
+                seed: Some(7),
+            }),
+            "Every flag is read, with or without ="
+        );
+        assert!(
+            args("--network devnet --weeks 5").is_err(),
+            "A start or append is needed"
+        );
+        assert!(
+            args("--network devnet --weeks 5 --start 1 --append").is_err(),
+            "An appended campaign starts after the existing one"
+        );
+        assert!(
+            args("--network devnet --start 1").is_err(),
+            "The amount of games is needed"
+        );
+        assert!(
+            args("--network devnet --weeks 5 --start 1 --windows 4d,x").is_err(),
+            "Invalid windows are rejected"
+        );
+        assert!(
+            args("5 720 devnet 1761523200").is_err(),
+            "Positional arguments are not taken anymore"
+        );
+    }
+
+    #[test]
+    fn validate_schedule_test() {
+        let games = jsons(&generate_games(&schedule(4), 42));
+        assert!(
+            validate_schedule(&[], &games, 720 * 60).is_empty(),
+            "A generated campaign follows its schedule"
+        );
+        assert!(
+            validate_schedule(&games[..2], &games[2..], 720 * 60).is_empty(),
+            "Appended games follow the previous ones"
+        );
+
+        let mut later = games.clone();
+        later[2].from_time += 3600 * 24;
+        assert_eq!(
+            validate_schedule(&later[..2], &later[2..], 720 * 60),
+            vec![String::from(
+                "no game takes proofs for 43200s before game 2 starts"
+            )],
+            "A gap after the previous games is reported"
+        );
+    }
+
+    #[test]
+    fn validate_games_test() {
+        let mut games = jsons(&generate_games(&schedule(10), 42));
+        assert!(
+            validate_games(&games, 0).is_empty(),
+            "A generated campaign is valid"
+        );
+
+        games[3].levels[2].static_blocks = 0;
+        games[3].levels[2].super_beasts = 1;
+        assert_eq!(
+            validate_games(&games, 0),
+            vec![String::from(
+                "game 3: level 3: super beasts can only be killed against static blocks but there are none"
+            )],
+            "The broken level is reported with its game"
+        );
+        assert_eq!(
+            validate_games(&games[3..], 3),
+            vec![String::from(
+                "game 3: level 3: super beasts can only be killed against static blocks but there are none"
+            )],
+            "Appended games are numbered after the existing ones"
+        );
+    }
 }
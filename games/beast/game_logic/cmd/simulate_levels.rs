@@ -0,0 +1,111 @@
+use std::fs;
+
+use game_logic::{
+    common::{game::GameJson, levels::Level},
+    simulation::{estimate_difficulty, find_outliers, LevelDifficulty},
+};
+
+fn exit_with_usage(program: &str) -> ! {
+    eprintln!(
+        "Usage: {} <levels.json> [--runs <runs per level>] [--seed <seed>] [--tolerance <win rate>]",
+        program
+    );
+    std::process::exit(1);
+}
+
+fn main() {
+    let mut args = std::env::args();
+    let program = args
+        .next()
+        .unwrap_or_else(|| String::from("simulate_levels"));
+    let mut runs: usize = 50;
+    let mut seed: u64 = 0;
+    let mut tolerance: f64 = 0.25;
+    let mut path = None;
+    while let Some(arg) = args.next() {
+        let (flag, value) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => {
+                (flag.to_string(), Some(value.to_string()))
+            }
+            _ if arg.starts_with("--") => (arg, args.next()),
+            _ => {
+                path = Some(arg);
+                continue;
+            }
+        };
+        let value = value.unwrap_or_else(|| exit_with_usage(&program));
+        match flag.as_str() {
+            "--runs" => runs = value.parse().unwrap_or_else(|_| exit_with_usage(&program)),
+            "--seed" => seed = value.parse().unwrap_or_else(|_| exit_with_usage(&program)),
+            "--tolerance" => {
+                tolerance = value.parse().unwrap_or_else(|_| exit_with_usage(&program))
+            }
+            _ => exit_with_usage(&program),
+        }
+    }
+    let path = path.unwrap_or_else(|| exit_with_usage(&program));
+
+    let content = fs::read_to_string(&path).expect("Unable to read the levels file");
+    let games: Vec<GameJson> = serde_json::from_str(&content).expect("Invalid levels file");
+
+    println!(
+        "Simulating {} runs per level of {} games in {}",
+        runs,
+        games.len(),
+        path
+    );
+    println!("game  level  win rate  avg clear  avg deaths  beasts squished");
+
+    let mut difficulties: Vec<Vec<LevelDifficulty>> = Vec::with_capacity(games.len());
+    for (game_idx, game) in games.iter().enumerate() {
+        let mut level = Some(Level::One);
+        let mut game_difficulties = Vec::with_capacity(game.levels.len());
+        for (level_idx, level_json) in game.levels.iter().enumerate() {
+            let Some(current) = level else {
+                break;
+            };
+            // every level is played on its own boards, the same seed gives the same boards again
+            let level_seed = seed
+                .wrapping_add(game_idx as u64 * 1000)
+                .wrapping_add(level_idx as u64);
+            let difficulty = estimate_difficulty(current, level_json, runs, level_seed);
+            println!(
+                "{:>4}  {:>5}  {:>7.1}%  {:>9}  {:>10.2}  {:>14.1}%",
+                game_idx,
+                current.number(),
+                difficulty.win_rate * 100.0,
+                difficulty
+                    .avg_clear_secs
+                    .map(|secs| format!("{:.1}s", secs))
+                    .unwrap_or_else(|| String::from("-")),
+                difficulty.avg_deaths,
+                difficulty.avg_kill_rate * 100.0,
+            );
+            game_difficulties.push(difficulty);
+            level = current.next();
+        }
+        difficulties.push(game_difficulties);
+    }
+
+    let outliers = find_outliers(&difficulties, tolerance);
+    if outliers.is_empty() {
+        println!(
+            "Every level is won within {:.0}% of the same level in the other games",
+            tolerance * 100.0
+        );
+        return;
+    }
+
+    eprintln!("These levels play very differently from the same level in the other games:");
+    for outlier in &outliers {
+        eprintln!(
+            "  game {} level {} is won {:.1}% of the time, the median of level {} is {:.1}%",
+            outlier.game,
+            outlier.level,
+            outlier.win_rate * 100.0,
+            outlier.level,
+            outlier.median_win_rate * 100.0,
+        );
+    }
+    std::process::exit(1);
+}
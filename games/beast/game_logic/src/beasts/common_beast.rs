@@ -1,6 +1,7 @@
 //! this module contains the common beast ├┤ logic
 
-use rand::seq::SliceRandom;
+#[cfg(feature = "rng")]
+use rand::{seq::SliceRandom, Rng};
 use serde::{Deserialize, Serialize};
 
 use crate::{
@@ -17,13 +18,70 @@ pub struct CommonBeast {
 
 impl CommonBeast {
     /// a function to shuffle the choices of where to go when two options are equal
-    fn shuffle_movements(mut coords: Vec<Coord>) -> Vec<Coord> {
+    #[cfg(feature = "rng")]
+    fn shuffle_movements(coords: Vec<Coord>) -> Vec<Coord> {
+        #[cfg(feature = "thread-rng")]
         let mut rng = rand::rng();
-        coords[1..3].shuffle(&mut rng);
-        coords[3..5].shuffle(&mut rng);
-        coords[5..7].shuffle(&mut rng);
+        // without a thread local rng (e.g. on wasm) we seed from a counter, the moves are logged anyway
+        #[cfg(not(feature = "thread-rng"))]
+        let mut rng = {
+            use rand::SeedableRng;
+            use std::sync::atomic::{AtomicU64, Ordering};
+
+            static SHUFFLE_COUNTER: AtomicU64 = AtomicU64::new(0);
+            rand::rngs::StdRng::seed_from_u64(SHUFFLE_COUNTER.fetch_add(1, Ordering::Relaxed))
+        };
+        Self::shuffle_movements_with_rng(coords, &mut rng)
+    }
+
+    /// shuffle the equal choices with the given rng, a seeded rng makes the beast move the same way again
+    #[cfg(feature = "rng")]
+    fn shuffle_movements_with_rng(mut coords: Vec<Coord>, rng: &mut impl Rng) -> Vec<Coord> {
+        coords[1..3].shuffle(rng);
+        coords[3..5].shuffle(rng);
+        coords[5..7].shuffle(rng);
+        coords
+    }
+
+    /// without an rng the equal choices keep their order
+    #[cfg(not(feature = "rng"))]
+    fn shuffle_movements(coords: Vec<Coord>) -> Vec<Coord> {
         coords
     }
+
+    /// advance like [Beast::advance] but break ties between equal moves with the given rng
+    #[cfg(feature = "rng")]
+    pub fn advance_with_rng(
+        &mut self,
+        board: &mut Board,
+        player_position: Coord,
+        rng: &mut impl Rng,
+    ) -> BeastAction {
+        let possible_moves = Self::shuffle_movements_with_rng(
+            Self::get_walkable_coords(board, &self.position, &player_position, false),
+            rng,
+        );
+        self.advance_through(board, player_position, possible_moves)
+    }
+
+    /// take the first of the possible moves that doesn't leave the beast where it is
+    fn advance_through(
+        &mut self,
+        board: &mut Board,
+        player_position: Coord,
+        possible_moves: Vec<Coord>,
+    ) -> BeastAction {
+        for coord in possible_moves {
+            // safe unwrap as these are possible moves
+            let action = self.advance_to(board, player_position, coord).unwrap();
+            // If the action is stayed, try with the next one
+            if action != BeastAction::Stayed {
+                return action;
+            }
+        }
+
+        BeastAction::Stayed
+    }
 }
 
 impl Beast for CommonBeast {
@@ -89,17 +147,7 @@ impl Beast for CommonBeast {
             &player_position,
             false,
         ));
-
-        for coord in possible_moves {
-            // safe unwrap as these are possible moves
-            let action = self.advance_to(board, player_position, coord).unwrap();
-            // If the action is stayed, try with the next one
-            if action != BeastAction::Stayed {
-                return action;
-            }
-        }
-
-        BeastAction::Stayed
+        self.advance_through(board, player_position, possible_moves)
     }
 
     /// this method is called when the common beast is killed to determine how much score the kill was worth
@@ -168,6 +216,41 @@ mod tests {
         );
     }
 
+    #[cfg(feature = "rng")]
+    #[test]
+    fn advance_with_rng_test() {
+        use rand::SeedableRng;
+        use rand_chacha::ChaCha8Rng;
+
+        // the player is straight above so the beast can go up left or up right just as well
+        let moves = |seed: u64| {
+            let mut rng = ChaCha8Rng::seed_from_u64(seed);
+            (0..20)
+                .map(|_| {
+                    let mut board = Board::new([[Tile::Empty; BOARD_WIDTH]; BOARD_HEIGHT]);
+                    let player_position = Coord { column: 5, row: 1 };
+                    board[&player_position] = Tile::Player;
+                    board[&Coord { column: 5, row: 4 }] = Tile::StaticBlock;
+                    let mut beast = CommonBeast::new(Coord { column: 5, row: 5 });
+                    board[&beast.position] = Tile::CommonBeast;
+                    beast.advance_with_rng(&mut board, player_position, &mut rng);
+                    beast.position
+                })
+                .collect::<Vec<Coord>>()
+        };
+
+        assert_eq!(
+            moves(7),
+            moves(7),
+            "The same seed moves the beast the same way"
+        );
+        assert!(
+            moves(7).iter().any(|coord| coord.column == 4)
+                && moves(7).iter().any(|coord| coord.column == 6),
+            "The beast still picks either of the equal moves"
+        );
+    }
+
     #[test]
     fn advance_above() {
         let mut board = Board::new([[Tile::Empty; BOARD_WIDTH]; BOARD_HEIGHT]);
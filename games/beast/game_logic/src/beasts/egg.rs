@@ -1,6 +1,6 @@
 //! this module contains the logic for eggs ○○ which ae later turned into hatched beasts
 
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use crate::{common::levels::LevelConfig, Coord};
 
@@ -47,6 +47,14 @@ impl Egg {
         }
     }
 
+    /// push the hatching time back, used when the game is paused so eggs don't hatch in the meantime
+    pub fn delay(&mut self, duration: Duration) {
+        self.instant += duration;
+        if let HatchingState::Hatching(position, _) = self.state {
+            self.state = HatchingState::Hatching(position, self.instant);
+        }
+    }
+
     /// killing an egg will give the player this score
     pub fn get_score() -> u16 {
         1
@@ -56,7 +64,6 @@ impl Egg {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::time::Duration;
 
     #[test]
     fn egg_creation_test() {
@@ -170,4 +177,40 @@ mod tests {
             "The egg should have hatched after 110% of the time has passed"
         );
     }
+
+    #[test]
+    fn egg_delay_test() {
+        let position = Coord { column: 5, row: 10 };
+        let past_time = Instant::now() - Duration::from_secs(80);
+        let mut egg = Egg::new(position, past_time);
+
+        let level = LevelConfig {
+            blocks: 10,
+            static_blocks: 5,
+            common_beasts: 3,
+            super_beasts: 1,
+            eggs: 4,
+            egg_hatching_time: Duration::from_secs(100),
+            beast_starting_distance: 5,
+            time: Duration::from_secs(300),
+            completion_score: 100,
+        };
+
+        assert_eq!(
+            egg.hatch(level.clone()),
+            HatchingState::Hatching(position, past_time),
+            "The egg is hatching before the pause"
+        );
+        egg.delay(Duration::from_secs(60));
+        assert_eq!(
+            egg.instant,
+            past_time + Duration::from_secs(60),
+            "The egg was delayed by the pause"
+        );
+        assert_eq!(
+            egg.hatch(level),
+            HatchingState::Incubating,
+            "The egg is back to incubating after the pause"
+        );
+    }
 }
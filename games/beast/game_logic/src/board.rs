@@ -1,16 +1,15 @@
-//! this module contains the board logic including terrain generation and rendering the board
+//! this module contains the board logic including terrain generation
 use std::ops::{Index, IndexMut};
 
-use rand::seq::SliceRandom;
-use std::fmt::Write;
-
+#[cfg(feature = "rng")]
+use crate::{beasts::Beast, common::levels::LevelConfig, PLAYER_START};
 use crate::{
-    beasts::{Beast, CommonBeast, Egg, HatchedBeast, SuperBeast},
-    common::levels::LevelConfig,
+    beasts::{CommonBeast, Egg, HatchedBeast, SuperBeast},
     player::Player,
-    Coord, Tile, ANSI_LEFT_BORDER, ANSI_RESET_BG, ANSI_RIGHT_BORDER, BOARD_HEIGHT, BOARD_WIDTH,
-    PLAYER_START,
+    Coord, Tile, BOARD_HEIGHT, BOARD_WIDTH,
 };
+#[cfg(feature = "rng")]
+use rand::{seq::SliceRandom, Rng};
 
 /// the board contains our internal representation of what we render on screen
 #[derive(Debug, Clone, Copy)]
@@ -85,7 +84,17 @@ impl Board {
     }
 
     /// generate the terrain of the board according to the level config we pass in
+    #[cfg(feature = "thread-rng")]
     pub fn generate_terrain(level_config: LevelConfig) -> BoardTerrainInfo {
+        Self::generate_terrain_with_rng(level_config, &mut rand::rng())
+    }
+
+    /// generate the terrain of the board with the given rng, use a seeded rng to get the same board again
+    #[cfg(feature = "rng")]
+    pub fn generate_terrain_with_rng(
+        level_config: LevelConfig,
+        rng: &mut impl Rng,
+    ) -> BoardTerrainInfo {
         let mut buffer = [[Tile::Empty; BOARD_WIDTH]; BOARD_HEIGHT];
 
         let mut common_beasts = Vec::with_capacity(level_config.common_beasts as usize);
@@ -102,8 +111,7 @@ impl Board {
         let total_entities = level_config.blocks as usize
             + level_config.static_blocks as usize
             + level_config.super_beasts as usize;
-        let mut rng = rand::rng();
-        all_positions.shuffle(&mut rng);
+        all_positions.shuffle(rng);
         let block_positions = all_positions
             .drain(0..total_entities)
             .collect::<Vec<Coord>>();
@@ -170,23 +178,4 @@ impl Board {
             player: Player::new(PLAYER_START),
         }
     }
-
-    /// render the board to the screen
-    pub fn render(&self) -> String {
-        let mut output = String::with_capacity(BOARD_WIDTH * BOARD_HEIGHT * 2 + BOARD_HEIGHT);
-
-        for row in self.buffer.iter() {
-            write!(output, "{ANSI_LEFT_BORDER}")
-                .unwrap_or_else(|_| panic!("Can't write to string buffer"));
-            for tile in row.iter() {
-                write!(output, "{tile}").unwrap_or_else(|_| panic!("Can't write to string buffer"));
-            }
-            writeln!(output, "{ANSI_RIGHT_BORDER}")
-                .unwrap_or_else(|_| panic!("Can't write to string buffer"));
-        }
-        write!(output, "{ANSI_RESET_BG}")
-            .unwrap_or_else(|_| panic!("Can't write to string buffer"));
-
-        output
-    }
 }
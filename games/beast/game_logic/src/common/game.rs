@@ -1,67 +1,220 @@
-use std::time::Duration;
+use std::fmt;
 
 use serde::{Deserialize, Serialize};
 
-use crate::common::levels::{Level, LevelConfig, LevelJson};
+use crate::common::levels::{Level, LevelConfig, LevelConfigError, LevelJson};
 
-#[derive(Serialize, Deserialize, Debug)]
+/// every game has one level for each [Level]
+pub const LEVELS_PER_GAME: usize = 3;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct GameJson {
     pub levels: Vec<LevelJson>,
     pub game_config: String,
     pub from_time: u64,
     pub to_time: u64,
+    /// the seed the levels were generated from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+    /// the generator and rng the seed has to be used with
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub generator: Option<String>,
+}
+
+/// why a game of a campaign can't be played
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameConfigError {
+    /// the game doesn't have one level for each [Level]
+    LevelCount(usize),
+    /// the game ends before it starts
+    EmptyWindow,
+    /// one of the levels is broken, `level` counts from 1
+    Level {
+        level: usize,
+        error: LevelConfigError,
+    },
+}
+
+impl fmt::Display for GameConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GameConfigError::LevelCount(count) => {
+                write!(f, "has {count} levels instead of {LEVELS_PER_GAME}")
+            }
+            GameConfigError::EmptyWindow => write!(f, "ends before it starts"),
+            GameConfigError::Level { level, error } => write!(f, "level {level}: {error}"),
+        }
+    }
+}
+
+impl GameJson {
+    /// check the window and every level of the game
+    pub fn validate(&self) -> Result<(), Vec<GameConfigError>> {
+        let mut errors = Vec::new();
+
+        if self.levels.len() != LEVELS_PER_GAME {
+            errors.push(GameConfigError::LevelCount(self.levels.len()));
+        }
+        if self.to_time <= self.from_time {
+            errors.push(GameConfigError::EmptyWindow);
+        }
+        for (idx, level) in self.levels.iter().enumerate() {
+            if let Err(level_errors) = level.validate() {
+                errors.extend(
+                    level_errors
+                        .into_iter()
+                        .map(|error| GameConfigError::Level {
+                            level: idx + 1,
+                            error,
+                        }),
+                );
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+/// pack the levels into the game config the leaderboard stores and the sp1 program commits, every level
+/// takes 4 bytes with its blocks, static blocks, common beasts and super beasts
+pub fn encode_game_config(levels: &[LevelJson]) -> [u8; 32] {
+    let mut config = [0u8; 32];
+    for (bytes, level) in config.chunks_exact_mut(4).zip(levels) {
+        bytes.copy_from_slice(&[
+            level.blocks,
+            level.static_blocks,
+            level.common_beasts,
+            level.super_beasts,
+        ]);
+    }
+    config
+}
+
+/// errors that can happen while selecting the game to play
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameLevelsError {
+    /// no game is active for the given block timestamp
+    NoActiveGame {
+        /// the `from_time` of the next scheduled game, if there is one
+        next_game_starts_at: Option<u64>,
+    },
+}
+
+/// when a game can be played and when its proofs can be claimed, in unix seconds
+///
+/// The `to_time` of a game includes a submission offset, so a game overlaps with the next one: once
+/// the next game starts the old one can't be picked anymore but its proofs are still accepted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GameWindow {
+    /// the index of the game in the campaign, the same as in the leaderboard contract
+    pub index: usize,
+    /// when the game starts
+    pub from_time: u64,
+    /// when the next game takes over
+    pub play_until: u64,
+    /// the `to_time` of the game, claims after it revert
+    pub submit_until: u64,
+}
+
+/// where a game window stands at a given time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowStatus {
+    /// the game is the current one and there is enough time to prove and claim
+    Open {
+        /// seconds until the next game takes over
+        secs_to_play: u64,
+        /// seconds until claims revert
+        secs_to_submit: u64,
+    },
+    /// the game is still the current one but a proof made now might not be claimed in time
+    Closing {
+        /// seconds until claims revert
+        secs_to_submit: u64,
+    },
+    /// the next game started, proofs of this game can still be claimed
+    RolledOver {
+        /// seconds until claims revert
+        secs_to_submit: u64,
+    },
+    /// claims for this game revert
+    Expired,
+}
+
+impl GameWindow {
+    /// the status at `now`, `submission_secs` is how long proving and claiming a proof takes
+    pub fn status(&self, now: u64, submission_secs: u64) -> WindowStatus {
+        if now >= self.submit_until {
+            return WindowStatus::Expired;
+        }
+
+        let secs_to_submit = self.submit_until - now;
+        if now >= self.play_until {
+            WindowStatus::RolledOver { secs_to_submit }
+        } else if secs_to_submit < submission_secs {
+            WindowStatus::Closing { secs_to_submit }
+        } else {
+            WindowStatus::Open {
+                secs_to_play: self.play_until - now,
+                secs_to_submit,
+            }
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
 pub struct GameLevels {
     levels: Vec<LevelConfig>,
+    window: Option<GameWindow>,
 }
 
 impl GameLevels {
-    pub fn new(block_timestamp: u64, games: Vec<GameJson>) -> GameLevels {
-        for game in games.into_iter().rev() {
+    pub fn new(block_timestamp: u64, games: Vec<GameJson>) -> Result<GameLevels, GameLevelsError> {
+        for (index, game) in games.iter().enumerate().rev() {
             if game.from_time <= block_timestamp && block_timestamp < game.to_time {
-                return Self::from_levels_json(&game.levels);
+                let play_until = games
+                    .get(index + 1)
+                    .map(|next| next.from_time.min(game.to_time))
+                    .unwrap_or(game.to_time);
+
+                return Ok(Self {
+                    window: Some(GameWindow {
+                        index,
+                        from_time: game.from_time,
+                        play_until,
+                        submit_until: game.to_time,
+                    }),
+                    ..Self::from_levels_json(&game.levels)
+                });
             }
         }
 
-        panic!("No game found to play yet");
+        let next_game_starts_at = games
+            .iter()
+            .map(|game| game.from_time)
+            .filter(|from_time| *from_time > block_timestamp)
+            .min();
+
+        Err(GameLevelsError::NoActiveGame {
+            next_game_starts_at,
+        })
     }
 
     pub fn from_levels_json(levels: &[LevelJson]) -> Self {
         Self {
-            levels: levels
-                .iter()
-                .map(|lvl| LevelConfig {
-                    blocks: lvl.blocks.try_into().expect("blocks out of range"),
-                    static_blocks: lvl
-                        .static_blocks
-                        .try_into()
-                        .expect("static_blocks out of range"),
-                    common_beasts: lvl
-                        .common_beasts
-                        .try_into()
-                        .expect("common_beasts out of range"),
-                    super_beasts: lvl
-                        .super_beasts
-                        .try_into()
-                        .expect("super_beasts out of range"),
-                    eggs: lvl.eggs.try_into().expect("eggs out of range"),
-                    egg_hatching_time: Duration::from_millis(lvl.egg_hatching_time),
-                    beast_starting_distance: lvl
-                        .beast_starting_distance
-                        .try_into()
-                        .expect("beast_starting_distance out of range"),
-                    time: Duration::from_secs(lvl.time),
-                    completion_score: lvl
-                        .completion_score
-                        .try_into()
-                        .expect("completion_score out of range"),
-                })
-                .collect(),
+            levels: levels.iter().map(LevelConfig::from).collect(),
+            window: None,
         }
     }
 
+    /// the window of the game, only known when it was picked from a campaign
+    pub fn window(&self) -> Option<GameWindow> {
+        self.window
+    }
+
     pub fn get_levels_in_json(&self) -> Vec<LevelJson> {
         self.levels
             .iter()
@@ -88,3 +241,153 @@ impl GameLevels {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn game(from_time: u64, to_time: u64) -> GameJson {
+        let level = LevelJson {
+            blocks: 10,
+            static_blocks: 5,
+            common_beasts: 3,
+            super_beasts: 1,
+            eggs: 0,
+            egg_hatching_time: 20000,
+            beast_starting_distance: 5,
+            time: 150,
+            completion_score: 5,
+        };
+
+        GameJson {
+            levels: vec![level.clone(), level.clone(), level],
+            game_config: String::new(),
+            from_time,
+            to_time,
+            seed: None,
+            generator: None,
+        }
+    }
+
+    #[test]
+    fn game_json_validate_test() {
+        assert_eq!(game(100, 200).validate(), Ok(()), "A regular game is valid");
+
+        let mut broken = game(200, 200);
+        broken.levels[1].time = 0;
+        broken.levels.pop();
+        assert_eq!(
+            broken.validate(),
+            Err(vec![
+                GameConfigError::LevelCount(2),
+                GameConfigError::EmptyWindow,
+                GameConfigError::Level {
+                    level: 2,
+                    error: LevelConfigError::NoTime,
+                },
+            ]),
+            "The window, the level count and every level are checked"
+        );
+    }
+
+    #[test]
+    fn encode_game_config_test() {
+        assert_eq!(
+            hex::encode(encode_game_config(&game(100, 200).levels)),
+            format!("{}{}", "0a050301".repeat(3), "00".repeat(20)),
+            "Every level takes 4 bytes and the rest is zero"
+        );
+    }
+
+    #[test]
+    fn game_levels_new_active_game_test() {
+        assert!(
+            GameLevels::new(150, vec![game(100, 200), game(200, 300)]).is_ok(),
+            "The game covering the timestamp is selected"
+        );
+    }
+
+    #[test]
+    fn game_levels_window_test() {
+        // the games overlap by a submission offset of 50
+        let games = vec![game(100, 250), game(200, 350)];
+
+        assert_eq!(
+            GameLevels::new(150, games.clone()).unwrap().window(),
+            Some(GameWindow {
+                index: 0,
+                from_time: 100,
+                play_until: 200,
+                submit_until: 250,
+            }),
+            "A game is played until the next one starts"
+        );
+        assert_eq!(
+            GameLevels::new(220, games.clone()).unwrap().window(),
+            Some(GameWindow {
+                index: 1,
+                from_time: 200,
+                play_until: 350,
+                submit_until: 350,
+            }),
+            "The newer game is picked once it started and the last game is played until its end"
+        );
+        assert_eq!(
+            GameLevels::from_levels_json(&games[0].levels).window(),
+            None,
+            "Levels without a campaign have no window"
+        );
+    }
+
+    #[test]
+    fn window_status_test() {
+        let window = GameWindow {
+            index: 0,
+            from_time: 100,
+            play_until: 200,
+            submit_until: 250,
+        };
+
+        assert_eq!(
+            window.status(120, 60),
+            WindowStatus::Open {
+                secs_to_play: 80,
+                secs_to_submit: 130,
+            },
+            "The game is open with enough time to claim"
+        );
+        assert_eq!(
+            window.status(195, 60),
+            WindowStatus::Closing { secs_to_submit: 55 },
+            "The game is closing when a proof can't be claimed in time"
+        );
+        assert_eq!(
+            window.status(200, 60),
+            WindowStatus::RolledOver { secs_to_submit: 50 },
+            "The game rolls over when the next one starts"
+        );
+        assert_eq!(
+            window.status(250, 60),
+            WindowStatus::Expired,
+            "Claims revert at the end of the game"
+        );
+    }
+
+    #[test]
+    fn game_levels_new_no_active_game_test() {
+        assert_eq!(
+            GameLevels::new(50, vec![game(100, 200), game(200, 300)]).unwrap_err(),
+            GameLevelsError::NoActiveGame {
+                next_game_starts_at: Some(100)
+            },
+            "The earliest upcoming game is reported"
+        );
+        assert_eq!(
+            GameLevels::new(400, vec![game(100, 200), game(200, 300)]).unwrap_err(),
+            GameLevelsError::NoActiveGame {
+                next_game_starts_at: None
+            },
+            "No upcoming game is reported once the campaign is over"
+        );
+    }
+}
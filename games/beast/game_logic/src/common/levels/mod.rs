@@ -2,6 +2,11 @@ use std::{fmt, time::Duration};
 
 use serde::{Deserialize, Serialize};
 
+use crate::{BOARD_HEIGHT, BOARD_WIDTH};
+
+/// the cells the terrain is generated on, every cell but the one the player starts on
+pub const FREE_CELLS: usize = BOARD_WIDTH * BOARD_HEIGHT - 1;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Level {
     /// level 1
@@ -9,7 +14,7 @@ pub enum Level {
     /// level 2
     Two,
     /// level 3
-    Three
+    Three,
 }
 
 impl Level {
@@ -29,6 +34,19 @@ impl Level {
             Self::Three => 3,
         }
     }
+
+    /// the score awarded when the level starts, reaching a level after the first is worth its completion score
+    pub fn start_score(&self, config: &LevelConfig) -> u16 {
+        match self {
+            Self::One => 0,
+            Self::Two | Self::Three => config.completion_score,
+        }
+    }
+
+    /// the score awarded for squishing the last beast of a level, a tenth of the seconds left
+    pub fn completed_score(secs_remaining: u64) -> u16 {
+        secs_remaining as u16 / 10
+    }
 }
 
 impl fmt::Display for Level {
@@ -76,3 +94,240 @@ pub struct LevelJson {
     pub time: u64,
     pub completion_score: u16,
 }
+
+/// why a level can't be generated or played
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LevelConfigError {
+    /// the blocks, static blocks and super beasts don't fit on the board
+    Capacity {
+        /// the cells the level needs
+        needed: usize,
+    },
+    /// the beasts can't be spread `distance` cells apart over the cells that are left
+    BeastPlacement {
+        /// the common and super beasts
+        beasts: usize,
+        /// the beast starting distance
+        distance: u8,
+        /// the cells left after the blocks were placed
+        free: usize,
+    },
+    /// super beasts can only be killed against static blocks
+    SuperBeastsWithoutStaticBlocks,
+    /// the level has no time to play
+    NoTime,
+}
+
+impl fmt::Display for LevelConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LevelConfigError::Capacity { needed } => write!(
+                f,
+                "the blocks, static blocks and super beasts need {needed} cells but the board only has {FREE_CELLS}"
+            ),
+            LevelConfigError::BeastPlacement {
+                beasts,
+                distance: 0,
+                ..
+            } => write!(
+                f,
+                "{beasts} beasts can't be placed with a beast starting distance of 0"
+            ),
+            LevelConfigError::BeastPlacement {
+                beasts,
+                distance,
+                free,
+            } => write!(
+                f,
+                "{beasts} beasts {distance} cells apart don't fit in the {free} cells left after the blocks"
+            ),
+            LevelConfigError::SuperBeastsWithoutStaticBlocks => write!(
+                f,
+                "super beasts can only be killed against static blocks but there are none"
+            ),
+            LevelConfigError::NoTime => write!(f, "the level has no time to play"),
+        }
+    }
+}
+
+impl LevelJson {
+    /// check that the terrain can be generated and the level can be completed, the checks follow
+    /// how [crate::board::Board::generate_terrain_with_rng] fills the board
+    pub fn validate(&self) -> Result<(), Vec<LevelConfigError>> {
+        let mut errors = Vec::new();
+
+        let needed =
+            self.blocks as usize + self.static_blocks as usize + self.super_beasts as usize;
+        let beasts = self.common_beasts as usize + self.super_beasts as usize;
+        if needed > FREE_CELLS {
+            errors.push(LevelConfigError::Capacity { needed });
+        } else if beasts > 0 {
+            // the beasts are placed on every `distance`th cell that is left
+            let free = FREE_CELLS - needed;
+            let distance = self.beast_starting_distance;
+            let last_cell = (beasts - 1) * distance as usize;
+            if (beasts > 1 && distance == 0) || last_cell >= free {
+                errors.push(LevelConfigError::BeastPlacement {
+                    beasts,
+                    distance,
+                    free,
+                });
+            }
+        }
+
+        if self.super_beasts > 0 && self.static_blocks == 0 {
+            errors.push(LevelConfigError::SuperBeastsWithoutStaticBlocks);
+        }
+
+        if self.time == 0 {
+            errors.push(LevelConfigError::NoTime);
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+impl From<&LevelJson> for LevelConfig {
+    fn from(lvl: &LevelJson) -> Self {
+        LevelConfig {
+            blocks: lvl.blocks,
+            static_blocks: lvl.static_blocks,
+            common_beasts: lvl.common_beasts,
+            super_beasts: lvl.super_beasts,
+            eggs: lvl.eggs,
+            egg_hatching_time: Duration::from_millis(lvl.egg_hatching_time),
+            beast_starting_distance: lvl.beast_starting_distance,
+            time: Duration::from_secs(lvl.time),
+            completion_score: lvl.completion_score,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "rng")]
+    use crate::board::Board;
+    #[cfg(feature = "rng")]
+    use rand::{rngs::StdRng, SeedableRng};
+
+    fn level() -> LevelJson {
+        LevelJson {
+            blocks: 200,
+            static_blocks: 10,
+            common_beasts: 5,
+            super_beasts: 0,
+            eggs: 0,
+            egg_hatching_time: 20000,
+            beast_starting_distance: 16,
+            time: 150,
+            completion_score: 5,
+        }
+    }
+
+    #[test]
+    fn validate_test() {
+        assert_eq!(level().validate(), Ok(()), "A regular level is valid");
+
+        assert_eq!(
+            LevelJson {
+                blocks: 255,
+                static_blocks: 255,
+                super_beasts: 255,
+                beast_starting_distance: 1,
+                ..level()
+            }
+            .validate(),
+            Ok(()),
+            "Full counts still fit on the board"
+        );
+
+        assert_eq!(
+            LevelJson {
+                common_beasts: 50,
+                beast_starting_distance: 255,
+                ..level()
+            }
+            .validate(),
+            Err(vec![LevelConfigError::BeastPlacement {
+                beasts: 50,
+                distance: 255,
+                free: FREE_CELLS - 210,
+            }]),
+            "Beasts that are spread too far apart are rejected"
+        );
+        assert_eq!(
+            LevelJson {
+                beast_starting_distance: 0,
+                ..level()
+            }
+            .validate(),
+            Err(vec![LevelConfigError::BeastPlacement {
+                beasts: 5,
+                distance: 0,
+                free: FREE_CELLS - 210,
+            }]),
+            "Beasts can't start on top of each other"
+        );
+        assert_eq!(
+            LevelJson {
+                common_beasts: 1,
+                beast_starting_distance: 0,
+                ..level()
+            }
+            .validate(),
+            Ok(()),
+            "A single beast doesn't need any distance"
+        );
+
+        assert_eq!(
+            LevelJson {
+                static_blocks: 0,
+                super_beasts: 1,
+                time: 0,
+                ..level()
+            }
+            .validate(),
+            Err(vec![
+                LevelConfigError::SuperBeastsWithoutStaticBlocks,
+                LevelConfigError::NoTime,
+            ]),
+            "Every problem is reported"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "rng")]
+    fn validate_matches_terrain_test() {
+        let mut rng = StdRng::seed_from_u64(7);
+        // the most beasts 30 cells apart that fit in the cells left after 210 blocks
+        let fits = (FREE_CELLS - 210 - 1) / 30 + 1;
+        let level = LevelJson {
+            common_beasts: fits as u8,
+            beast_starting_distance: 30,
+            ..level()
+        };
+
+        assert_eq!(level.validate(), Ok(()), "The largest placement is valid");
+        let terrain = Board::generate_terrain_with_rng(LevelConfig::from(&level), &mut rng);
+        assert_eq!(
+            terrain.common_beasts.len(),
+            fits,
+            "Every beast of a valid level is placed"
+        );
+
+        assert!(
+            LevelJson {
+                common_beasts: fits as u8 + 1,
+                ..level
+            }
+            .validate()
+            .is_err(),
+            "One more beast doesn't fit"
+        );
+    }
+}
@@ -0,0 +1,571 @@
+//! this module contains a front-end agnostic session for playing a single level
+//!
+//! The session is driven by ticks instead of wall clock time so it behaves the same in the terminal,
+//! in the browser and in simulations. Every move is recorded in a [LevelLog] that can be proven.
+
+use crate::{
+    beasts::{Beast, BeastAction, CommonBeast, HatchedBeast, SuperBeast},
+    board::{Board, BoardTerrainInfo},
+    common::levels::{Level, LevelConfig},
+    player::{Player, PlayerAction},
+    proving::{GameLogEntry, LevelLog},
+    render::Hud,
+    Coord, Dir, Tile,
+};
+#[cfg(feature = "rng")]
+use rand::SeedableRng;
+#[cfg(feature = "rng")]
+use rand_chacha::ChaCha8Rng;
+
+/// the time between game ticks
+pub const TICK_DURATION_MS: u64 = 200;
+/// beasts move once every this many ticks
+pub const BEAST_MOVE_TICKS: u64 = 5;
+
+/// where a level session stands
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionStatus {
+    /// the level is still being played
+    Playing,
+    /// all beasts have been squished
+    Completed,
+    /// the player ran out of lives
+    Died,
+    /// the level time ran out
+    TimeUp,
+}
+
+/// what happened during a single tick
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct StepOutcome {
+    /// the player squished a beast this tick
+    pub beast_killed: bool,
+    /// the player lost a life this tick
+    pub player_killed: bool,
+}
+
+/// an egg counts its age in ticks, eggs of the terrain are laid when the level starts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct SessionEgg {
+    position: Coord,
+    laid_at: u64,
+}
+
+/// a single level being played
+#[derive(Debug, Clone)]
+pub struct LevelSession {
+    level: Level,
+    config: LevelConfig,
+    board: Board,
+    player: Player,
+    common_beasts: Vec<CommonBeast>,
+    super_beasts: Vec<SuperBeast>,
+    eggs: Vec<SessionEgg>,
+    hatched_beasts: Vec<HatchedBeast>,
+    log: LevelLog,
+    ticks: u64,
+    beat: u64,
+    /// breaks ties between equally good beast moves
+    #[cfg(feature = "rng")]
+    rng: ChaCha8Rng,
+}
+
+impl LevelSession {
+    /// start a new session from a generated terrain
+    pub fn new(level: Level, config: LevelConfig, terrain: BoardTerrainInfo) -> Self {
+        let board = Board::new(terrain.buffer);
+        let mut player = terrain.player;
+        player.score += level.start_score(&config);
+
+        Self {
+            level,
+            config,
+            log: LevelLog {
+                level,
+                board: board.to_vec(),
+                game_log: vec![],
+            },
+            board,
+            player,
+            common_beasts: terrain.common_beasts,
+            super_beasts: terrain.super_beasts,
+            eggs: terrain
+                .eggs
+                .iter()
+                .map(|egg| SessionEgg {
+                    position: egg.position,
+                    laid_at: 0,
+                })
+                .collect(),
+            hatched_beasts: terrain.hatched_beasts,
+            ticks: 0,
+            beat: 0,
+            #[cfg(feature = "rng")]
+            rng: unseeded_rng(),
+        }
+    }
+
+    /// start a new session whose beasts break ties with an rng seeded from `seed`, the same seed and moves
+    /// play out the same
+    #[cfg(feature = "rng")]
+    pub fn with_seed(
+        level: Level,
+        config: LevelConfig,
+        terrain: BoardTerrainInfo,
+        seed: u64,
+    ) -> Self {
+        Self {
+            rng: ChaCha8Rng::seed_from_u64(seed),
+            ..Self::new(level, config, terrain)
+        }
+    }
+
+    /// the level being played
+    pub fn level(&self) -> Level {
+        self.level
+    }
+
+    /// the current board
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    /// the player
+    pub fn player(&self) -> &Player {
+        &self.player
+    }
+
+    /// how many beasts are left on the board
+    pub fn beasts_left(&self) -> usize {
+        self.common_beasts.len() + self.super_beasts.len() + self.hatched_beasts.len()
+    }
+
+    /// how many eggs are left on the board, they have to be squished as well to complete the level
+    pub fn eggs_left(&self) -> usize {
+        self.eggs.len()
+    }
+
+    /// how many ticks have been played
+    pub fn ticks(&self) -> u64 {
+        self.ticks
+    }
+
+    /// how many seconds are left to complete the level
+    pub fn secs_remaining(&self) -> u64 {
+        let elapsed_ms = self.ticks * TICK_DURATION_MS;
+        (self.config.time.as_millis() as u64).saturating_sub(elapsed_ms) / 1000
+    }
+
+    /// the log of everything that happened so far
+    pub fn log(&self) -> &LevelLog {
+        &self.log
+    }
+
+    /// the HUD values for renderers
+    pub fn hud(&self) -> Hud {
+        Hud {
+            level: self.level,
+            beasts: self.beasts_left(),
+            secs_remaining: self.secs_remaining(),
+            lives: self.player.lives,
+            score: self.player.score,
+            tick: self.ticks,
+        }
+    }
+
+    /// where the session stands
+    pub fn status(&self) -> SessionStatus {
+        if self.player.lives == 0 {
+            SessionStatus::Died
+        } else if self.beasts_left() + self.eggs_left() == 0 {
+            SessionStatus::Completed
+        } else if self.ticks * TICK_DURATION_MS >= self.config.time.as_millis() as u64 {
+            SessionStatus::TimeUp
+        } else {
+            SessionStatus::Playing
+        }
+    }
+
+    /// move the player in a direction
+    pub fn move_player(&mut self, dir: Dir) -> StepOutcome {
+        let mut outcome = StepOutcome::default();
+        if self.status() != SessionStatus::Playing {
+            return outcome;
+        }
+
+        let action = self.player.advance(&mut self.board, &dir);
+        self.log.game_log.push(GameLogEntry::PlayerMoved { dir });
+
+        match action {
+            PlayerAction::KillCommonBeast(coord) => {
+                outcome.beast_killed = true;
+                self.common_beasts.retain(|beast| beast.position != coord);
+            }
+            PlayerAction::KillSuperBeast(coord) => {
+                outcome.beast_killed = true;
+                self.super_beasts.retain(|beast| beast.position != coord);
+            }
+            PlayerAction::KillHatchedBeast(coord) => {
+                outcome.beast_killed = true;
+                self.hatched_beasts.retain(|beast| beast.position != coord);
+            }
+            PlayerAction::KillEgg(coord) => {
+                outcome.beast_killed = true;
+                self.eggs.retain(|egg| egg.position != coord);
+            }
+            PlayerAction::KillPlayer => {
+                outcome.player_killed = true;
+            }
+            PlayerAction::None => {}
+        }
+
+        // like the game, a completed level is worth a tenth of the seconds left, its completion score was
+        // awarded when it started
+        if outcome.beast_killed && self.status() == SessionStatus::Completed {
+            self.player.score += Level::completed_score(self.secs_remaining());
+        }
+
+        outcome
+    }
+
+    /// advance the clock by one tick with an optional player move, beasts move every few ticks
+    pub fn step(&mut self, input: Option<Dir>) -> StepOutcome {
+        let mut outcome = match input {
+            Some(dir) => self.move_player(dir),
+            None => StepOutcome::default(),
+        };
+
+        if self.status() != SessionStatus::Playing {
+            return outcome;
+        }
+
+        self.ticks += 1;
+        self.hatch_eggs();
+        self.beat += 1;
+        if self.beat == BEAST_MOVE_TICKS {
+            self.beat = 0;
+            outcome.player_killed |= self.advance_beasts();
+        }
+
+        outcome
+    }
+
+    /// move all beasts once, returns true if the player was killed
+    pub fn advance_beasts(&mut self) -> bool {
+        let mut player_killed = false;
+
+        for idx in 0..self.common_beasts.len() {
+            let old_pos = self.common_beasts[idx].position;
+            #[cfg(feature = "rng")]
+            let action = self.common_beasts[idx].advance_with_rng(
+                &mut self.board,
+                self.player.position,
+                &mut self.rng,
+            );
+            #[cfg(not(feature = "rng"))]
+            let action = self.common_beasts[idx].advance(&mut self.board, self.player.position);
+            self.log.game_log.push(GameLogEntry::CommonBeastMoved {
+                old_pos,
+                new_pos: self.common_beasts[idx].position,
+            });
+            player_killed |= self.handle_beast_action(action);
+        }
+
+        for idx in 0..self.super_beasts.len() {
+            let old_pos = self.super_beasts[idx].position;
+            let action = self.super_beasts[idx].advance(&mut self.board, self.player.position);
+            self.log.game_log.push(GameLogEntry::SuperBeastMoved {
+                old_pos,
+                new_pos: self.super_beasts[idx].position,
+            });
+            player_killed |= self.handle_beast_action(action);
+        }
+
+        for idx in 0..self.hatched_beasts.len() {
+            let action = self.hatched_beasts[idx].advance(&mut self.board, self.player.position);
+            self.log.game_log.push(GameLogEntry::HatchedBeastMoved {
+                idx,
+                new_pos: self.hatched_beasts[idx].position,
+            });
+            player_killed |= self.handle_beast_action(action);
+        }
+
+        player_killed
+    }
+
+    /// eggs start hatching at 80% of the hatching time and turn into hatched beasts once it is over
+    fn hatch_eggs(&mut self) {
+        let hatching_time = self.config.egg_hatching_time.as_millis() as u64;
+        let ticks = self.ticks;
+
+        self.eggs.retain(|egg| {
+            let age = (ticks - egg.laid_at) * TICK_DURATION_MS;
+            if age >= hatching_time {
+                self.hatched_beasts.push(HatchedBeast::new(egg.position));
+                self.board[&egg.position] = Tile::HatchedBeast;
+                false
+            } else {
+                if age >= hatching_time / 10 * 8 {
+                    self.board[&egg.position] = Tile::EggHatching;
+                }
+                true
+            }
+        });
+    }
+
+    fn handle_beast_action(&mut self, action: BeastAction) -> bool {
+        if action == BeastAction::PlayerKilled && self.player.lives > 0 {
+            self.player.lives -= 1;
+            self.player.respawn(&mut self.board);
+            return true;
+        }
+        false
+    }
+}
+
+/// the rng of a session without a seed
+#[cfg(feature = "thread-rng")]
+fn unseeded_rng() -> ChaCha8Rng {
+    ChaCha8Rng::from_rng(&mut rand::rng())
+}
+
+/// without a thread local rng (e.g. on wasm) we seed from a counter, the moves are logged anyway
+#[cfg(all(feature = "rng", not(feature = "thread-rng")))]
+fn unseeded_rng() -> ChaCha8Rng {
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    static SESSION_COUNTER: AtomicU64 = AtomicU64::new(0);
+    ChaCha8Rng::seed_from_u64(SESSION_COUNTER.fetch_add(1, Ordering::Relaxed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{beasts::Egg, BOARD_HEIGHT, BOARD_WIDTH, PLAYER_START};
+    use std::time::{Duration, Instant};
+
+    fn config() -> LevelConfig {
+        LevelConfig {
+            blocks: 0,
+            static_blocks: 0,
+            common_beasts: 1,
+            super_beasts: 0,
+            eggs: 0,
+            egg_hatching_time: Duration::from_secs(20),
+            beast_starting_distance: 5,
+            time: Duration::from_secs(1),
+            completion_score: 5,
+        }
+    }
+
+    fn terrain(beast: Coord) -> BoardTerrainInfo {
+        let mut buffer = [[Tile::Empty; BOARD_WIDTH]; BOARD_HEIGHT];
+        buffer[PLAYER_START.row][PLAYER_START.column] = Tile::Player;
+        buffer[beast.row][beast.column] = Tile::CommonBeast;
+
+        BoardTerrainInfo {
+            buffer,
+            common_beasts: vec![CommonBeast::new(beast)],
+            super_beasts: vec![],
+            eggs: vec![],
+            hatched_beasts: vec![],
+            player: Player::new(PLAYER_START),
+        }
+    }
+
+    #[test]
+    fn session_logs_moves_test() {
+        let mut session =
+            LevelSession::new(Level::One, config(), terrain(Coord { column: 40, row: 0 }));
+
+        session.step(Some(Dir::Right));
+        assert_eq!(
+            session.player().position,
+            Coord {
+                column: 1,
+                row: BOARD_HEIGHT - 1
+            },
+            "The player moved"
+        );
+        assert!(
+            matches!(
+                session.log().game_log[0],
+                GameLogEntry::PlayerMoved { dir: Dir::Right }
+            ),
+            "The move was logged"
+        );
+
+        for _ in 0..BEAST_MOVE_TICKS {
+            session.step(None);
+        }
+        assert!(
+            session
+                .log()
+                .game_log
+                .iter()
+                .any(|entry| matches!(entry, GameLogEntry::CommonBeastMoved { .. })),
+            "The beast moved after a few ticks"
+        );
+    }
+
+    #[test]
+    fn session_runs_out_of_time_test() {
+        let mut session =
+            LevelSession::new(Level::One, config(), terrain(Coord { column: 40, row: 0 }));
+
+        assert_eq!(
+            session.status(),
+            SessionStatus::Playing,
+            "The level starts playing"
+        );
+        for _ in 0..(1000 / TICK_DURATION_MS) {
+            session.step(None);
+        }
+        assert_eq!(session.secs_remaining(), 0, "No time is left");
+        assert_eq!(session.status(), SessionStatus::TimeUp, "The time ran out");
+    }
+
+    #[test]
+    fn session_completes_when_beasts_are_squished_test() {
+        // the beast sits in the corner so a single push against the frame squishes it
+        let mut buffer_terrain = terrain(Coord {
+            column: 2,
+            row: BOARD_HEIGHT - 1,
+        });
+        buffer_terrain.buffer[BOARD_HEIGHT - 1][1] = Tile::Block;
+        buffer_terrain.buffer[BOARD_HEIGHT - 1][3] = Tile::StaticBlock;
+        let mut session = LevelSession::new(Level::One, config(), buffer_terrain);
+
+        let outcome = session.step(Some(Dir::Right));
+        assert!(outcome.beast_killed, "The beast was squished");
+        assert_eq!(
+            session.status(),
+            SessionStatus::Completed,
+            "The level is completed"
+        );
+        assert_eq!(
+            session.player().score,
+            CommonBeast::get_score(),
+            "Only the beast counts, the first level has no start score and no whole ten seconds were left"
+        );
+    }
+
+    #[test]
+    fn session_scores_like_the_game_test() {
+        let scored = |level: Level| {
+            let mut buffer_terrain = terrain(Coord {
+                column: 2,
+                row: BOARD_HEIGHT - 1,
+            });
+            buffer_terrain.buffer[BOARD_HEIGHT - 1][1] = Tile::Block;
+            buffer_terrain.buffer[BOARD_HEIGHT - 1][3] = Tile::StaticBlock;
+            let long_config = LevelConfig {
+                time: Duration::from_secs(60),
+                ..config()
+            };
+            let mut session = LevelSession::new(level, long_config, buffer_terrain);
+            let started = session.player().score;
+            session.step(Some(Dir::Right));
+            (started, session.player().score, session.secs_remaining())
+        };
+
+        let (started, completed, secs_remaining) = scored(Level::One);
+        assert_eq!(started, 0, "The first level starts without a score");
+        assert_eq!(
+            completed,
+            CommonBeast::get_score() + secs_remaining as u16 / 10,
+            "A completed level is worth a tenth of the seconds left"
+        );
+
+        for level in [Level::Two, Level::Three] {
+            let (started, completed, secs_remaining) = scored(level);
+            assert_eq!(
+                started,
+                config().completion_score,
+                "The next levels start with their completion score like in the game"
+            );
+            assert_eq!(
+                completed,
+                config().completion_score + CommonBeast::get_score() + secs_remaining as u16 / 10,
+                "Completing the level doesn't award the completion score again"
+            );
+        }
+    }
+
+    #[test]
+    fn session_hatches_eggs_test() {
+        let egg = Coord {
+            column: 20,
+            row: 10,
+        };
+        let mut egg_terrain = terrain(Coord { column: 40, row: 0 });
+        egg_terrain.buffer[egg.row][egg.column] = Tile::Egg;
+        egg_terrain.eggs = vec![Egg::new(egg, Instant::now())];
+        let egg_config = LevelConfig {
+            // hatching after 3 ticks, hatched after 4 before the beasts move on the 5th
+            egg_hatching_time: Duration::from_millis(700),
+            time: Duration::from_secs(10),
+            ..config()
+        };
+        let mut session = LevelSession::new(Level::One, egg_config, egg_terrain);
+
+        assert_eq!(
+            session.eggs_left(),
+            1,
+            "The egg of the terrain is in the session"
+        );
+        for _ in 0..3 {
+            session.step(None);
+        }
+        assert_eq!(
+            session.board()[&egg],
+            Tile::EggHatching,
+            "The egg is about to hatch"
+        );
+
+        session.step(None);
+        assert_eq!(session.eggs_left(), 0, "The egg hatched");
+        assert_eq!(
+            session.beasts_left(),
+            2,
+            "The hatched beast is on the board"
+        );
+        assert_eq!(
+            session.board()[&egg],
+            Tile::HatchedBeast,
+            "The hatched beast took the place of the egg"
+        );
+    }
+
+    #[test]
+    fn session_needs_eggs_squished_to_complete_test() {
+        let egg = Coord {
+            column: 20,
+            row: 10,
+        };
+        let mut egg_terrain = terrain(Coord {
+            column: 2,
+            row: BOARD_HEIGHT - 1,
+        });
+        egg_terrain.buffer[BOARD_HEIGHT - 1][1] = Tile::Block;
+        egg_terrain.buffer[BOARD_HEIGHT - 1][3] = Tile::StaticBlock;
+        egg_terrain.buffer[egg.row][egg.column] = Tile::Egg;
+        egg_terrain.eggs = vec![Egg::new(egg, Instant::now())];
+        let mut session = LevelSession::new(Level::One, config(), egg_terrain);
+
+        assert!(
+            session.step(Some(Dir::Right)).beast_killed,
+            "The beast was squished"
+        );
+        assert_eq!(
+            session.status(),
+            SessionStatus::Playing,
+            "The level goes on while an egg is left"
+        );
+        assert_eq!(
+            session.player().score,
+            CommonBeast::get_score(),
+            "No completion score is awarded yet"
+        );
+    }
+}
@@ -1,9 +1,14 @@
 pub mod beasts;
 pub mod board;
 pub mod common;
+pub mod engine;
 pub mod pathing;
 pub mod player;
+pub mod render;
+#[cfg(feature = "rng")]
+pub mod simulation;
 mod tile;
 
+pub use render::ansi::*;
 pub use tile::*;
 pub mod proving;
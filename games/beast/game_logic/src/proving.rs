@@ -12,7 +12,7 @@ pub enum GameLogEntry {
     PlayerMoved { dir: Dir },
 }
 
-#[derive(Clone, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct LevelLog {
     /// The level to prove and check the board against
     pub level: Level,
@@ -0,0 +1,66 @@
+//! this module contains the ANSI terminal flavour of rendering, the colors, frame borders and logo
+
+use std::fmt::{self, Write};
+
+use crate::{board::Board, Tile, BOARD_HEIGHT, BOARD_WIDTH};
+
+/// ANSI escape sequence for bold
+pub const ANSI_BOLD: &str = "\x1B[1m";
+/// ANSI escape sequence to reset all styles and colors
+pub const ANSI_RESET: &str = "\x1B[0m";
+/// ANSI escape sequence to reset font color
+pub const ANSI_RESET_FONT: &str = "\x1B[39m";
+/// ANSI escape sequence to reset background color
+pub const ANSI_RESET_BG: &str = "\x1B[49m";
+/// left border with color ANSI escape sequence
+pub const ANSI_LEFT_BORDER: &str = "\x1b[33m▌\x1b[39m";
+/// right border with color ANSI escape sequence
+pub const ANSI_RIGHT_BORDER: &str = "\x1b[33m▐\x1b[39m";
+/// the logo
+pub const LOGO: [&str; 10] = [
+    "\x1b[33m▌\x1b[39m                                                                                                    \x1b[33m▐\x1b[39m",
+    "\x1b[33m▌\x1b[39m                                                                                                    \x1b[33m▐\x1b[39m",
+    "\x1b[33m▌\x1b[39m                               HHHH    HHHHH    HHH     HHHH   HHHHH                                \x1b[33m▐\x1b[39m",
+    "\x1b[33m▌\x1b[39m                               H   H   H       H   H   H         H                                  \x1b[33m▐\x1b[39m",
+    "\x1b[33m▌\x1b[39m                               H   H   H       H   H   H         H                                  \x1b[33m▐\x1b[39m",
+    "\x1b[33m▌\x1b[39m                               HHHH    HHHH    HHHHH    HHH      H                                  \x1b[33m▐\x1b[39m",
+    "\x1b[33m▌\x1b[39m                               H   H   H       H   H       H     H                                  \x1b[33m▐\x1b[39m",
+    "\x1b[33m▌\x1b[39m                               H   H   H       H   H       H     H                                  \x1b[33m▐\x1b[39m",
+    "\x1b[33m▌\x1b[39m                               HHHH    HHHHH   H   H   HHHH      H                                  \x1b[33m▐\x1b[39m",
+    "\x1b[33m▌\x1b[39m                                                                                                    \x1b[33m▐\x1b[39m",
+];
+
+impl fmt::Display for Tile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Tile::Empty => write!(f, "{}", self.raw_symbol()),
+            Tile::Block => write!(f, "\x1b[32m{}\x1b[39m", self.raw_symbol()),
+            Tile::StaticBlock => write!(f, "\x1b[33m{}\x1b[39m", self.raw_symbol()),
+            Tile::Player => write!(f, "\x1b[36m{}\x1b[39m", self.raw_symbol()),
+            Tile::CommonBeast => write!(f, "\x1b[31m{}\x1b[39m", self.raw_symbol()),
+            Tile::SuperBeast => write!(f, "\x1b[31m{}\x1b[39m", self.raw_symbol()),
+            Tile::Egg => write!(f, "\x1b[31m{}\x1b[39m", self.raw_symbol()),
+            Tile::EggHatching => write!(f, "\x1b[35m{}\x1b[39m", self.raw_symbol()),
+            Tile::HatchedBeast => write!(f, "\x1b[31m{}\x1b[39m", self.raw_symbol()),
+        }
+    }
+}
+
+/// render the board rows with their left and right frame borders
+pub fn render_board(board: &Board) -> String {
+    let mut output = String::with_capacity(BOARD_WIDTH * BOARD_HEIGHT * 2 + BOARD_HEIGHT);
+
+    for row in board.buffer.iter() {
+        write!(output, "{ANSI_LEFT_BORDER}")
+            .unwrap_or_else(|_| panic!("Can't write to string buffer"));
+        for tile in row.iter() {
+            write!(output, "{tile}").unwrap_or_else(|_| panic!("Can't write to string buffer"));
+        }
+        writeln!(output, "{ANSI_RIGHT_BORDER}")
+            .unwrap_or_else(|_| panic!("Can't write to string buffer"));
+    }
+    write!(output, "{ANSI_RESET_BG}")
+        .unwrap_or_else(|_| panic!("Can't write to string buffer"));
+
+    output
+}
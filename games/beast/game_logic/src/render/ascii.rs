@@ -0,0 +1,136 @@
+//! this module contains a plain ASCII renderer without any escape sequences
+//!
+//! Useful for logs, screen readers, terminals without unicode support and snapshot tests.
+
+use crate::{
+    render::{Flash, Frame, Renderer},
+    Tile, BOARD_WIDTH,
+};
+
+/// a renderer that only uses printable ASCII characters
+#[derive(Debug, Default, Clone, Copy)]
+pub struct AsciiRenderer;
+
+impl AsciiRenderer {
+    /// the two character symbol of a tile
+    pub fn symbol(tile: &Tile) -> &'static str {
+        match tile {
+            Tile::Empty => "  ",
+            Tile::Block => "[]",
+            Tile::StaticBlock => "##",
+            Tile::Player => "<>",
+            Tile::CommonBeast => "HH",
+            Tile::SuperBeast => "$$",
+            Tile::Egg => "oo",
+            Tile::EggHatching => "OO",
+            Tile::HatchedBeast => "%%",
+        }
+    }
+}
+
+impl Renderer for AsciiRenderer {
+    type Output = String;
+
+    fn render(&mut self, frame: &Frame) -> String {
+        let mut output = String::new();
+        let border = format!("+{}+\n", "-".repeat(BOARD_WIDTH * 2));
+
+        output.push_str(&border);
+        for row in frame.cells.iter() {
+            output.push('|');
+            for tile in row.iter() {
+                output.push_str(Self::symbol(tile));
+            }
+            output.push_str("|\n");
+        }
+        output.push_str(&border);
+
+        let hud = &frame.hud;
+        output.push_str(&format!(
+            "Beasts: {:>2}  Level: {:>2}  Time: {:02}:{:02}  Lives: {}  Score: {:>4}\n",
+            hud.beasts,
+            hud.level.number(),
+            hud.secs_remaining / 60,
+            hud.secs_remaining % 60,
+            hud.lives,
+            hud.score
+        ));
+
+        match frame.flash {
+            Flash::None => {}
+            Flash::PlayerHit(_) => output.push_str("!! YOU WERE HIT !!\n"),
+            Flash::BeastKilled => output.push_str("** BEAST SQUISHED **\n"),
+        }
+
+        if let Some(modal) = &frame.modal {
+            output.push_str(&format!("\n{}\n", modal.title));
+            for line in &modal.lines {
+                output.push_str(&format!("{line}\n"));
+            }
+        }
+
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        board::Board,
+        common::levels::Level,
+        render::{Hud, Modal},
+        BOARD_HEIGHT,
+    };
+
+    fn frame() -> Frame {
+        let mut board = Board::new([[Tile::Empty; BOARD_WIDTH]; BOARD_HEIGHT]);
+        board.buffer[0][0] = Tile::Player;
+        board.buffer[0][1] = Tile::CommonBeast;
+
+        Frame::new(
+            &board,
+            Hud {
+                level: Level::Two,
+                beasts: 1,
+                secs_remaining: 75,
+                lives: 3,
+                score: 42,
+                tick: 0,
+            },
+        )
+    }
+
+    #[test]
+    fn ascii_render_board_test() {
+        let output = AsciiRenderer.render(&frame());
+
+        assert!(output.is_ascii(), "The output only contains ASCII");
+        assert!(
+            output.lines().nth(1).unwrap().starts_with("|<>HH  "),
+            "The first row is rendered with ASCII symbols"
+        );
+        assert_eq!(
+            output.lines().count(),
+            BOARD_HEIGHT + 3,
+            "The output has a frame, the rows and the HUD"
+        );
+    }
+
+    #[test]
+    fn ascii_render_hud_and_modal_test() {
+        let output = AsciiRenderer.render(&frame().with_modal(Modal {
+            title: String::from("PAUSED"),
+            lines: vec![String::from("Press [P] to continue")],
+        }));
+
+        assert!(
+            output.contains("Level:  2  Time: 01:15  Lives: 3  Score:   42"),
+            "The HUD values are rendered"
+        );
+        assert!(
+            output.ends_with("\nPAUSED\nPress [P] to continue\n"),
+            "The modal is rendered below the board"
+        );
+    }
+}
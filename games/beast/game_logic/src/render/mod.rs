@@ -0,0 +1,97 @@
+//! this module contains the frame model that front-ends draw from
+//!
+//! The game fills a [Frame] with everything that is visible at a given moment and hands it to a
+//! [Renderer]. Renderers decide how that looks: the ANSI terminal, plain ASCII, a test renderer that
+//! asserts on the frame itself or a web front-end.
+
+pub mod ansi;
+pub mod ascii;
+
+use crate::{board::Board, common::levels::Level, Tile, BOARD_HEIGHT, BOARD_WIDTH};
+
+/// the values shown in the heads-up display next to the board
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hud {
+    /// the level that is being played
+    pub level: Level,
+    /// how many beasts are still alive
+    pub beasts: usize,
+    /// how many seconds are left to complete the level
+    pub secs_remaining: u64,
+    /// how many lives the player has left
+    pub lives: u8,
+    /// the current score
+    pub score: u16,
+    /// the game ticks since the level started, used for animations like a blinking timer
+    pub tick: u64,
+}
+
+/// a short flash over the whole board to give feedback on what just happened
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Flash {
+    /// nothing to highlight
+    #[default]
+    None,
+    /// the player was hit, the number counts up for every frame of the animation
+    PlayerHit(u8),
+    /// a beast was squished
+    BeastKilled,
+}
+
+/// a message box drawn on top of the board
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Modal {
+    /// the main message
+    pub title: String,
+    /// additional lines below the message
+    pub lines: Vec<String>,
+}
+
+/// everything that is visible at a given moment
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    /// the board cells
+    pub cells: [[Tile; BOARD_WIDTH]; BOARD_HEIGHT],
+    /// the heads-up display values
+    pub hud: Hud,
+    /// a flash over the board
+    pub flash: Flash,
+    /// an optional message drawn on top of the board
+    pub modal: Option<Modal>,
+}
+
+impl Frame {
+    /// create a frame from a board and the HUD values
+    pub fn new(board: &Board, hud: Hud) -> Self {
+        Self {
+            cells: board.buffer,
+            hud,
+            flash: Flash::None,
+            modal: None,
+        }
+    }
+
+    /// add a flash to the frame
+    pub fn with_flash(mut self, flash: Flash) -> Self {
+        self.flash = flash;
+        self
+    }
+
+    /// add a modal to the frame
+    pub fn with_modal(mut self, modal: Modal) -> Self {
+        self.modal = Some(modal);
+        self
+    }
+}
+
+/// a front-end that knows how to draw a [Frame]
+pub trait Renderer {
+    /// what the renderer produces, e.g. a string for the terminal
+    type Output;
+
+    /// draw a frame
+    fn render(&mut self, frame: &Frame) -> Self::Output;
+
+    /// forget any state about what was drawn before so the next frame is drawn from scratch
+    fn invalidate(&mut self) {}
+}
@@ -0,0 +1,545 @@
+//! this module contains a bot that plays levels through a [LevelSession] to estimate how hard they are
+//!
+//! Every run generates a board from a seeded rng and lets the bot play it until the level is completed,
+//! the player died or the time ran out. The bot squishes the closest beast it can reach a push for, keeps
+//! out of reach of the beasts and otherwise wanders around pushing blocks. The beasts break ties with an rng
+//! seeded from the same seed, so two simulations of the same level with the same seed give the same numbers.
+
+use std::collections::VecDeque;
+
+use rand::{seq::IndexedRandom, Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::{
+    board::Board,
+    common::levels::{Level, LevelConfig, LevelJson},
+    engine::{LevelSession, SessionStatus, TICK_DURATION_MS},
+    pathing::{get_end_of_block_chain, get_next_coord},
+    proving::GameLogEntry,
+    Coord, Dir, Tile, BOARD_HEIGHT, BOARD_WIDTH, PLAYER_START,
+};
+
+const DIRS: [Dir; 4] = [Dir::Up, Dir::Right, Dir::Down, Dir::Left];
+
+/// how a single run of the bot ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RunResult {
+    /// how the level ended
+    pub status: SessionStatus,
+    /// how many ticks were played
+    pub ticks: u64,
+    /// how many lives the player lost
+    pub deaths: u8,
+    /// how many beasts were squished
+    pub beasts_killed: usize,
+    /// how many beasts were on the board at the start
+    pub beasts: usize,
+}
+
+/// what the bot achieved on a level over many runs
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LevelDifficulty {
+    /// how many runs were played
+    pub runs: usize,
+    /// the share of runs that completed the level, from 0 to 1
+    pub win_rate: f64,
+    /// the average seconds a completed run took, `None` if no run completed the level
+    pub avg_clear_secs: Option<f64>,
+    /// the average lives lost per run
+    pub avg_deaths: f64,
+    /// the average share of the beasts squished per run, from 0 to 1
+    pub avg_kill_rate: f64,
+}
+
+/// a level that plays very differently from the same level in the other games of a campaign
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Outlier {
+    /// the index of the game in the campaign
+    pub game: usize,
+    /// the level in the game
+    pub level: Level,
+    /// the win rate of the level
+    pub win_rate: f64,
+    /// the median win rate of this level over all games
+    pub median_win_rate: f64,
+}
+
+fn opposite(dir: Dir) -> Dir {
+    match dir {
+        Dir::Up => Dir::Down,
+        Dir::Right => Dir::Left,
+        Dir::Down => Dir::Up,
+        Dir::Left => Dir::Right,
+    }
+}
+
+fn is_beast(tile: Tile) -> bool {
+    matches!(
+        tile,
+        Tile::CommonBeast | Tile::SuperBeast | Tile::HatchedBeast
+    )
+}
+
+/// a beast can reach every tile around it, diagonals included, the next time it moves
+fn is_dangerous(board: &Board, coord: &Coord) -> bool {
+    let rows = coord.row.saturating_sub(1)..=(coord.row + 1).min(BOARD_HEIGHT - 1);
+    rows.flat_map(|row| {
+        let columns = coord.column.saturating_sub(1)..=(coord.column + 1).min(BOARD_WIDTH - 1);
+        columns.map(move |column| Coord { column, row })
+    })
+    .any(|coord| is_beast(board[&coord]))
+}
+
+/// can a beast on `beast` be squished by a push in `dir`, the same rules as [crate::player::Player::advance]
+fn can_squish(board: &Board, beast: &Coord, dir: Dir) -> bool {
+    let behind = get_next_coord(beast, &dir).map(|coord| board[&coord]);
+    match board[beast] {
+        Tile::CommonBeast | Tile::HatchedBeast => {
+            matches!(behind, None | Some(Tile::Block) | Some(Tile::StaticBlock))
+        }
+        Tile::SuperBeast => behind == Some(Tile::StaticBlock),
+        _ => false,
+    }
+}
+
+/// does pushing from `from` in `dir` squish a beast
+fn push_kills(board: &Board, from: &Coord, dir: Dir) -> bool {
+    get_next_coord(from, &dir)
+        .filter(|next| board[next] == Tile::Block)
+        .and_then(|next| get_end_of_block_chain(board, &next, &dir))
+        .is_some_and(|(end, _)| can_squish(board, &end, dir))
+}
+
+/// every cell the player could push from to squish a beast, with the direction of the push
+fn kill_spots(board: &Board) -> Vec<(Coord, Dir)> {
+    let mut spots = Vec::new();
+
+    for row in 0..BOARD_HEIGHT {
+        for column in 0..BOARD_WIDTH {
+            let beast = Coord { column, row };
+            if !is_beast(board[&beast]) {
+                continue;
+            }
+
+            for dir in DIRS {
+                if !can_squish(board, &beast, dir) {
+                    continue;
+                }
+
+                // walk back over the blocks that would be pushed onto the beast
+                let back = opposite(dir);
+                let mut coord = get_next_coord(&beast, &back);
+                let mut blocks = 0;
+                while let Some(block) = coord.filter(|coord| board[coord] == Tile::Block) {
+                    blocks += 1;
+                    coord = get_next_coord(&block, &back);
+                }
+                if let Some(spot) = coord.filter(|_| blocks > 0) {
+                    if matches!(board[&spot], Tile::Empty | Tile::Player) {
+                        spots.push((spot, dir));
+                    }
+                }
+            }
+        }
+    }
+
+    spots
+}
+
+/// every tile a beast can reach the next time it moves
+fn danger_map(board: &Board) -> [[bool; BOARD_WIDTH]; BOARD_HEIGHT] {
+    let mut danger = [[false; BOARD_WIDTH]; BOARD_HEIGHT];
+    for (row, tiles) in danger.iter_mut().enumerate() {
+        for (column, tile) in tiles.iter_mut().enumerate() {
+            *tile = is_dangerous(board, &Coord { column, row });
+        }
+    }
+    danger
+}
+
+/// the first step on the shortest safe walk to one of the spots
+fn step_towards(board: &Board, start: &Coord, spots: &[(Coord, Dir)]) -> Option<Dir> {
+    if spots.is_empty() {
+        return None;
+    }
+
+    let mut is_spot = [[false; BOARD_WIDTH]; BOARD_HEIGHT];
+    for (spot, _) in spots {
+        is_spot[spot.row][spot.column] = true;
+    }
+    let danger = danger_map(board);
+    let mut first_step: [[Option<Dir>; BOARD_WIDTH]; BOARD_HEIGHT] =
+        [[None; BOARD_WIDTH]; BOARD_HEIGHT];
+    let mut visited = [[false; BOARD_WIDTH]; BOARD_HEIGHT];
+    let mut queue = VecDeque::from([*start]);
+    visited[start.row][start.column] = true;
+
+    while let Some(coord) = queue.pop_front() {
+        if coord != *start && is_spot[coord.row][coord.column] {
+            return first_step[coord.row][coord.column];
+        }
+
+        for dir in DIRS {
+            let Some(next) = get_next_coord(&coord, &dir) else {
+                continue;
+            };
+            if visited[next.row][next.column]
+                || board[&next] != Tile::Empty
+                || danger[next.row][next.column]
+            {
+                continue;
+            }
+
+            visited[next.row][next.column] = true;
+            first_step[next.row][next.column] = if coord == *start {
+                Some(dir)
+            } else {
+                first_step[coord.row][coord.column]
+            };
+            queue.push_back(next);
+        }
+    }
+
+    None
+}
+
+/// where the player ends up when moving in `dir`, if the move goes anywhere
+fn move_target(board: &Board, from: &Coord, dir: Dir) -> Option<Coord> {
+    let next = get_next_coord(from, &dir)?;
+    match board[&next] {
+        Tile::Empty => Some(next),
+        Tile::Block => get_end_of_block_chain(board, &next, &dir)
+            .filter(|(end, _)| board[end] == Tile::Empty)
+            .map(|_| next),
+        _ => None,
+    }
+}
+
+/// the next move of the bot, `None` to stay where it is
+pub fn next_move(session: &LevelSession, rng: &mut impl Rng) -> Option<Dir> {
+    let board = session.board();
+    let player = session.player().position;
+
+    if let Some(dir) = DIRS
+        .into_iter()
+        .find(|dir| push_kills(board, &player, *dir))
+    {
+        return Some(dir);
+    }
+
+    if let Some(dir) = step_towards(board, &player, &kill_spots(board)) {
+        return Some(dir);
+    }
+
+    // nothing to squish yet, so move the blocks around to build one without getting caught
+    let safe_moves = DIRS
+        .into_iter()
+        .filter(|dir| {
+            move_target(board, &player, *dir).is_some_and(|coord| !is_dangerous(board, &coord))
+        })
+        .collect::<Vec<Dir>>();
+    safe_moves.choose(rng).copied()
+}
+
+/// let the bot play a session until it ends
+pub fn play_session(mut session: LevelSession, rng: &mut impl Rng) -> RunResult {
+    let lives = session.player().lives;
+    let beasts = session.beasts_left();
+
+    let mut status = session.status();
+    while status == SessionStatus::Playing {
+        let input = next_move(&session, rng);
+        let logged = session.log().game_log.len();
+        let outcome = session.step(input);
+        status = session.status();
+
+        // the player respawns on top of a beast that sits on the start, the game can't go on from
+        // there so the run counts as lost
+        if outcome.player_killed && beast_on_start(&session.log().game_log[logged..]) {
+            status = SessionStatus::Died;
+        }
+    }
+
+    RunResult {
+        status,
+        ticks: session.ticks(),
+        deaths: lives - session.player().lives,
+        beasts_killed: beasts - session.beasts_left(),
+        beasts,
+    }
+}
+
+/// generate a board for the level and let the bot play it
+pub fn play_level(level: Level, config: LevelConfig, rng: &mut impl Rng) -> RunResult {
+    let terrain = Board::generate_terrain_with_rng(config, rng);
+    let seed = rng.random();
+    play_session(LevelSession::with_seed(level, config, terrain, seed), rng)
+}
+
+/// play the level `runs` times on boards generated from `seed` and sum up how the bot did
+pub fn estimate_difficulty(
+    level: Level,
+    config: &LevelJson,
+    runs: usize,
+    seed: u64,
+) -> LevelDifficulty {
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    let results = (0..runs)
+        .map(|_| play_level(level, LevelConfig::from(config), &mut rng))
+        .collect::<Vec<RunResult>>();
+
+    let average = |values: Vec<f64>| -> Option<f64> {
+        if values.is_empty() {
+            None
+        } else {
+            Some(values.iter().sum::<f64>() / values.len() as f64)
+        }
+    };
+    let clear_secs = results
+        .iter()
+        .filter(|result| result.status == SessionStatus::Completed)
+        .map(|result| (result.ticks * TICK_DURATION_MS) as f64 / 1000.0)
+        .collect::<Vec<f64>>();
+
+    LevelDifficulty {
+        runs,
+        win_rate: clear_secs.len() as f64 / runs.max(1) as f64,
+        avg_clear_secs: average(clear_secs),
+        avg_deaths: average(results.iter().map(|result| result.deaths as f64).collect())
+            .unwrap_or_default(),
+        avg_kill_rate: average(
+            results
+                .iter()
+                .map(|result| result.beasts_killed as f64 / result.beasts.max(1) as f64)
+                .collect(),
+        )
+        .unwrap_or_default(),
+    }
+}
+
+/// the levels whose win rate is further than `tolerance` from the median of the same level over all games
+pub fn find_outliers(games: &[Vec<LevelDifficulty>], tolerance: f64) -> Vec<Outlier> {
+    let mut outliers = Vec::new();
+    let mut level = Some(Level::One);
+    let mut idx = 0;
+
+    while let Some(current) = level {
+        let mut win_rates = games
+            .iter()
+            .filter_map(|game| game.get(idx).map(|difficulty| difficulty.win_rate))
+            .collect::<Vec<f64>>();
+        if win_rates.is_empty() {
+            break;
+        }
+        win_rates.sort_by(f64::total_cmp);
+        let middle = win_rates.len() / 2;
+        let median_win_rate = if win_rates.len() % 2 == 0 {
+            (win_rates[middle - 1] + win_rates[middle]) / 2.0
+        } else {
+            win_rates[middle]
+        };
+
+        for (game, difficulties) in games.iter().enumerate() {
+            if let Some(difficulty) = difficulties.get(idx) {
+                if (difficulty.win_rate - median_win_rate).abs() > tolerance {
+                    outliers.push(Outlier {
+                        game,
+                        level: current,
+                        win_rate: difficulty.win_rate,
+                        median_win_rate,
+                    });
+                }
+            }
+        }
+
+        level = current.next();
+        idx += 1;
+    }
+
+    outliers
+}
+
+/// every beast logs its position when it moves, so one beast move round is enough to tell
+fn beast_on_start(entries: &[GameLogEntry]) -> bool {
+    entries.iter().any(|entry| match entry {
+        GameLogEntry::CommonBeastMoved { new_pos, .. }
+        | GameLogEntry::SuperBeastMoved { new_pos, .. }
+        | GameLogEntry::HatchedBeastMoved { new_pos, .. } => *new_pos == PLAYER_START,
+        GameLogEntry::PlayerMoved { .. } => false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        beasts::Beast, beasts::CommonBeast, board::BoardTerrainInfo, player::Player, PLAYER_START,
+    };
+    use std::time::Duration;
+
+    fn level() -> LevelJson {
+        LevelJson {
+            blocks: 200,
+            static_blocks: 10,
+            common_beasts: 3,
+            super_beasts: 0,
+            eggs: 0,
+            egg_hatching_time: 20000,
+            beast_starting_distance: 16,
+            time: 30,
+            completion_score: 5,
+        }
+    }
+
+    fn difficulty(win_rate: f64) -> LevelDifficulty {
+        LevelDifficulty {
+            runs: 10,
+            win_rate,
+            avg_clear_secs: None,
+            avg_deaths: 0.0,
+            avg_kill_rate: 0.0,
+        }
+    }
+
+    #[test]
+    fn next_move_squishes_test() {
+        // ◀▶░░├┤  ├┤ sits two cells from the static block, the bot walks up to the push
+        let mut buffer = [[Tile::Empty; BOARD_WIDTH]; BOARD_HEIGHT];
+        let row = BOARD_HEIGHT - 1;
+        buffer[row][0] = Tile::Player;
+        buffer[row][3] = Tile::Block;
+        buffer[row][4] = Tile::CommonBeast;
+        buffer[row][5] = Tile::StaticBlock;
+        let terrain = BoardTerrainInfo {
+            buffer,
+            common_beasts: vec![CommonBeast::new(Coord { column: 4, row })],
+            super_beasts: vec![],
+            eggs: vec![],
+            hatched_beasts: vec![],
+            player: Player::new(PLAYER_START),
+        };
+        let config = LevelConfig {
+            time: Duration::from_secs(10),
+            ..LevelConfig::from(&level())
+        };
+        let mut session = LevelSession::with_seed(Level::One, config, terrain, 1);
+        let mut rng = ChaCha8Rng::seed_from_u64(1);
+
+        assert_eq!(
+            next_move(&session, &mut rng),
+            Some(Dir::Right),
+            "The bot walks towards the push"
+        );
+        session.step(Some(Dir::Right));
+        session.step(Some(Dir::Right));
+        assert_eq!(
+            next_move(&session, &mut rng),
+            Some(Dir::Right),
+            "The bot pushes the block onto the beast"
+        );
+
+        let result = play_session(session, &mut rng);
+        assert_eq!(
+            result.status,
+            SessionStatus::Completed,
+            "The level is completed"
+        );
+        assert_eq!(result.beasts_killed, 1, "The beast was squished");
+    }
+
+    #[test]
+    fn play_session_beast_on_start_test() {
+        // ██
+        // ◀▶├┤  the player is boxed in on the start and the beast walks onto it
+        let mut buffer = [[Tile::Empty; BOARD_WIDTH]; BOARD_HEIGHT];
+        let row = PLAYER_START.row;
+        buffer[row][PLAYER_START.column] = Tile::Player;
+        buffer[row][PLAYER_START.column + 1] = Tile::CommonBeast;
+        buffer[row - 1][PLAYER_START.column] = Tile::StaticBlock;
+        let terrain = BoardTerrainInfo {
+            buffer,
+            common_beasts: vec![CommonBeast::new(Coord {
+                column: PLAYER_START.column + 1,
+                row,
+            })],
+            super_beasts: vec![],
+            eggs: vec![],
+            hatched_beasts: vec![],
+            player: Player::new(PLAYER_START),
+        };
+        let session = LevelSession::with_seed(Level::One, LevelConfig::from(&level()), terrain, 1);
+        let mut rng = ChaCha8Rng::seed_from_u64(1);
+
+        let result = play_session(session, &mut rng);
+        assert_eq!(
+            result.status,
+            SessionStatus::Died,
+            "The run is lost once the player respawns on the beast"
+        );
+        assert_eq!(result.deaths, 1, "The player was killed once");
+    }
+
+    #[test]
+    fn estimate_difficulty_test() {
+        let difficulty = estimate_difficulty(Level::One, &level(), 4, 7);
+        assert_eq!(difficulty.runs, 4, "Every run is counted");
+        assert!(
+            (0.0..=1.0).contains(&difficulty.win_rate),
+            "The win rate is a share"
+        );
+        assert!(
+            (0.0..=1.0).contains(&difficulty.avg_kill_rate),
+            "The kill rate is a share"
+        );
+        assert_eq!(
+            estimate_difficulty(Level::One, &level(), 20, 7),
+            estimate_difficulty(Level::One, &level(), 20, 7),
+            "The same seed plays the same runs, beasts included"
+        );
+
+        // super beasts can't be squished without static blocks
+        let impossible = LevelJson {
+            static_blocks: 0,
+            common_beasts: 0,
+            super_beasts: 1,
+            time: 5,
+            ..level()
+        };
+        let difficulty = estimate_difficulty(Level::One, &impossible, 3, 7);
+        assert_eq!(difficulty.win_rate, 0.0, "An impossible level is never won");
+        assert_eq!(difficulty.avg_clear_secs, None, "No run cleared the level");
+    }
+
+    #[test]
+    fn find_outliers_test() {
+        let games = vec![
+            vec![difficulty(0.75), difficulty(0.5)],
+            vec![difficulty(0.875), difficulty(0.625)],
+            vec![difficulty(0.75), difficulty(0.125)],
+            vec![difficulty(0.25), difficulty(0.5)],
+        ];
+
+        assert_eq!(
+            find_outliers(&games, 0.25),
+            vec![
+                Outlier {
+                    game: 3,
+                    level: Level::One,
+                    win_rate: 0.25,
+                    median_win_rate: 0.75,
+                },
+                Outlier {
+                    game: 2,
+                    level: Level::Two,
+                    win_rate: 0.125,
+                    median_win_rate: 0.5,
+                },
+            ],
+            "Levels far from the median of their level are reported"
+        );
+        assert!(
+            find_outliers(&games, 1.0).is_empty(),
+            "Nothing is an outlier with the largest tolerance"
+        );
+    }
+}
@@ -1,5 +1,3 @@
-use std::fmt;
-
 use serde::{Deserialize, Serialize};
 
 /// the board width
@@ -11,31 +9,6 @@ pub const PLAYER_START: Coord = Coord {
     column: 0,
     row: BOARD_HEIGHT - 1,
 };
-/// ANSI escape sequence for bold
-pub const ANSI_BOLD: &str = "\x1B[1m";
-/// ANSI escape sequence to reset all styles and colors
-pub const ANSI_RESET: &str = "\x1B[0m";
-/// ANSI escape sequence to reset font color
-pub const ANSI_RESET_FONT: &str = "\x1B[39m";
-/// ANSI escape sequence to reset background color
-pub const ANSI_RESET_BG: &str = "\x1B[49m";
-/// left border with color ANSI escape sequence
-pub const ANSI_LEFT_BORDER: &str = "\x1b[33m▌\x1b[39m";
-/// right border with color ANSI escape sequence
-pub const ANSI_RIGHT_BORDER: &str = "\x1b[33m▐\x1b[39m";
-/// the logo
-pub const LOGO: [&str; 10] = [
-    "\x1b[33m▌\x1b[39m                                                                                                    \x1b[33m▐\x1b[39m",
-    "\x1b[33m▌\x1b[39m                                                                                                    \x1b[33m▐\x1b[39m",
-    "\x1b[33m▌\x1b[39m                               HHHH    HHHHH    HHH     HHHH   HHHHH                                \x1b[33m▐\x1b[39m",
-    "\x1b[33m▌\x1b[39m                               H   H   H       H   H   H         H                                  \x1b[33m▐\x1b[39m",
-    "\x1b[33m▌\x1b[39m                               H   H   H       H   H   H         H                                  \x1b[33m▐\x1b[39m",
-    "\x1b[33m▌\x1b[39m                               HHHH    HHHH    HHHHH    HHH      H                                  \x1b[33m▐\x1b[39m",
-    "\x1b[33m▌\x1b[39m                               H   H   H       H   H       H     H                                  \x1b[33m▐\x1b[39m",
-    "\x1b[33m▌\x1b[39m                               H   H   H       H   H       H     H                                  \x1b[33m▐\x1b[39m",
-    "\x1b[33m▌\x1b[39m                               HHHH    HHHHH   H   H   HHHH      H                                  \x1b[33m▐\x1b[39m",
-    "\x1b[33m▌\x1b[39m                                                                                                    \x1b[33m▐\x1b[39m",
-];
 
 /// a data structure to place items on a board
 #[derive(
@@ -89,22 +62,6 @@ impl Tile {
     }
 }
 
-impl fmt::Display for Tile {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match self {
-            Tile::Empty => write!(f, "{}", self.raw_symbol()),
-            Tile::Block => write!(f, "\x1b[32m{}\x1b[39m", self.raw_symbol()),
-            Tile::StaticBlock => write!(f, "\x1b[33m{}\x1b[39m", self.raw_symbol()),
-            Tile::Player => write!(f, "\x1b[36m{}\x1b[39m", self.raw_symbol()),
-            Tile::CommonBeast => write!(f, "\x1b[31m{}\x1b[39m", self.raw_symbol()),
-            Tile::SuperBeast => write!(f, "\x1b[31m{}\x1b[39m", self.raw_symbol()),
-            Tile::Egg => write!(f, "\x1b[31m{}\x1b[39m", self.raw_symbol()),
-            Tile::EggHatching => write!(f, "\x1b[35m{}\x1b[39m", self.raw_symbol()),
-            Tile::HatchedBeast => write!(f, "\x1b[31m{}\x1b[39m", self.raw_symbol()),
-        }
-    }
-}
-
 /// the allowed directions an entity can move
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Dir {
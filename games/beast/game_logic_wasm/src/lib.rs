@@ -0,0 +1,296 @@
+//! WebAssembly bindings for playing a single level of BEAST in the browser
+//!
+//! ```js
+//! import init, { BeastLevel } from "./pkg/game_logic_wasm.js";
+//!
+//! await init();
+//! const level = new BeastLevel(JSON.stringify(levelJson), 1, BigInt(Date.now()));
+//! setInterval(() => {
+//!     level.step(pressedKey); // "up" | "down" | "left" | "right" | undefined
+//!     draw(level.board(), BeastLevel.width(), BeastLevel.height());
+//! }, 200);
+//! download(level.level_log()); // replay to prove locally
+//! ```
+
+use game_logic::{
+    board::Board,
+    common::levels::{Level, LevelConfig, LevelJson},
+    engine::{LevelSession, SessionStatus},
+    Dir, Tile, BOARD_HEIGHT, BOARD_WIDTH,
+};
+use rand::{rngs::StdRng, SeedableRng};
+use wasm_bindgen::prelude::*;
+
+/// a single level session exposed to JavaScript
+#[wasm_bindgen]
+pub struct BeastLevel {
+    session: LevelSession,
+}
+
+#[wasm_bindgen]
+impl BeastLevel {
+    /// create a level from a `LevelJson` string, the level number (1-3) and a seed for the board and the beasts
+    #[wasm_bindgen(constructor)]
+    pub fn new(level_json: &str, level_number: u16, seed: u64) -> Result<BeastLevel, JsError> {
+        Self::from_level_json(level_json, level_number, seed).map_err(|error| JsError::new(&error))
+    }
+
+    /// the board width in tiles
+    pub fn width() -> usize {
+        BOARD_WIDTH
+    }
+
+    /// the board height in tiles
+    pub fn height() -> usize {
+        BOARD_HEIGHT
+    }
+
+    /// advance one tick with an optional move ("up", "down", "left" or "right")
+    pub fn step(&mut self, input: Option<String>) -> Result<(), JsError> {
+        self.step_input(input.as_deref())
+            .map_err(|error| JsError::new(&error))
+    }
+
+    /// the board as row-major tile codes, see `tile_code` for the mapping
+    pub fn board(&self) -> Vec<u8> {
+        self.session
+            .board()
+            .buffer
+            .iter()
+            .flat_map(|row| row.iter().map(tile_code))
+            .collect()
+    }
+
+    /// "playing", "completed", "died" or "time_up"
+    pub fn status(&self) -> String {
+        match self.session.status() {
+            SessionStatus::Playing => "playing",
+            SessionStatus::Completed => "completed",
+            SessionStatus::Died => "died",
+            SessionStatus::TimeUp => "time_up",
+        }
+        .to_string()
+    }
+
+    /// how many lives the player has left
+    pub fn lives(&self) -> u8 {
+        self.session.player().lives
+    }
+
+    /// the current score
+    pub fn score(&self) -> u16 {
+        self.session.player().score
+    }
+
+    /// how many beasts are left
+    pub fn beasts_left(&self) -> usize {
+        self.session.beasts_left()
+    }
+
+    /// how many seconds are left to complete the level
+    pub fn secs_remaining(&self) -> u64 {
+        self.session.secs_remaining()
+    }
+
+    /// the `LevelLog` as JSON so it can be proven with the native prover
+    pub fn level_log(&self) -> Result<String, JsError> {
+        Ok(serde_json::to_string(self.session.log())?)
+    }
+}
+
+// the fallible parts return plain errors, a `JsError` can only be created on wasm
+impl BeastLevel {
+    fn from_level_json(level_json: &str, level_number: u16, seed: u64) -> Result<Self, String> {
+        let level_json: LevelJson =
+            serde_json::from_str(level_json).map_err(|error| error.to_string())?;
+        let level = parse_level(level_number)?;
+        let config = LevelConfig::from(&level_json);
+        let terrain = Board::generate_terrain_with_rng(config, &mut StdRng::seed_from_u64(seed));
+
+        Ok(Self {
+            session: LevelSession::with_seed(level, config, terrain, seed),
+        })
+    }
+
+    fn step_input(&mut self, input: Option<&str>) -> Result<(), String> {
+        let dir = input.map(parse_dir).transpose()?;
+        self.session.step(dir);
+        Ok(())
+    }
+}
+
+/// map a tile to the code used in `BeastLevel::board`
+pub fn tile_code(tile: &Tile) -> u8 {
+    match tile {
+        Tile::Empty => 0,
+        Tile::Block => 1,
+        Tile::StaticBlock => 2,
+        Tile::Player => 3,
+        Tile::CommonBeast => 4,
+        Tile::SuperBeast => 5,
+        Tile::Egg => 6,
+        Tile::EggHatching => 7,
+        Tile::HatchedBeast => 8,
+    }
+}
+
+fn parse_dir(input: &str) -> Result<Dir, String> {
+    match input {
+        "up" => Ok(Dir::Up),
+        "down" => Ok(Dir::Down),
+        "left" => Ok(Dir::Left),
+        "right" => Ok(Dir::Right),
+        _ => Err(format!("unknown direction '{input}'")),
+    }
+}
+
+fn parse_level(level_number: u16) -> Result<Level, String> {
+    match level_number {
+        1 => Ok(Level::One),
+        2 => Ok(Level::Two),
+        3 => Ok(Level::Three),
+        _ => Err(format!("unknown level {level_number}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use game_logic::{proving::LevelLog, PLAYER_START};
+
+    const LEVEL_JSON: &str = r#"{
+        "blocks": 200,
+        "static_blocks": 10,
+        "common_beasts": 3,
+        "super_beasts": 0,
+        "eggs": 0,
+        "egg_hatching_time": 20000,
+        "beast_starting_distance": 16,
+        "time": 120,
+        "completion_score": 5
+    }"#;
+
+    fn beast_level() -> BeastLevel {
+        BeastLevel::from_level_json(LEVEL_JSON, 1, 7).expect("The level is valid")
+    }
+
+    #[test]
+    fn new_test() {
+        assert!(
+            BeastLevel::from_level_json(LEVEL_JSON, 3, 7).is_ok(),
+            "A valid level is created"
+        );
+        assert_eq!(
+            BeastLevel::from_level_json(LEVEL_JSON, 4, 7).err(),
+            Some(String::from("unknown level 4")),
+            "A level number past 3 is rejected"
+        );
+        assert!(
+            BeastLevel::from_level_json(LEVEL_JSON, 0, 7).is_err(),
+            "Levels count from 1"
+        );
+        assert!(
+            BeastLevel::from_level_json("{\"blocks\": 200}", 1, 7).is_err(),
+            "An incomplete level is rejected"
+        );
+        assert!(
+            BeastLevel::from_level_json("not json", 1, 7).is_err(),
+            "Invalid JSON is rejected"
+        );
+    }
+
+    #[test]
+    fn step_test() {
+        let mut level = beast_level();
+
+        assert_eq!(
+            level.step_input(Some("jump")),
+            Err(String::from("unknown direction 'jump'")),
+            "An unknown direction is rejected"
+        );
+        assert!(
+            level.session.log().game_log.is_empty(),
+            "A rejected direction isn't played"
+        );
+        assert_eq!(
+            level.step_input(None),
+            Ok(()),
+            "A tick without a move is played"
+        );
+        assert_eq!(
+            level.step_input(Some("right")),
+            Ok(()),
+            "A known direction is played"
+        );
+    }
+
+    #[test]
+    fn board_test() {
+        let level = beast_level();
+        let board = level.board();
+
+        assert_eq!(
+            board.len(),
+            BeastLevel::width() * BeastLevel::height(),
+            "Every tile is in the board"
+        );
+        assert_eq!(
+            board[PLAYER_START.row * BeastLevel::width() + PLAYER_START.column],
+            tile_code(&Tile::Player),
+            "The board is row-major"
+        );
+        assert_eq!(
+            board,
+            level
+                .session
+                .board()
+                .buffer
+                .iter()
+                .flatten()
+                .map(tile_code)
+                .collect::<Vec<u8>>(),
+            "Every tile uses its tile code"
+        );
+        assert_eq!(
+            [
+                Tile::Empty,
+                Tile::Block,
+                Tile::StaticBlock,
+                Tile::Player,
+                Tile::CommonBeast,
+                Tile::SuperBeast,
+                Tile::Egg,
+                Tile::EggHatching,
+                Tile::HatchedBeast,
+            ]
+            .iter()
+            .map(tile_code)
+            .collect::<Vec<u8>>(),
+            (0..=8).collect::<Vec<u8>>(),
+            "Every tile has its own code"
+        );
+    }
+
+    #[test]
+    fn level_log_test() {
+        let mut level = beast_level();
+        level
+            .step_input(Some("up"))
+            .expect("The direction is known");
+
+        let log: LevelLog =
+            serde_json::from_str(&level.level_log().expect("The log is serialized"))
+                .expect("The log deserializes into a LevelLog");
+        assert_eq!(log.level, Level::One, "The log is of the level played");
+        assert_eq!(
+            log.board,
+            level.session.log().board,
+            "The log starts from the generated board"
+        );
+        assert_eq!(
+            log.game_log.len(),
+            level.session.log().game_log.len(),
+            "Every move is in the log"
+        );
+    }
+}
@@ -0,0 +1,500 @@
+//! this crate contains the campaign scheduling and leaderboard json shared by the level generators
+//!
+//! A campaign is a list of games that follow each other. Every game is played during its window and
+//! still takes proofs for a submission offset after it, the next game starts when the window ends. A
+//! game only brings the generator of its levels and the encoder of its config, see [GameGenerator].
+
+use std::fmt;
+
+use primitive_types::U256;
+use serde::{Deserialize, Serialize};
+
+pub const DAY_SECONDS: u64 = 24 * 3600;
+pub const WEEK_SECONDS: u64 = 7 * DAY_SECONDS;
+
+/// games last 4 and 3 days in turn unless other windows are set
+pub const DEFAULT_WINDOWS: [u64; 2] = [4 * DAY_SECONDS, 3 * DAY_SECONDS];
+
+/// when a game takes proofs, in seconds since the epoch
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GameWindow {
+    pub starts_at: u64,
+    /// the end of the window plus the submission offset
+    pub ends_at: u64,
+}
+
+/// how the games of a campaign follow each other
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Schedule {
+    /// when the first game starts, in seconds since the epoch
+    pub start_time: u64,
+    /// how many games there are
+    pub games: usize,
+    /// how long the games last in seconds, repeated until every game has one
+    pub windows: Vec<u64>,
+    /// how long after its window a game still takes proofs, in seconds
+    pub submission_offset: u64,
+}
+
+impl Schedule {
+    /// the length of the window of the game
+    pub fn window(&self, game: usize) -> u64 {
+        self.windows[game % self.windows.len()]
+    }
+
+    /// when each game takes proofs
+    pub fn game_windows(&self) -> Vec<GameWindow> {
+        let mut starts_at = self.start_time;
+        (0..self.games)
+            .map(|game| {
+                let window_end = starts_at + self.window(game);
+                let window = GameWindow {
+                    starts_at,
+                    ends_at: window_end + self.submission_offset,
+                };
+                starts_at = window_end;
+                window
+            })
+            .collect()
+    }
+
+    /// check the schedule, games may only overlap during the submission offset of the previous game
+    pub fn validate(&self) -> Result<(), Vec<ScheduleError>> {
+        if self.windows.is_empty() || self.windows.contains(&0) {
+            return Err(vec![ScheduleError::NoWindows]);
+        }
+        validate_windows(&self.game_windows(), self.submission_offset, 0)
+    }
+}
+
+/// how many games it takes to fill the weeks with the windows
+pub fn games_in_weeks(weeks: u64, windows: &[u64]) -> usize {
+    if windows.is_empty() || windows.contains(&0) {
+        return 0;
+    }
+
+    let mut games = 0;
+    let mut seconds = 0;
+    while seconds < weeks * WEEK_SECONDS {
+        seconds += windows[games % windows.len()];
+        games += 1;
+    }
+    games
+}
+
+/// parse a window length like `4d`, `12h` or `30m`, a number without a unit is in days
+pub fn parse_window(window: &str) -> Option<u64> {
+    let (amount, unit) = match window.char_indices().last()? {
+        (idx, 'd') => (&window[..idx], DAY_SECONDS),
+        (idx, 'h') => (&window[..idx], 3600),
+        (idx, 'm') => (&window[..idx], 60),
+        _ => (window, DAY_SECONDS),
+    };
+    amount
+        .parse::<u64>()
+        .ok()
+        .filter(|amount| *amount > 0)
+        .map(|amount| amount * unit)
+}
+
+/// parse a comma separated list of window lengths like `4d,3d`
+pub fn parse_windows(windows: &str) -> Option<Vec<u64>> {
+    windows
+        .split(',')
+        .map(|window| parse_window(window.trim()))
+        .collect()
+}
+
+/// what makes the games of a campaign different from another game's
+pub trait GameGenerator {
+    /// the levels of a single game
+    type Levels;
+
+    /// generate the levels of the next game of the campaign
+    fn generate_levels(&mut self) -> Self::Levels;
+
+    /// encode the levels into the game config the contract stores
+    fn encode_config(&self, levels: &Self::Levels) -> [u8; 32];
+}
+
+/// a game of a campaign with its levels
+#[derive(Debug, Clone)]
+pub struct ScheduledGame<L> {
+    pub window: GameWindow,
+    pub levels: L,
+    pub config: [u8; 32],
+}
+
+impl<L> ScheduledGame<L> {
+    /// the entry of the game in the leaderboard json
+    pub fn leaderboard_game(&self) -> LeaderboardGame {
+        LeaderboardGame::new(&self.window, &self.config)
+    }
+}
+
+/// generate the levels of every game of the schedule, in order
+pub fn generate_campaign<G: GameGenerator>(
+    schedule: &Schedule,
+    generator: &mut G,
+) -> Vec<ScheduledGame<G::Levels>> {
+    schedule
+        .game_windows()
+        .into_iter()
+        .map(|window| {
+            let levels = generator.generate_levels();
+            ScheduledGame {
+                window,
+                config: generator.encode_config(&levels),
+                levels,
+            }
+        })
+        .collect()
+}
+
+/// a time as the 0x prefixed big endian uint256 the contract config takes
+pub fn encode_time(time: u64) -> String {
+    let mut buf = [0u8; 32];
+    U256::from(time).to_big_endian(&mut buf);
+    format!("0x{}", hex::encode(buf))
+}
+
+/// the time of an [encode_time] string, `None` if it is not one or doesn't fit in seconds
+pub fn decode_time(time: &str) -> Option<u64> {
+    let bytes = hex::decode(time.strip_prefix("0x")?).ok()?;
+    if bytes.len() != 32 {
+        return None;
+    }
+    let time = U256::from_big_endian(&bytes);
+    (time <= U256::from(u64::MAX)).then(|| time.as_u64())
+}
+
+/// a game as the leaderboard contract is configured with it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LeaderboardGame {
+    #[serde(rename = "endsAtTime")]
+    pub ends_at_time: String,
+    #[serde(rename = "gameConfig")]
+    pub game_config: String,
+    #[serde(rename = "startsAtTime")]
+    pub starts_at_time: String,
+}
+
+impl LeaderboardGame {
+    pub fn new(window: &GameWindow, config: &[u8; 32]) -> Self {
+        Self {
+            ends_at_time: encode_time(window.ends_at),
+            game_config: format!("0x{}", hex::encode(config)),
+            starts_at_time: encode_time(window.starts_at),
+        }
+    }
+
+    /// the window of the game, `None` if one of the times isn't valid
+    pub fn window(&self) -> Option<GameWindow> {
+        Some(GameWindow {
+            starts_at: decode_time(&self.starts_at_time)?,
+            ends_at: decode_time(&self.ends_at_time)?,
+        })
+    }
+}
+
+/// the games of a campaign for the leaderboard contract config
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Leaderboard {
+    /// the seed the campaign was generated from, if the generator takes one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+    /// the generator that used the seed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub generator: Option<String>,
+    pub games: Vec<LeaderboardGame>,
+    /// the games appended to the campaign after it was generated
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub appended: Vec<AppendedGames>,
+}
+
+impl Leaderboard {
+    /// the leaderboard of the games, without a seed
+    pub fn new<L>(games: &[ScheduledGame<L>]) -> Self {
+        Self {
+            seed: None,
+            generator: None,
+            games: games.iter().map(ScheduledGame::leaderboard_game).collect(),
+            appended: vec![],
+        }
+    }
+}
+
+/// games appended to a campaign, the seed regenerates them from `first_game` on
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AppendedGames {
+    pub first_game: usize,
+    pub seed: u64,
+    pub generator: String,
+}
+
+/// why the games of a campaign don't follow each other
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScheduleError {
+    /// there is no window length or one of them is empty
+    NoWindows,
+    /// the game stops taking proofs before it starts
+    EmptyWindow { game: usize },
+    /// no game takes proofs for a while before the game starts
+    Gap { game: usize, seconds: u64 },
+    /// the game starts while the previous one is still played
+    Overlap { game: usize, seconds: u64 },
+}
+
+impl fmt::Display for ScheduleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ScheduleError::NoWindows => write!(f, "the games need window lengths above zero"),
+            ScheduleError::EmptyWindow { game } => {
+                write!(f, "game {} stops taking proofs before it starts", game)
+            }
+            ScheduleError::Gap { game, seconds } => write!(
+                f,
+                "no game takes proofs for {}s before game {} starts",
+                seconds, game
+            ),
+            ScheduleError::Overlap { game, seconds } => write!(
+                f,
+                "game {} starts {}s before the previous game ends",
+                game, seconds
+            ),
+        }
+    }
+}
+
+/// check that every game starts by the time the previous one stops taking proofs, and not more than
+/// `overlap` seconds before that, the games are numbered from `first_game`
+pub fn validate_windows(
+    windows: &[GameWindow],
+    overlap: u64,
+    first_game: usize,
+) -> Result<(), Vec<ScheduleError>> {
+    let mut errors = vec![];
+
+    for (idx, window) in windows.iter().enumerate() {
+        let game = first_game + idx;
+        if window.ends_at <= window.starts_at {
+            errors.push(ScheduleError::EmptyWindow { game });
+        }
+
+        let Some(previous) = idx.checked_sub(1).map(|previous| windows[previous]) else {
+            continue;
+        };
+        if window.starts_at > previous.ends_at {
+            errors.push(ScheduleError::Gap {
+                game,
+                seconds: window.starts_at - previous.ends_at,
+            });
+        } else if previous.ends_at - window.starts_at > overlap {
+            errors.push(ScheduleError::Overlap {
+                game,
+                seconds: previous.ends_at - window.starts_at,
+            });
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schedule(games: usize) -> Schedule {
+        Schedule {
+            start_time: 1761523200,
+            games,
+            windows: DEFAULT_WINDOWS.to_vec(),
+            submission_offset: 720 * 60,
+        }
+    }
+
+    struct CountingGenerator(u8);
+
+    impl GameGenerator for CountingGenerator {
+        type Levels = u8;
+
+        fn generate_levels(&mut self) -> u8 {
+            self.0 += 1;
+            self.0
+        }
+
+        fn encode_config(&self, levels: &u8) -> [u8; 32] {
+            [*levels; 32]
+        }
+    }
+
+    #[test]
+    fn game_windows_test() {
+        assert_eq!(
+            schedule(3).game_windows(),
+            vec![
+                GameWindow {
+                    starts_at: 1761523200,
+                    ends_at: 1761523200 + 4 * DAY_SECONDS + 720 * 60
+                },
+                GameWindow {
+                    starts_at: 1761523200 + 4 * DAY_SECONDS,
+                    ends_at: 1761523200 + 7 * DAY_SECONDS + 720 * 60
+                },
+                GameWindow {
+                    starts_at: 1761523200 + 7 * DAY_SECONDS,
+                    ends_at: 1761523200 + 11 * DAY_SECONDS + 720 * 60
+                },
+            ],
+            "The windows alternate and take proofs for the offset after them"
+        );
+        assert!(schedule(3).validate().is_ok(), "The schedule is valid");
+    }
+
+    #[test]
+    fn games_in_weeks_test() {
+        assert_eq!(
+            games_in_weeks(5, &DEFAULT_WINDOWS),
+            10,
+            "Two games fill a week"
+        );
+        assert_eq!(
+            games_in_weeks(1, &[DAY_SECONDS]),
+            7,
+            "Daily games fill a week"
+        );
+        assert_eq!(
+            games_in_weeks(1, &[5 * DAY_SECONDS]),
+            2,
+            "The last game may end after the weeks"
+        );
+        assert_eq!(games_in_weeks(1, &[]), 0, "Games need a window");
+    }
+
+    #[test]
+    fn parse_window_test() {
+        assert_eq!(parse_window("4d"), Some(4 * DAY_SECONDS), "Days");
+        assert_eq!(parse_window("12h"), Some(12 * 3600), "Hours");
+        assert_eq!(parse_window("90m"), Some(90 * 60), "Minutes");
+        assert_eq!(
+            parse_window("3"),
+            Some(3 * DAY_SECONDS),
+            "Days without a unit"
+        );
+        assert_eq!(parse_window("0d"), None, "A window can't be empty");
+        assert_eq!(parse_window("4w"), None, "Unknown units are rejected");
+        assert_eq!(parse_window(""), None, "A length is needed");
+        assert_eq!(
+            parse_windows("4d, 12h"),
+            Some(vec![4 * DAY_SECONDS, 12 * 3600]),
+            "A list is split"
+        );
+        assert_eq!(
+            parse_windows("4d,x"),
+            None,
+            "A list with a bad window is rejected"
+        );
+    }
+
+    #[test]
+    fn time_encoding_test() {
+        assert_eq!(
+            encode_time(0x688cd24a),
+            "0x00000000000000000000000000000000000000000000000000000000688cd24a",
+            "Times are 32 big endian bytes"
+        );
+        assert_eq!(
+            decode_time(&encode_time(1761523200)),
+            Some(1761523200),
+            "Encoded times decode again"
+        );
+        assert_eq!(decode_time("0x688cd24a"), None, "Times are 32 bytes");
+        assert_eq!(
+            decode_time(&format!("0x{}", "ff".repeat(32))),
+            None,
+            "Times fit in seconds"
+        );
+    }
+
+    #[test]
+    fn generate_campaign_test() {
+        let games = generate_campaign(&schedule(2), &mut CountingGenerator(0));
+        assert_eq!(
+            games.iter().map(|game| game.levels).collect::<Vec<_>>(),
+            vec![1, 2],
+            "The levels are generated in order"
+        );
+        assert_eq!(
+            games[1].window,
+            schedule(2).game_windows()[1],
+            "Every game gets its window"
+        );
+
+        let json = serde_json::to_value(Leaderboard::new(&games)).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "games": [
+                    {
+                        "endsAtTime": encode_time(games[0].window.ends_at),
+                        "gameConfig": format!("0x{}", "01".repeat(32)),
+                        "startsAtTime": encode_time(games[0].window.starts_at),
+                    },
+                    {
+                        "endsAtTime": encode_time(games[1].window.ends_at),
+                        "gameConfig": format!("0x{}", "02".repeat(32)),
+                        "startsAtTime": encode_time(games[1].window.starts_at),
+                    },
+                ]
+            }),
+            "The leaderboard has the shape of the contract config"
+        );
+        assert_eq!(
+            games[0].leaderboard_game().window(),
+            Some(games[0].window),
+            "The window is read back from the leaderboard"
+        );
+    }
+
+    #[test]
+    fn validate_windows_test() {
+        let window = |starts_at, ends_at| GameWindow { starts_at, ends_at };
+
+        assert!(
+            validate_windows(&[window(0, 100), window(80, 200)], 20, 0).is_ok(),
+            "Games may overlap by the submission offset"
+        );
+        assert!(
+            validate_windows(&[window(0, 100), window(100, 200)], 20, 0).is_ok(),
+            "Games may follow each other right away"
+        );
+        assert_eq!(
+            validate_windows(&[window(0, 100), window(50, 200), window(230, 220)], 20, 4),
+            Err(vec![
+                ScheduleError::Overlap {
+                    game: 5,
+                    seconds: 50
+                },
+                ScheduleError::EmptyWindow { game: 6 },
+                ScheduleError::Gap {
+                    game: 6,
+                    seconds: 30
+                },
+            ]),
+            "Every problem is reported with the number of its game"
+        );
+        assert_eq!(
+            Schedule {
+                windows: vec![],
+                ..schedule(2)
+            }
+            .validate(),
+            Err(vec![ScheduleError::NoWindows]),
+            "A schedule needs windows"
+        );
+    }
+}
@@ -1,22 +1,11 @@
 use std::{fs::File, io::Write};
 
-use primitive_types::U256;
+use campaign::{
+    games_in_weeks, generate_campaign, GameGenerator, Leaderboard, Schedule, DEFAULT_WINDOWS,
+};
 use rand::Rng;
 use serde::{Deserialize, Serialize};
 
-#[derive(Deserialize, Serialize, Debug)]
-#[allow(non_snake_case)]
-struct GameEntry {
-    endsAtTime: String,
-    gameConfig: String,
-    startsAtTime: String,
-}
-
-#[derive(Deserialize, Serialize, Debug)]
-struct Game {
-    games: Vec<GameEntry>,
-}
-
 #[derive(Deserialize, Serialize, Debug)]
 enum Movement {
     Up,
@@ -34,7 +23,7 @@ struct ParityLevel {
     solution: Vec<Movement>,
 }
 
-fn encode_parity_levels(levels: Vec<ParityLevel>) -> [u8; 32] {
+fn encode_parity_levels(levels: &[ParityLevel]) -> [u8; 32] {
     let mut bytes: [u8; 32] = [0; 32];
     // Because each level takes 10 bytes we use a total of 30 bytes
     // we pad the bytes with two leading zeroes
@@ -172,6 +161,32 @@ fn gen_levels(
     levels
 }
 
+/// the parity levels of a campaign
+struct ParityLevels {
+    min_end_of_level: u8,
+    max_end_of_level: u8,
+    min_movements: u8,
+    max_movements: u8,
+}
+
+impl GameGenerator for ParityLevels {
+    type Levels = Vec<ParityLevel>;
+
+    fn generate_levels(&mut self) -> Vec<ParityLevel> {
+        gen_levels(
+            3,
+            self.min_end_of_level,
+            self.max_end_of_level,
+            self.min_movements,
+            self.max_movements,
+        )
+    }
+
+    fn encode_config(&self, levels: &Vec<ParityLevel>) -> [u8; 32] {
+        encode_parity_levels(levels)
+    }
+}
+
 fn main() {
     let args: Vec<String> = std::env::args().collect();
     if args.len() != 9 {
@@ -191,52 +206,32 @@ fn main() {
     let network: String = args[7].parse().expect("Invalid network");
     let start_time_utc: u64 = args[8].parse().expect("Invalid start time");
 
-    // Get the time in seconds from the parameter
-    let mut current_timestamp = std::time::Duration::from_secs(start_time_utc).as_secs();
-
-    let four_days_seconds = 4 * 24 * 3600;
-    let three_days_seconds = 3 * 24 * 3600;
-
-    // Generate a sequence of seconds per game alternating between 4 and 3 days
-    let mut seconds_per_game_sequence = vec![];
-    for i in 0..(weeks_amount * 2) {
-        if i % 2 == 0 {
-            seconds_per_game_sequence.push(four_days_seconds);
-        } else {
-            seconds_per_game_sequence.push(three_days_seconds);
+    let schedule = Schedule {
+        start_time: start_time_utc,
+        games: games_in_weeks(weeks_amount, &DEFAULT_WINDOWS),
+        windows: DEFAULT_WINDOWS.to_vec(),
+        submission_offset: submission_offset_minutes * 60,
+    };
+    if let Err(errors) = schedule.validate() {
+        eprintln!("The campaign schedule is not valid:");
+        for error in &errors {
+            eprintln!("  {}", error);
         }
+        std::process::exit(1);
     }
 
-    let num_levels: u8 = 3;
-
-    let mut games: Vec<GameEntry> = vec![];
-    for seconds_per_game in seconds_per_game_sequence {
-        let game_levels = gen_levels(
-            num_levels,
+    let games = generate_campaign(
+        &schedule,
+        &mut ParityLevels {
             min_end_of_level,
             max_end_of_level,
             min_movements,
             max_movements,
-        );
-        let bytes = encode_parity_levels(game_levels);
-        let game_config = format!("0x{}", hex::encode(bytes));
-
-        let mut start_at_time_bytes = [0u8; 32];
-        let mut ends_at_time_bytes = [0u8; 32];
-        U256::from(current_timestamp).to_big_endian(&mut start_at_time_bytes);
-        U256::from(current_timestamp + seconds_per_game + submission_offset_minutes * 60)
-            .to_big_endian(&mut ends_at_time_bytes);
-        current_timestamp = current_timestamp + seconds_per_game;
-
-        games.push(GameEntry {
-            startsAtTime: format!("0x{}", hex::encode(&start_at_time_bytes)),
-            endsAtTime: format!("0x{}", hex::encode(&ends_at_time_bytes)),
-            gameConfig: game_config,
-        });
-    }
+        },
+    );
 
-    let game = Game { games };
-    let json = serde_json::to_string_pretty(&game).expect("Failed to serialize");
+    let leaderboard = Leaderboard::new(&games);
+    let json = serde_json::to_string_pretty(&leaderboard).expect("Failed to serialize");
 
     let mut file =
         File::create(format!("levels/parity_{}.json", network)).expect("Unable to create file");
@@ -29,6 +29,10 @@ import { SocialLinks } from "../../SocialLinks";
 import { useProofStopFlag } from "../../../hooks/useProofStopFlag";
 import { useGasBasedTimeEstimate } from "../../../hooks/useGasBasedTimeEstimate";
 import { useQuestNumber } from "../../../hooks/useQuestNumber";
+import {
+	loadBeastProgram,
+	parseBeastProofFile,
+} from "../../../modules/BeastGame/proofFile";
 
 type Game = {
 	id: "beast" | string;
@@ -188,28 +192,27 @@ export const SubmitProofStep = ({
 		const file = e.target.files?.[0];
 		if (!file) return;
 
-		const buffer = await file.arrayBuffer();
-		const view = new DataView(buffer);
-		const bytes = new Uint8Array(buffer);
-
-		let offset = 0;
-
-		const provingSystemId = bytes.slice(0, 1);
-		const provingSystem = provingSystemByteToName[provingSystemId[0]];
-		setProvingSystem(provingSystem);
-		offset += 1;
-
-		function readChunk(): Uint8Array {
-			const len = view.getUint32(offset, true);
-			offset += 4;
-			const chunk = bytes.slice(offset, offset + len);
-			offset += len;
-			return chunk;
+		const result = parseBeastProofFile(await file.arrayBuffer());
+		if (!result.ok) {
+			addToast({
+				title: "Invalid proof file",
+				desc: result.error,
+				type: "error",
+			});
+			return;
 		}
+		const { provingSystem, proof, publicInputs } = result.file;
+		setProvingSystem(provingSystemByteToName[provingSystem]);
 
-		const proof = readChunk();
-		const proofId = readChunk();
-		const publicInputs = readChunk();
+		const proofId = await loadBeastProgram(result.file);
+		if (!proofId) {
+			addToast({
+				title: "Outdated proof file",
+				desc: "The proof was generated with another version of the game",
+				type: "error",
+			});
+			return;
+		}
 
 		const parsed = parsePublicInputs(publicInputs);
 		if (!parsed) {
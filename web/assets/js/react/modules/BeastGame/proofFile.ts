@@ -0,0 +1,179 @@
+import { bytesToHex, concat, keccak256 } from "viem";
+
+// Reader for the proof files written by beast1984, the layout is documented in
+// games/beast/beast1984/src/proof_file.rs and has to be kept in sync with it.
+// Files written before the versioned format are still read, see parseLegacyProofFile.
+const MAGIC = new Uint8Array([66, 69, 65, 83, 84, 56, 52, 0]); // "BEAST84\0"
+const FORMAT_VERSION = 1;
+const PUBLIC_VALUES_LEN = 96;
+const PROGRAM_PATH = "/artifacts/beast_1984_program";
+
+export type BeastProofHeader = {
+	version: number;
+	provingSystem: number;
+	game: string;
+	network: string;
+	address: `0x${string}`;
+	level: number;
+	timestamp: number;
+	programVkCommitment: `0x${string}`;
+};
+
+export type BeastProofFile = {
+	// null for legacy files, they have no header
+	header: BeastProofHeader | null;
+	provingSystem: number;
+	proof: Uint8Array;
+	publicInputs: Uint8Array;
+	// legacy files carry the program they were proven with
+	program: Uint8Array | null;
+};
+
+export type BeastProofFileResult =
+	| { ok: true; file: BeastProofFile }
+	| { ok: false; error: string };
+
+// Before the versioned format a proof file was the proving system byte followed by the proof, the
+// program and the public values, each prefixed with its length as a little endian u32
+const parseLegacyProofFile = (bytes: Uint8Array): BeastProofFile | null => {
+	const view = new DataView(bytes.buffer, bytes.byteOffset, bytes.byteLength);
+	let offset = 1;
+
+	const readChunk = (): Uint8Array | null => {
+		if (offset + 4 > bytes.length) return null;
+		const len = view.getUint32(offset, true);
+		offset += 4;
+		if (offset + len > bytes.length) return null;
+		const chunk = bytes.slice(offset, offset + len);
+		offset += len;
+		return chunk;
+	};
+
+	if (bytes.length === 0) return null;
+	const proof = readChunk();
+	const program = readChunk();
+	const publicInputs = readChunk();
+	if (!proof || !program || !publicInputs || offset !== bytes.length) {
+		return null;
+	}
+	if (publicInputs.length !== PUBLIC_VALUES_LEN) return null;
+
+	return {
+		header: null,
+		provingSystem: bytes[0],
+		proof,
+		publicInputs,
+		program,
+	};
+};
+
+export const parseBeastProofFile = (
+	buffer: ArrayBuffer
+): BeastProofFileResult => {
+	const bytes = new Uint8Array(buffer);
+	let offset = 0;
+
+	const take = (len: number, field: string): Uint8Array => {
+		if (offset + len > bytes.length) {
+			throw new Error(`The proof file ends inside the ${field}`);
+		}
+		const value = bytes.slice(offset, offset + len);
+		offset += len;
+		return value;
+	};
+	const readNumber = (len: number, field: string) =>
+		take(len, field).reduceRight((value, byte) => value * 256 + byte, 0);
+	const readString = (field: string) =>
+		new TextDecoder().decode(take(take(1, field)[0], field));
+	const readChunk = (field: string) => take(readNumber(4, field), field);
+
+	try {
+		const magic = bytes.slice(0, MAGIC.length);
+		if (
+			magic.length !== MAGIC.length ||
+			magic.some((byte, i) => byte !== MAGIC[i])
+		) {
+			const legacy = parseLegacyProofFile(bytes);
+			return legacy
+				? { ok: true, file: legacy }
+				: { ok: false, error: "This is not a beast1984 proof file" };
+		}
+		offset = MAGIC.length;
+
+		const version = readNumber(2, "format version");
+		if (version !== FORMAT_VERSION) {
+			return {
+				ok: false,
+				error: `Proof file version ${version} is not supported, please update the game`,
+			};
+		}
+
+		const provingSystem = take(1, "proving system")[0];
+		const game = readString("game");
+		const network = readString("network");
+		const address = bytesToHex(take(20, "address"));
+		const level = readNumber(2, "level");
+		const timestamp = readNumber(8, "timestamp");
+		const programVkCommitment = bytesToHex(
+			take(32, "program vk commitment")
+		);
+		const proof = readChunk("proof");
+		const publicInputs = readChunk("public values");
+
+		if (offset !== bytes.length) {
+			return {
+				ok: false,
+				error: "The proof file has unexpected bytes at the end",
+			};
+		}
+		if (publicInputs.length !== PUBLIC_VALUES_LEN) {
+			return { ok: false, error: "The proof file has invalid public values" };
+		}
+
+		return {
+			ok: true,
+			file: {
+				header: {
+					version,
+					provingSystem,
+					game,
+					network,
+					address,
+					level,
+					timestamp,
+					programVkCommitment,
+				},
+				provingSystem,
+				proof,
+				publicInputs,
+				program: null,
+			},
+		};
+	} catch (e) {
+		return { ok: false, error: (e as Error).message };
+	}
+};
+
+const programVkCommitment = (program: Uint8Array, provingSystem: number) =>
+	keccak256(concat([program, new Uint8Array([provingSystem])]));
+
+// The program isn't part of the proof file, it is served as an artifact and has to match the
+// vk commitment in the header. Legacy files are only accepted when they carry the served program.
+export const loadBeastProgram = async (
+	file: BeastProofFile
+): Promise<Uint8Array | null> => {
+	const response = await fetch(PROGRAM_PATH);
+	if (!response.ok) return null;
+
+	const program = new Uint8Array(await response.arrayBuffer());
+	const expected = file.header
+		? file.header.programVkCommitment
+		: programVkCommitment(
+				file.program ?? new Uint8Array(),
+				file.provingSystem
+			);
+
+	return programVkCommitment(program, file.provingSystem) === expected
+		? program
+		: null;
+};